@@ -0,0 +1,602 @@
+//! Command-line client for the REST API of the site.
+//!
+//! ```text
+//! aoc-site-cli login <token> [--url <url>]
+//! aoc-site-cli push <year> <day> <part> <file> [--language <language>] [--visibility <visibility>] [--writeup <file>]
+//! aoc-site-cli standings <year> [--scoring <mode>]
+//! aoc-site-cli pull <year> <day> [--author <username>] [--out <directory>]
+//! ```
+//!
+//! `login` stores the URL of the site and an API token (created in the settings of the site) in
+//! `$XDG_CONFIG_HOME/aoc-site-cli/config.json`. The environment variables `AOC_SITE_URL` and
+//! `AOC_SITE_TOKEN` take precedence over the stored values.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Component, Path, PathBuf},
+};
+
+use aoc_website::{
+    functions::{language_of_extension, Visibility, LANGUAGES},
+    utils::{
+        api::{ApiError, ApiLeaderboard, ApiProfile, ApiSolution, NewSolution, API_V1},
+        scoring::ScoringMode,
+    },
+};
+use reqwest::{header::AUTHORIZATION, Method, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+const USAGE: &str = "\
+Usage:
+    aoc-site-cli login <token> [--url <url>]
+    aoc-site-cli push <year> <day> <part> <file> [--language <language>] [--visibility <visibility>] [--writeup <file>]
+    aoc-site-cli standings <year> [--scoring <mode>]
+    aoc-site-cli pull <year> <day> [--author <username>] [--out <directory>]
+
+Visibilities: public, members, private, delayed:<hours>, after-leaderboard
+Scoring modes: local, stars, median, delta, morning";
+
+const DEFAULT_URL: &str = "http://127.0.0.1:3000";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Login {
+        token: String,
+        url: String,
+    },
+    Push {
+        year: i32,
+        day: u8,
+        part: u8,
+        file: PathBuf,
+        language: Option<String>,
+        visibility: Option<Visibility>,
+        writeup: Option<PathBuf>,
+    },
+    Standings {
+        year: i32,
+        scoring: ScoringMode,
+    },
+    Pull {
+        year: i32,
+        day: u8,
+        author: Option<String>,
+        out: PathBuf,
+    },
+    Help,
+}
+
+/// Parse a visibility in the form `kind` or `delayed:<hours>`.
+fn parse_visibility(visibility: &str) -> Result<Visibility, String> {
+    let (kind, hours) = match visibility.split_once(':') {
+        Some((kind, hours)) => (
+            kind,
+            hours
+                .parse()
+                .map_err(|_| format!("invalid hours in visibility '{visibility}'"))?,
+        ),
+        None => (visibility, 0),
+    };
+
+    Visibility::from_form(kind, hours).ok_or_else(|| format!("unknown visibility '{visibility}'"))
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("missing {name}"))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid {name} '{value}'"))
+}
+
+/// Parse the command line arguments (without the name of the binary).
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut positional = vec![];
+    let mut options = HashMap::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--") {
+            Some("help") => return Ok(Command::Help),
+            Some(option) => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("missing value for --{option}"))?;
+                options.insert(option, value.clone());
+            }
+            None => positional.push(arg.clone()),
+        }
+    }
+
+    let mut option = |name: &str| options.remove(name);
+
+    let command = match positional.first().map(String::as_str) {
+        None | Some("help") => return Ok(Command::Help),
+        Some("login") => Command::Login {
+            token: positional
+                .get(1)
+                .cloned()
+                .ok_or("missing token, create one in the settings of the site")?,
+            url: option("url")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| DEFAULT_URL.to_string()),
+        },
+        Some("push") => Command::Push {
+            year: parse_number("year", positional.get(1))?,
+            day: parse_number("day", positional.get(2))?,
+            part: parse_number("part", positional.get(3))?,
+            file: positional.get(4).map(PathBuf::from).ok_or("missing file")?,
+            language: option("language"),
+            visibility: option("visibility")
+                .map(|visibility| parse_visibility(&visibility))
+                .transpose()?,
+            writeup: option("writeup").map(PathBuf::from),
+        },
+        Some("standings") => Command::Standings {
+            year: parse_number("year", positional.get(1))?,
+            scoring: match option("scoring") {
+                Some(scoring) => scoring
+                    .parse()
+                    .map_err(|_| format!("unknown scoring mode '{scoring}'"))?,
+                None => ScoringMode::default(),
+            },
+        },
+        Some("pull") => Command::Pull {
+            year: parse_number("year", positional.get(1))?,
+            day: parse_number("day", positional.get(2))?,
+            author: option("author"),
+            out: option("out")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(".")),
+        },
+        Some(command) => return Err(format!("unknown command '{command}'")),
+    };
+
+    if let Some(option) = options.keys().next() {
+        return Err(format!("unknown option --{option}"));
+    }
+
+    Ok(command)
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Config {
+    url: Option<String>,
+    token: Option<String>,
+}
+
+fn config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(base.join("aoc-site-cli").join("config.json"))
+}
+
+impl Config {
+    /// Load the stored configuration, overridden by the environment.
+    fn load() -> Config {
+        let stored: Config = config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+
+        Config {
+            url: std::env::var("AOC_SITE_URL").ok().or(stored.url),
+            token: std::env::var("AOC_SITE_TOKEN").ok().or(stored.token),
+        }
+    }
+
+    fn save(&self) -> Result<PathBuf, String> {
+        let path = config_path().ok_or("cannot determine the configuration directory")?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("failed to create {parent:?}: {e}"))?;
+        }
+
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+
+        // the token grants access to the account, so nobody else may read it, not even briefly
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            options.mode(0o600);
+
+            // files created before are not affected by the mode
+            if path.exists() {
+                fs::set_permissions(&path, fs::Permissions::from_mode(0o600))
+                    .map_err(|e| format!("failed to restrict permissions of {path:?}: {e}"))?;
+            }
+        }
+
+        options
+            .open(&path)
+            .and_then(|mut file| file.write_all(json.as_bytes()))
+            .map_err(|e| format!("failed to write {path:?}: {e}"))?;
+
+        Ok(path)
+    }
+
+    fn client(self) -> Result<Client, String> {
+        let token = self
+            .token
+            .ok_or("not logged in, run `aoc-site-cli login <token>` first")?;
+
+        Ok(Client {
+            url: self.url.unwrap_or_else(|| DEFAULT_URL.to_string()),
+            token,
+            http: reqwest::Client::new(),
+        })
+    }
+}
+
+struct Client {
+    url: String,
+    token: String,
+    http: reqwest::Client,
+}
+
+impl Client {
+    /// Send a request to the REST API and return the status together with the body of a
+    /// successful response.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<String>,
+    ) -> Result<(StatusCode, String), String> {
+        let mut request = self
+            .http
+            .request(method, format!("{}{API_V1}{path}", self.url))
+            .query(query)
+            .header(AUTHORIZATION, format!("Bearer {}", self.token));
+
+        if let Some(body) = body {
+            request = request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("request failed: {e}"))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("failed to read response: {e}"))?;
+
+        if !status.is_success() {
+            return Err(match serde_json::from_str::<ApiError>(&body) {
+                Ok(ApiError { error }) => format!("{error} ({status})"),
+                Err(_) => format!("request failed with {status}"),
+            });
+        }
+
+        Ok((status, body))
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, String> {
+        let (_, body) = self.send(Method::GET, path, query, None).await?;
+
+        serde_json::from_str(&body).map_err(|e| format!("unexpected response: {e}"))
+    }
+}
+
+async fn login(token: String, url: String) -> Result<(), String> {
+    let client = Config {
+        url: Some(url.clone()),
+        token: Some(token.clone()),
+    }
+    .client()?;
+
+    let profile: ApiProfile = client.get("/profile", &[]).await?;
+
+    let path = Config {
+        url: Some(url),
+        token: Some(token),
+    }
+    .save()?;
+
+    println!("Logged in as {}, stored in {path:?}", profile.username);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn push(
+    client: Client,
+    year: i32,
+    day: u8,
+    part: u8,
+    file: &Path,
+    language: Option<String>,
+    visibility: Option<Visibility>,
+    writeup: Option<&Path>,
+) -> Result<(), String> {
+    let language = match language {
+        Some(language) => language,
+        None => file
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(language_of_extension)
+            .map(|language| language.to_string())
+            .ok_or_else(|| {
+                format!(
+                    "cannot tell the language of {file:?}, pass --language (one of {})",
+                    LANGUAGES.join(", ")
+                )
+            })?,
+    };
+
+    let code = fs::read_to_string(file).map_err(|e| format!("failed to read {file:?}: {e}"))?;
+    let writeup = writeup
+        .map(|writeup| {
+            fs::read_to_string(writeup).map_err(|e| format!("failed to read {writeup:?}: {e}"))
+        })
+        .transpose()?;
+
+    let solution = NewSolution {
+        year,
+        day,
+        part,
+        language: language.clone(),
+        code,
+        writeup,
+        visibility,
+    };
+    let body = serde_json::to_string(&solution).map_err(|e| e.to_string())?;
+
+    client
+        .send(Method::POST, "/solutions", &[], Some(body))
+        .await?;
+
+    println!("Submitted {year}/{day} part {part} ({language})");
+    Ok(())
+}
+
+async fn standings(client: Client, year: i32, scoring: ScoringMode) -> Result<(), String> {
+    let leaderboard: ApiLeaderboard = client
+        .get(
+            &format!("/leaderboard/{year}"),
+            &[("scoring", scoring.key())],
+        )
+        .await?;
+
+    let Some(fetched_at) = leaderboard.fetched_at else {
+        println!("There is no leaderboard for {year} yet");
+        return Ok(());
+    };
+
+    println!("Leaderboard {year} ({scoring}), as of {fetched_at}");
+    for standing in leaderboard.standings {
+        let name = match standing.username {
+            Some(username) => format!("{} ({username})", standing.name),
+            None => standing.name,
+        };
+
+        println!(
+            "{rank:>4}  {name:<40} {score:>12} {stars:>4}*",
+            rank = standing.rank,
+            score = standing.score_label,
+            stars = standing.stars
+        );
+    }
+
+    Ok(())
+}
+
+/// Whether the name can be used as a single component of a path, i.e., it neither contains
+/// separators (or drive prefixes on Windows) nor refers to the current or parent directory.
+fn is_path_segment(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+
+    !name.contains(['/', '\\', ':'])
+        && matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        )
+}
+
+/// Download the solutions of others for a day into `<out>/<author>/`. Only possible once the
+/// owner of the token submitted a solution for the day themselves.
+async fn pull(
+    client: Client,
+    year: i32,
+    day: u8,
+    author: Option<String>,
+    out: &Path,
+) -> Result<(), String> {
+    let profile: ApiProfile = client.get("/profile", &[]).await?;
+
+    // the server refuses to hand out the solutions of others before that
+    let query = match &author {
+        Some(author) => vec![("author", author.as_str())],
+        None => vec![],
+    };
+    let solutions: Vec<ApiSolution> = client
+        .get(&format!("/solutions/{year}/{day}"), &query)
+        .await?;
+
+    let mut pulled = 0;
+    for solution in solutions
+        .iter()
+        .filter(|solution| solution.author != profile.username)
+    {
+        // the name comes from the server and must not lead outside of the output directory
+        if !is_path_segment(&solution.author) {
+            eprintln!(
+                "skipping solution of '{}', the name cannot be used as a directory",
+                solution.author
+            );
+            continue;
+        }
+
+        let directory = out.join(&solution.author);
+        fs::create_dir_all(&directory)
+            .map_err(|e| format!("failed to create {directory:?}: {e}"))?;
+
+        let file = directory.join(solution.file_name());
+        fs::write(&file, &solution.code).map_err(|e| format!("failed to write {file:?}: {e}"))?;
+
+        println!("{}", file.display());
+        pulled += 1;
+    }
+
+    if pulled == 0 {
+        println!("No solutions of others visible for {year}/{day}");
+    }
+
+    Ok(())
+}
+
+async fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Command::Login { token, url } => login(token, url).await,
+        Command::Push {
+            year,
+            day,
+            part,
+            file,
+            language,
+            visibility,
+            writeup,
+        } => {
+            push(
+                Config::load().client()?,
+                year,
+                day,
+                part,
+                &file,
+                language,
+                visibility,
+                writeup.as_deref(),
+            )
+            .await
+        }
+        Command::Standings { year, scoring } => {
+            standings(Config::load().client()?, year, scoring).await
+        }
+        Command::Pull {
+            year,
+            day,
+            author,
+            out,
+        } => pull(Config::load().client()?, year, day, author, &out).await,
+    }
+}
+
+#[actix_web::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match parse_args(&args) {
+        Ok(command) => run(command).await,
+        Err(e) => Err(format!("{e}\n\n{USAGE}")),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_push() {
+        assert_eq!(
+            parse_args(&args(
+                "push 2023 1 2 src/day01.rs --visibility delayed:6 --language rust"
+            )),
+            Ok(Command::Push {
+                year: 2023,
+                day: 1,
+                part: 2,
+                file: PathBuf::from("src/day01.rs"),
+                language: Some("rust".to_string()),
+                visibility: Some(Visibility::Delayed(6)),
+                writeup: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_defaults() {
+        assert_eq!(
+            parse_args(&args("login aoc_123")),
+            Ok(Command::Login {
+                token: "aoc_123".to_string(),
+                url: DEFAULT_URL.to_string(),
+            })
+        );
+        assert_eq!(
+            parse_args(&args("standings 2023")),
+            Ok(Command::Standings {
+                year: 2023,
+                scoring: ScoringMode::LocalScore,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("pull 2023 5")),
+            Ok(Command::Pull {
+                year: 2023,
+                day: 5,
+                author: None,
+                out: PathBuf::from("."),
+            })
+        );
+        assert_eq!(parse_args(&[]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&args("push 2023 one 1 a.rs")).is_err());
+        assert!(parse_args(&args("push 2023 1 1")).is_err());
+        assert!(parse_args(&args("standings 2023 --scoring fastest")).is_err());
+        assert!(parse_args(&args("pull 2023 1 --unknown x")).is_err());
+        assert!(parse_args(&args("pull 2023 1 --out")).is_err());
+        assert!(parse_args(&args("deploy")).is_err());
+    }
+
+    #[test]
+    fn test_is_path_segment() {
+        assert!(is_path_segment("agent"));
+        assert!(is_path_segment("agent.smith"));
+        assert!(!is_path_segment(""));
+        assert!(!is_path_segment("."));
+        assert!(!is_path_segment(".."));
+        assert!(!is_path_segment("../../.ssh"));
+        assert!(!is_path_segment("/etc"));
+        assert!(!is_path_segment("a/b"));
+        assert!(!is_path_segment("a\\b"));
+        assert!(!is_path_segment("C:"));
+    }
+
+    #[test]
+    fn test_parse_visibility() {
+        assert_eq!(parse_visibility("members"), Ok(Visibility::Members));
+        assert_eq!(parse_visibility("delayed:12"), Ok(Visibility::Delayed(12)));
+        assert!(parse_visibility("delayed:soon").is_err());
+        assert!(parse_visibility("secret").is_err());
+    }
+}
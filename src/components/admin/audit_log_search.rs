@@ -0,0 +1,49 @@
+use leptos::*;
+use leptos_router::*;
+
+use crate::{
+    components::AuditLogTable,
+    functions::{get_audit_log, AuditEvent},
+};
+
+/// Audit log of all accounts, filterable by username and kind of event via the query string.
+#[component]
+pub fn AuditLogSearch() -> impl IntoView {
+    let query = use_query_map();
+    let username = move || query.with(|query| query.get("audit_user").cloned().unwrap_or_default());
+    let kind = move || query.with(|query| query.get("audit_kind").cloned().unwrap_or_default());
+
+    let entries = create_resource(
+        move || (username(), kind()),
+        |(username, kind)| get_audit_log(username, kind),
+    );
+
+    view! {
+        <h2>"Audit Log"</h2>
+        <div class="audit-log-search">
+            <Form method="GET" action="">
+                <input type="text" name="audit_user" placeholder="Username" value=username/>
+                <select name="audit_kind">
+                    <option value="" selected=move || kind().is_empty()>"All events"</option>
+                    {AuditEvent::KINDS
+                        .into_iter()
+                        .map(|option| view! {
+                            <option value=option selected=move || kind() == option>{option}</option>
+                        })
+                        .collect_view()}
+                </select>
+                <input type="submit" value="Search"/>
+            </Form>
+        </div>
+        <Transition fallback=move || ()>
+            {move || entries.get().map(|entries| match entries {
+                Ok(Some(entries)) if !entries.is_empty() => view! {
+                    <AuditLogTable entries=entries show_user=true/>
+                }.into_view(),
+                Ok(Some(_)) => view! { <p>"No matching entries"</p> }.into_view(),
+                Ok(None) => ().into_view(),
+                Err(_) => view! { <p>"Failed to load the audit log"</p> }.into_view(),
+            })}
+        </Transition>
+    }
+}
@@ -0,0 +1,54 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::functions::{
+    get_leaderboard_config, AdminResult, LeaderboardConfig, SetLeaderboardConfig,
+};
+
+/// Form for the URL and poll interval of the private leaderboard.
+#[component]
+pub fn LeaderboardConfigForm() -> impl IntoView {
+    let save = create_server_action::<SetLeaderboardConfig>();
+
+    let config = create_resource(move || save.version().get(), |_| get_leaderboard_config());
+
+    let message = move || {
+        save.value().get().map(|result| {
+            result
+                .unwrap_or(AdminResult::InternalServerError)
+                .to_string()
+        })
+    };
+
+    view! {
+        <Transition fallback=move || ()>
+            {move || config.get().map(|config| match config {
+                Ok(Some(LeaderboardConfig { url, poll_interval })) => view! {
+                    <ActionForm action=save class="leaderboard-config">
+                        <p class="message">{message}</p>
+                        <p>"Empty fields fall back to the environment of the server."</p>
+                        <label>
+                            <span>"Leaderboard URL"</span>
+                            <input
+                                type="text"
+                                name="url"
+                                value=url.unwrap_or_default()
+                                placeholder="https://adventofcode.com/{year}/leaderboard/private/view/123456.json"
+                            />
+                        </label>
+                        <label>
+                            <span>"Poll interval (minutes)"</span>
+                            <input
+                                type="number"
+                                name="poll_interval"
+                                value=poll_interval.map(|minutes| minutes.to_string()).unwrap_or_default()
+                            />
+                        </label>
+                        <button type="submit" class="primary">"Save"</button>
+                    </ActionForm>
+                }.into_view(),
+                _ => ().into_view(),
+            })}
+        </Transition>
+    }
+}
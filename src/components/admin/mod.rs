@@ -0,0 +1,9 @@
+mod audit_log_search;
+mod leaderboard_config_form;
+mod moderation_queue;
+mod user_table;
+
+pub use self::audit_log_search::*;
+pub use self::leaderboard_config_form::*;
+pub use self::moderation_queue::*;
+pub use self::user_table::*;
@@ -0,0 +1,128 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::functions::{
+    get_moderation_log, get_moderation_queue, ModerateSolution, ModerationAction, ModerationItem,
+    ModerationLogEntry, ModerationResult, ReportEntry, MAX_REASON_LENGTH,
+};
+
+/// Reported and hidden solutions together with the log of recent moderation actions.
+#[component]
+pub fn ModerationQueue() -> impl IntoView {
+    let moderate = create_server_action::<ModerateSolution>();
+
+    let queue = create_resource(move || moderate.version().get(), |_| get_moderation_queue());
+    let log = create_resource(move || moderate.version().get(), |_| get_moderation_log());
+
+    let message = move || {
+        moderate.value().get().map(|result| {
+            result
+                .unwrap_or(ModerationResult::InternalServerError)
+                .to_string()
+        })
+    };
+
+    view! {
+        <h2>"Moderation"</h2>
+        <p class="message">{message}</p>
+        <Transition fallback=move || ()>
+            {move || queue.get().map(|queue| match queue {
+                Ok(Some(queue)) if !queue.is_empty() => view! {
+                    <ul class="moderation-queue">
+                        {queue
+                            .into_iter()
+                            .map(|ModerationItem { solution, author, year, day, part, language, code, hidden_reason, reports }| {
+                                let hidden = hidden_reason.is_some();
+
+                                view! {
+                                    <li>
+                                        <h3>
+                                            <a href=format!("/code/{author}?year={year}&day={day}")>
+                                                {format!("{author} {year} Day {day} Part {part}")}
+                                            </a>
+                                        </h3>
+                                        {hidden_reason.map(|reason| view! {
+                                            <p class="hidden-notice">"Hidden: " {reason}</p>
+                                        })}
+                                        <ul class="reports">
+                                            {reports
+                                                .into_iter()
+                                                .map(|ReportEntry { reporter, reason, created_at }| view! {
+                                                    <li>
+                                                        <span class="reporter">
+                                                            {reporter.unwrap_or_else(|| "Deleted user".to_string())}
+                                                            ", " {created_at} ": "
+                                                        </span>
+                                                        {reason}
+                                                    </li>
+                                                })
+                                                .collect_view()}
+                                        </ul>
+                                        <details>
+                                            <summary>"Code"</summary>
+                                            <pre>
+                                                <code class=format!("language-{language}")>{code}</code>
+                                            </pre>
+                                        </details>
+                                        <ActionForm action=moderate>
+                                            <input type="hidden" name="solution" value=solution/>
+                                            <select name="action">
+                                                {ModerationAction::ALL
+                                                    .into_iter()
+                                                    .filter(|action| match action {
+                                                        ModerationAction::Hide => !hidden,
+                                                        ModerationAction::Restore => hidden,
+                                                        _ => true,
+                                                    })
+                                                    .map(|action| view! {
+                                                        <option value=action.key()>{action.to_string()}</option>
+                                                    })
+                                                    .collect_view()}
+                                            </select>
+                                            <input
+                                                type="text"
+                                                name="reason"
+                                                placeholder="Reason"
+                                                maxlength=MAX_REASON_LENGTH
+                                                required
+                                            />
+                                            <button type="submit">"Apply"</button>
+                                        </ActionForm>
+                                    </li>
+                                }
+                            })
+                            .collect_view()}
+                    </ul>
+                }.into_view(),
+                Ok(Some(_)) => view! { <p>"Nothing to moderate"</p> }.into_view(),
+                Ok(None) => ().into_view(),
+                Err(_) => view! { <p>"Failed to load the moderation queue"</p> }.into_view(),
+            })}
+        </Transition>
+        <h3>"Recent actions"</h3>
+        <Transition fallback=move || ()>
+            {move || log.get().map(|log| match log {
+                Ok(Some(log)) if !log.is_empty() => view! {
+                    <table class="moderation-log">
+                        <tbody>
+                            {log
+                                .into_iter()
+                                .map(|ModerationLogEntry { moderator, action, subject, reason, created_at }| view! {
+                                    <tr>
+                                        <td>{created_at}</td>
+                                        <td>{moderator}</td>
+                                        <td>{action.to_string()}</td>
+                                        <td>{subject}</td>
+                                        <td>{reason}</td>
+                                    </tr>
+                                })
+                                .collect_view()}
+                        </tbody>
+                    </table>
+                }.into_view(),
+                Ok(_) => view! { <p>"No actions yet"</p> }.into_view(),
+                Err(_) => view! { <p>"Failed to load the moderation log"</p> }.into_view(),
+            })}
+        </Transition>
+    }
+}
@@ -0,0 +1,114 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::functions::{
+    get_admin_users, AdminResendVerification, AdminResult, AdminUser, AdminVerifyEmail, Role,
+    SetUserDisabled, SetUserRole,
+};
+
+/// All users with controls for their role, email verification and whether they are disabled.
+#[component]
+pub fn UserTable() -> impl IntoView {
+    let set_role = create_server_action::<SetUserRole>();
+    let set_disabled = create_server_action::<SetUserDisabled>();
+    let resend_verification = create_server_action::<AdminResendVerification>();
+    let verify_email = create_server_action::<AdminVerifyEmail>();
+
+    let users = create_resource(
+        move || {
+            (
+                set_role.version().get(),
+                set_disabled.version().get(),
+                verify_email.version().get(),
+            )
+        },
+        |_| get_admin_users(),
+    );
+
+    let message = move || {
+        set_role
+            .value()
+            .get()
+            .or_else(|| set_disabled.value().get())
+            .or_else(|| resend_verification.value().get())
+            .or_else(|| verify_email.value().get())
+            .map(|result| {
+                result
+                    .unwrap_or(AdminResult::InternalServerError)
+                    .to_string()
+            })
+    };
+
+    view! {
+        <h2>"Users"</h2>
+        <p class="message">{message}</p>
+        <Transition fallback=move || ()>
+            {move || users.get().map(|users| match users {
+                Ok(Some(users)) => view! {
+                    <table class="user-table">
+                        <thead>
+                            <tr>
+                                <th>"User"</th>
+                                <th>"Email"</th>
+                                <th>"Role"</th>
+                                <th>"Status"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {users
+                                .into_iter()
+                                .map(|AdminUser { username, email, email_verified, role, disabled, deletion_requested }| view! {
+                                    <tr class:disabled=disabled>
+                                        <td><a href=format!("/u/{username}")>{username.clone()}</a></td>
+                                        <td>
+                                            {email}
+                                            {(!email_verified).then(|| view! {
+                                                <span class="unverified">" (unverified)"</span>
+                                                <ActionForm action=resend_verification>
+                                                    <input type="hidden" name="username" value=username.clone()/>
+                                                    <button type="submit">"Resend mail"</button>
+                                                </ActionForm>
+                                                <ActionForm action=verify_email>
+                                                    <input type="hidden" name="username" value=username.clone()/>
+                                                    <button type="submit">"Mark verified"</button>
+                                                </ActionForm>
+                                            })}
+                                        </td>
+                                        <td>
+                                            <ActionForm action=set_role>
+                                                <input type="hidden" name="username" value=username.clone()/>
+                                                <select name="role">
+                                                    {Role::ALL
+                                                        .into_iter()
+                                                        .map(|option| view! {
+                                                            <option value=option.key() selected=option == role>{option.to_string()}</option>
+                                                        })
+                                                        .collect_view()}
+                                                </select>
+                                                <button type="submit">"Save"</button>
+                                            </ActionForm>
+                                        </td>
+                                        <td>
+                                            {deletion_requested.then(|| view! { <span>"Deletion requested "</span> })}
+                                            <ActionForm action=set_disabled>
+                                                <input type="hidden" name="username" value=username.clone()/>
+                                                <input type="hidden" name="disabled" value=(!disabled).to_string()/>
+                                                {if disabled {
+                                                    view! { <button type="submit">"Enable"</button> }
+                                                } else {
+                                                    view! { <button type="submit" class="danger">"Disable"</button> }
+                                                }}
+                                            </ActionForm>
+                                        </td>
+                                    </tr>
+                                })
+                                .collect_view()}
+                        </tbody>
+                    </table>
+                }.into_view(),
+                Ok(None) => view! { <p>"You are not allowed to manage users."</p> }.into_view(),
+                Err(_) => view! { <p>"Failed to load the users"</p> }.into_view(),
+            })}
+        </Transition>
+    }
+}
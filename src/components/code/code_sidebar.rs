@@ -1,63 +1,94 @@
+use chrono::Utc;
 use leptos::*;
 use leptos_router::*;
 
-use crate::components::Sidebar;
+use crate::{
+    components::{Avatar, Sidebar},
+    functions::{get_day_overview, AuthorOverview},
+    hooks::use_selected_day,
+    utils::calendar::unlocked_days,
+};
 
 #[component]
-pub fn CodeSidebar() -> impl IntoView {
+pub fn CodeSidebar(
+    /// Changes whenever reactions or votes of the selected day change.
+    #[prop(into)]
+    version: Signal<usize>,
+) -> impl IntoView {
     let params = use_params_map();
     let user = move || params.with(|params| params.get("user").cloned().unwrap_or_default());
 
-    let names = vec![
-        "h1ghbre4k3r",
-        "dobiko",
-        "dormanil",
-        "maclement",
-        "melf",
-        "zihark",
-        "sebfisch",
-        "xtay2",
-        "estugon",
-        "fwcd",
-        "b3z",
-        "felioh",
-        "h1tchhiker",
-        "hendrick404",
-        "tuhhy",
-        "yorick",
-        "skgland",
-    ];
+    let (year, day) = use_selected_day();
+    let location = use_location();
+    let navigate = use_navigate();
 
-    let (users, _) = create_signal(names);
+    let overview = create_resource(
+        move || (year(), day(), version()),
+        |(year, day, _)| get_day_overview(year, day),
+    );
+
+    let on_day_change = move |ev| {
+        navigate(
+            &format!(
+                "{path}?year={year}&day={day}",
+                path = location.pathname.get_untracked(),
+                year = year.get_untracked(),
+                day = event_target_value(&ev)
+            ),
+            Default::default(),
+        );
+    };
 
     view! {
         <Sidebar>
             <header><h3>Users</h3></header>
             <div class="day">
                 <label for="day-select">Day</label>
-                <select name="day" id="day-select">
-                    <option value="1">1</option>
-                    <option value="2">2</option>
-                    <option value="3">3</option>
-                    <option value="4">4</option>
-                    <option value="5">5</option>
-                    <option value="6">6</option>
-                    <option value="7">7</option>
-                    <option value="8">8</option>
+                <select name="day" id="day-select" on:change=on_day_change>
+                    {move || (1..=unlocked_days(year(), Utc::now()).max(1))
+                        .map(|option| view! {
+                            <option value=option selected=move || option == day()>{option}</option>
+                        })
+                        .collect_view()}
                 </select>
+                <a href=move || format!("/stats/{}/{}", year(), day())>"Statistics"</a>
             </div>
-            <ul>
-                <For each=users key=|name| name.to_owned() children=move |name| {
-                    let is_active = move || name == user();
-                    let link = move || format!("/code/{name}");
+            <Transition fallback=move || ()>
+                {move || overview.get().map(|overview| match overview {
+                    Ok(overview) if !overview.is_empty() => view! {
+                        <ul>
+                            {overview
+                                .into_iter()
+                                .map(|AuthorOverview { author, reactions, votes, favourite }| {
+                                    let is_active = {
+                                        let author = author.clone();
+                                        move || author == user()
+                                    };
+                                    let link = format!("/code/{author}?year={year}&day={day}", year = year(), day = day());
 
-                    view! {
-                        <li>
-                            <a href=link class:active=is_active>{name}</a>
-                        </li>
-                    }
-                }/>
-            </ul>
+                                    view! {
+                                        <li>
+                                            <a href=link class:active=is_active>
+                                                <Avatar username=author.clone()/>
+                                                {author}
+                                                <span class="badges">
+                                                    {favourite.then(|| view! {
+                                                        <span class="badge favourite" title="Favourite solution of the day">"🏆"</span>
+                                                    })}
+                                                    <span class="badge" title="Reactions">{reactions} " 👍"</span>
+                                                    <span class="badge" title="Votes">{votes} " ★"</span>
+                                                </span>
+                                            </a>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                    Ok(_) => view! { <p>"No solutions yet"</p> }.into_view(),
+                    Err(_) => view! { <p>"Failed to load solutions"</p> }.into_view(),
+                })}
+            </Transition>
         </Sidebar>
     }
 }
@@ -1,3 +1,9 @@
 mod code_sidebar;
+mod solution_snippet;
+mod submit_solution;
+mod visibility_select;
 
 pub use self::code_sidebar::*;
+pub use self::solution_snippet::*;
+pub use self::submit_solution::*;
+pub use self::visibility_select::*;
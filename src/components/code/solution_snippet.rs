@@ -0,0 +1,149 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::{
+    components::VisibilitySelect,
+    functions::{
+        ReactionCount, ReactionResult, ReportResult, ReportSolution, SetSolutionVisibility,
+        SolutionData, ToggleReaction, VoteResult, VoteSolution, MAX_REASON_LENGTH,
+    },
+};
+
+#[component]
+pub fn SolutionSnippet(
+    solution: SolutionData,
+    react: Action<ToggleReaction, Result<ReactionResult, ServerFnError>>,
+    vote: Action<VoteSolution, Result<VoteResult, ServerFnError>>,
+    #[prop(optional)] open: bool,
+) -> impl IntoView {
+    let SolutionData {
+        id,
+        author,
+        year,
+        day,
+        part,
+        language,
+        code,
+        writeup_html,
+        visibility,
+        own,
+        reactions,
+        votes,
+        voted,
+        favourite,
+        hidden_reason,
+        ..
+    } = solution;
+
+    let reactions = reactions
+        .into_iter()
+        .map(
+            |ReactionCount {
+                 reaction,
+                 count,
+                 reacted,
+             }| {
+                let solution = id.clone();
+                let on_click = move |_| {
+                    react.dispatch(ToggleReaction {
+                        solution: solution.clone(),
+                        reaction,
+                    })
+                };
+
+                view! {
+                    <button class="reaction" class:active=reacted on:click=on_click>
+                        {reaction.emoji()}
+                        {(count != 0).then(|| view! { <span class="count">{count}</span> })}
+                    </button>
+                }
+            },
+        )
+        .collect_view();
+
+    let set_visibility = create_server_action::<SetSolutionVisibility>();
+    let visibility_message = move || match set_visibility.value().get() {
+        Some(Ok(result)) => result.to_string(),
+        Some(Err(e)) => e.to_string(),
+        None => "".to_string(),
+    };
+
+    let visibility_form = own.then(|| {
+        let solution = id.clone();
+
+        view! {
+            <ActionForm action=set_visibility class="solution-visibility">
+                <input type="hidden" name="solution" value=solution/>
+                <VisibilitySelect selected=visibility empty_option="Use my default visibility"/>
+                <button type="submit">"Save"</button>
+                <span class="message">{visibility_message}</span>
+            </ActionForm>
+        }
+    });
+
+    let report = create_server_action::<ReportSolution>();
+    let report_message = move || {
+        report.value().get().map(|result| {
+            result
+                .unwrap_or(ReportResult::InternalServerError)
+                .to_string()
+        })
+    };
+
+    let report_form = (!own).then(|| {
+        let solution = id.clone();
+
+        view! {
+            <details class="report">
+                <summary>"Report"</summary>
+                <ActionForm action=report>
+                    <p class="message">{report_message}</p>
+                    <input type="hidden" name="solution" value=solution/>
+                    <label>
+                        <span>"Reason"</span>
+                        <textarea name="reason" rows="3" maxlength=MAX_REASON_LENGTH required></textarea>
+                    </label>
+                    <button type="submit">"Send report"</button>
+                </ActionForm>
+            </details>
+        }
+    });
+
+    let raw_link = format!("/raw/{year}/{day}/{author}/{part}");
+
+    let on_vote = move |_| {
+        vote.dispatch(VoteSolution {
+            solution: id.clone(),
+        })
+    };
+
+    view! {
+        <div class="code-snippet">
+            <details open=open>
+                <summary>
+                    {author} " Part " {part}
+                    <Show when=move || favourite fallback=|| ()>
+                        <span class="badge favourite" title="Favourite solution of the day">"🏆"</span>
+                    </Show>
+                </summary>
+                {hidden_reason.map(|reason| view! {
+                    <p class="hidden-notice">"Hidden by a moderator: " {reason}</p>
+                })}
+                {writeup_html.map(|html| view! { <div class="writeup" inner_html=html></div> })}
+                <pre>
+                    <code class=format!("language-{language}")>{code.trim().to_string()}</code>
+                </pre>
+                {visibility_form}
+                {report_form}
+            </details>
+            <div class="reactions">
+                <a class="raw" href=raw_link rel="external">"Raw"</a>
+                {reactions}
+                <button class="vote" class:active=voted on:click=on_vote>
+                    "★ Favourite"
+                    <span class="count">{votes}</span>
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,70 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::{
+    components::VisibilitySelect,
+    functions::{SubmissionResult, SubmitSolution, LANGUAGES},
+};
+
+#[component]
+pub fn SubmitSolutionForm(
+    action: Action<SubmitSolution, Result<SubmissionResult, ServerFnError>>,
+    #[prop(into)] year: Signal<i32>,
+    #[prop(into)] day: Signal<u8>,
+) -> impl IntoView {
+    let result = move || match action.value().get() {
+        Some(Ok(result)) => Some(result),
+        Some(Err(_)) => Some(SubmissionResult::InternalServerError),
+        None => None,
+    };
+
+    let is_ok = move || matches!(result(), Some(SubmissionResult::Ok));
+
+    view! {
+        <details class="submit-solution">
+            <summary>"Submit a solution for day " {move || day()}</summary>
+            <ActionForm action=action>
+                <Show
+                    when=move || result().is_some()
+                    fallback=|| view! { <span></span> }
+                >
+                    <div
+                        class="result"
+                        class:error=move || !is_ok()
+                        class:success=is_ok
+                    >
+                        {move || result().map(|result| result.to_string())}
+                    </div>
+                </Show>
+                <input type="hidden" name="year" prop:value=move || year().to_string()/>
+                <input type="hidden" name="day" prop:value=move || day().to_string()/>
+                <label>
+                    <span>"Part"</span>
+                    <select name="part">
+                        <option value="1">"1"</option>
+                        <option value="2">"2"</option>
+                    </select>
+                </label>
+                <label>
+                    <span>"Language"</span>
+                    <select name="language">
+                        {LANGUAGES
+                            .into_iter()
+                            .map(|language| view! { <option value=language>{language}</option> })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label>
+                    <span>"Code"</span>
+                    <textarea name="code" rows="20" required></textarea>
+                </label>
+                <label>
+                    <span>"Write-up (Markdown, optional)"</span>
+                    <textarea name="writeup" rows="8"></textarea>
+                </label>
+                <VisibilitySelect name="visibility" empty_option="Use my default visibility"/>
+                <button type="submit" class="primary">"Submit"</button>
+            </ActionForm>
+        </details>
+    }
+}
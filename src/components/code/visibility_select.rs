@@ -0,0 +1,51 @@
+use leptos::*;
+
+use crate::functions::Visibility;
+
+/// Form inputs for selecting a visibility. The delay of a delayed visibility is named `hours`.
+#[component]
+pub fn VisibilitySelect(
+    /// Name of the select element.
+    #[prop(default = "kind")]
+    name: &'static str,
+    /// Currently selected visibility.
+    #[prop(default = None)]
+    selected: Option<Visibility>,
+    /// Label of an additional option with an empty value, e.g., for resetting to a default.
+    #[prop(optional)]
+    empty_option: Option<&'static str>,
+) -> impl IntoView {
+    let hours = match selected {
+        Some(Visibility::Delayed(hours)) => hours,
+        _ => 24,
+    };
+
+    let selected_kind = selected.map(|visibility| visibility.kind());
+
+    view! {
+        <label>
+            <span>"Visibility"</span>
+            <select name=name>
+                {empty_option.map(|label| view! {
+                    <option value="" selected=selected_kind.is_none()>{label}</option>
+                })}
+                {Visibility::KINDS
+                    .into_iter()
+                    .map(|kind| {
+                        let label = Visibility::from_form(kind, hours)
+                            .map(|visibility| visibility.to_string())
+                            .unwrap_or_default();
+
+                        view! {
+                            <option value=kind selected=selected_kind == Some(kind)>{label}</option>
+                        }
+                    })
+                    .collect_view()}
+            </select>
+        </label>
+        <label>
+            <span>"Delay in hours (for delayed visibility)"</span>
+            <input type="number" name="hours" min="0" max=Visibility::MAX_DELAY_HOURS value=hours/>
+        </label>
+    }
+}
@@ -0,0 +1,43 @@
+use leptos::*;
+
+use crate::functions::AuditEntry;
+
+/// Table of audit log entries, newest first.
+#[component]
+pub fn AuditLogTable(
+    entries: Vec<AuditEntry>,
+    /// Whether to show the account each entry concerns.
+    #[prop(optional)]
+    show_user: bool,
+) -> impl IntoView {
+    view! {
+        <table class="audit-log">
+            <thead>
+                <tr>
+                    <th>"Time"</th>
+                    {show_user.then(|| view! { <th>"User"</th> })}
+                    <th>"Event"</th>
+                    <th>"IP address"</th>
+                    <th>"Browser"</th>
+                </tr>
+            </thead>
+            <tbody>
+                {entries
+                    .into_iter()
+                    .map(|AuditEntry { username, actor, event, ip, user_agent, created_at }| view! {
+                        <tr>
+                            <td>{created_at}</td>
+                            {show_user.then(|| view! { <td>{username}</td> })}
+                            <td>
+                                {event.to_string()}
+                                {actor.map(|actor| view! { <span class="actor">" by " {actor}</span> })}
+                            </td>
+                            <td>{ip}</td>
+                            <td class="user-agent">{user_agent}</td>
+                        </tr>
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+    }
+}
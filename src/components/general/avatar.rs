@@ -0,0 +1,18 @@
+use leptos::*;
+
+use crate::utils::avatar::avatar_url;
+
+/// Avatar of a user, falling back to a generated identicon if the user did not upload one.
+#[component]
+pub fn Avatar(#[prop(into)] username: String, #[prop(default = 24)] size: u32) -> impl IntoView {
+    view! {
+        <img
+            class="avatar"
+            src=avatar_url(&username)
+            alt=""
+            width=size
+            height=size
+            loading="lazy"
+        />
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use leptos::*;
+
+use crate::utils::calendar::next_unlock;
+
+/// Format the time until the given point in time as e.g. `3d 04:05:06`.
+fn format_remaining(now: DateTime<Utc>, at: DateTime<Utc>) -> String {
+    let seconds = (at - now).num_seconds().max(0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Countdown until the next puzzle unlocks. It is rendered on the server and ticks every second
+/// once hydrated.
+#[component]
+pub fn Countdown() -> impl IntoView {
+    let (now, set_now) = create_signal(Utc::now());
+
+    create_effect(move |_| {
+        if let Ok(handle) = set_interval_with_handle(
+            move || set_now.set(Utc::now()),
+            std::time::Duration::from_secs(1),
+        ) {
+            on_cleanup(move || handle.clear());
+        }
+    });
+
+    let next = move || next_unlock(now());
+
+    view! {
+        <div class="countdown">
+            {move || next().map(|(year, day, at)| view! {
+                <span>"Day " {day} " of " {year} " unlocks in "</span>
+                <time datetime=at.to_rfc3339()>{format_remaining(now(), at)}</time>
+            })}
+        </div>
+    }
+}
@@ -1,5 +1,11 @@
+mod audit_log_table;
+mod avatar;
+mod countdown;
 mod structure;
 mod svg;
 
+pub use self::audit_log_table::*;
+pub use self::avatar::*;
+pub use self::countdown::*;
 pub use self::structure::*;
 pub use self::svg::*;
@@ -1,6 +1,10 @@
 use leptos::*;
 
-use crate::{components::Svg, hooks::use_auth};
+use crate::{
+    components::{Avatar, Svg},
+    functions::Role,
+    hooks::use_auth,
+};
 
 #[component]
 pub fn Navigation() -> impl IntoView {
@@ -21,6 +25,21 @@ pub fn Navigation() -> impl IntoView {
                         <span class="nav-label">Code</span>
                     </a>
                 </li>
+                <li>
+                    <a href="/search">
+                        <span class="nav-label">Search</span>
+                    </a>
+                </li>
+                <li>
+                    <a href="/hall-of-fame">
+                        <span class="nav-label">Hall of Fame</span>
+                    </a>
+                </li>
+                <li>
+                    <a href="/teams">
+                        <span class="nav-label">Teams</span>
+                    </a>
+                </li>
                 <li>
                     <a href="/last-years">
                         <span class="nav-label">Last Years</span>
@@ -37,7 +56,10 @@ pub fn Navigation() -> impl IntoView {
                                     <summary>
                                         <span class="nav-label">{user.clone()}</span>
                                         <span class="profile-picture">
-                                            <Svg id="user-circle" />
+                                            {match user.clone() {
+                                                Some(Ok(Some(username))) => view! { <Avatar username=username size=32/> }.into_view(),
+                                                _ => view! { <Svg id="user-circle" /> }.into_view(),
+                                            }}
                                         </span>
                                     </summary>
                                     <aside>
@@ -55,6 +77,13 @@ pub fn Navigation() -> impl IntoView {
                                                             <span class="icon"><Svg id="settings" /></span>Settings
                                                         </a>
                                                     </li>
+                                                    {move || matches!(auth.role.get(), Some(Ok(Some(Role::Moderator | Role::Admin)))).then(|| view! {
+                                                        <li>
+                                                            <a href="/admin">
+                                                                <span class="icon"><Svg id="tools" /></span>Admin
+                                                            </a>
+                                                        </li>
+                                                    })}
                                                     <li>
                                                         <a href="/logout">
                                                             <span class="icon"><Svg id="logout" /></span>Logout
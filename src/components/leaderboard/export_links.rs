@@ -0,0 +1,14 @@
+use leptos::*;
+
+/// Links for downloading the leaderboard of a year with all star timestamps.
+#[component]
+pub fn ExportLinks(year: i32) -> impl IntoView {
+    view! {
+        <p class="export">
+            "Export: "
+            <a href=format!("/export/leaderboard/{year}.csv") rel="external">"CSV"</a>
+            " · "
+            <a href=format!("/export/leaderboard/{year}.json") rel="external">"JSON"</a>
+        </p>
+    }
+}
@@ -0,0 +1,48 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::{
+    components::PendingMemberLinks,
+    functions::{LeaderboardResult, RefreshLeaderboard, UploadLeaderboard},
+};
+
+fn result_message<I: 'static>(
+    action: Action<I, Result<LeaderboardResult, ServerFnError>>,
+) -> impl Fn() -> Option<String> + Copy {
+    move || {
+        action.value().get().map(|result| {
+            result
+                .map(|result| result.to_string())
+                .unwrap_or_else(|e| e.to_string())
+        })
+    }
+}
+
+/// Controls for refreshing the leaderboard or uploading it manually.
+#[component]
+pub fn LeaderboardAdmin(
+    year: i32,
+    refresh: Action<RefreshLeaderboard, Result<LeaderboardResult, ServerFnError>>,
+    upload: Action<UploadLeaderboard, Result<LeaderboardResult, ServerFnError>>,
+) -> impl IntoView {
+    view! {
+        <details class="leaderboard-admin">
+            <summary>"Manage leaderboard"</summary>
+            <ActionForm action=refresh>
+                <p class="message">{result_message(refresh)}</p>
+                <input type="hidden" name="year" value=year/>
+                <button type="submit" class="primary">"Refresh now"</button>
+            </ActionForm>
+            <ActionForm action=upload>
+                <p class="message">{result_message(upload)}</p>
+                <input type="hidden" name="year" value=year/>
+                <label>
+                    <span>"Leaderboard JSON"</span>
+                    <textarea name="json" rows="6" required></textarea>
+                </label>
+                <button type="submit" class="primary">"Upload"</button>
+            </ActionForm>
+            <PendingMemberLinks/>
+        </details>
+    }
+}
@@ -0,0 +1,63 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::functions::{
+    get_member_link, ClaimMemberId, MemberLinkData, MemberLinkResult, VerifyMemberLink,
+};
+
+fn result_message<I: 'static>(
+    action: Action<I, Result<MemberLinkResult, ServerFnError>>,
+) -> impl Fn() -> Option<String> + Copy {
+    move || {
+        action.value().get().map(|result| {
+            result
+                .unwrap_or(MemberLinkResult::InternalServerError)
+                .to_string()
+        })
+    }
+}
+
+/// Form for linking the current user to an Advent of Code member.
+#[component]
+pub fn MemberLinkForm() -> impl IntoView {
+    let claim = create_server_action::<ClaimMemberId>();
+    let verify = create_server_action::<VerifyMemberLink>();
+
+    let link = create_resource(
+        move || (claim.version().get(), verify.version().get()),
+        |_| get_member_link(),
+    );
+
+    view! {
+        <div class="member-link">
+            <h2>"Advent of Code Account"</h2>
+            <Transition fallback=move || ()>
+                {move || link.get().map(|link| match link {
+                    Ok(Some(MemberLinkData { member_id, confirmed: true, .. })) => view! {
+                        <p>"Linked to Advent of Code member #" {member_id}</p>
+                    }.into_view(),
+                    Ok(Some(MemberLinkData { member_id, code, .. })) => view! {
+                        <p>
+                            "To prove that you are member #" {member_id} ", put "
+                            <code>{code}</code>
+                            " into your Advent of Code display name, wait for the next leaderboard update and verify. Afterwards, you can change your name back. Alternatively, ask an admin to confirm your claim."
+                        </p>
+                        <ActionForm action=verify>
+                            <p class="message">{result_message(verify)}</p>
+                            <button type="submit" class="primary">"Verify"</button>
+                        </ActionForm>
+                    }.into_view(),
+                    _ => ().into_view(),
+                })}
+            </Transition>
+            <ActionForm action=claim>
+                <p class="message">{result_message(claim)}</p>
+                <label>
+                    <span>"Member ID (see your Advent of Code settings)"</span>
+                    <input type="number" name="member_id" min="1" required/>
+                </label>
+                <button type="submit" class="primary">"Claim"</button>
+            </ActionForm>
+        </div>
+    }
+}
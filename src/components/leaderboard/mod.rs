@@ -0,0 +1,11 @@
+mod export_links;
+mod leaderboard_admin;
+mod member_link_form;
+mod pending_member_links;
+mod standings_table;
+
+pub use self::export_links::*;
+pub use self::leaderboard_admin::*;
+pub use self::member_link_form::*;
+pub use self::pending_member_links::*;
+pub use self::standings_table::*;
@@ -0,0 +1,41 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::functions::{get_pending_member_links, ConfirmMemberLink, PendingMemberLink};
+
+/// List of unconfirmed member links for admins to confirm.
+#[component]
+pub fn PendingMemberLinks() -> impl IntoView {
+    let confirm = create_server_action::<ConfirmMemberLink>();
+
+    let pending = create_resource(
+        move || confirm.version().get(),
+        |_| get_pending_member_links(),
+    );
+
+    view! {
+        <h3>"Pending member links"</h3>
+        <Transition fallback=move || ()>
+            {move || pending.get().map(|pending| match pending {
+                Ok(pending) if !pending.is_empty() => view! {
+                    <ul class="pending-member-links">
+                        {pending
+                            .into_iter()
+                            .map(|PendingMemberLink { id, username, member_id, member_name }| view! {
+                                <li>
+                                    {username} " claims #" {member_id}
+                                    {member_name.map(|name| format!(" ({name})"))}
+                                    <ActionForm action=confirm>
+                                        <input type="hidden" name="link" value=id/>
+                                        <button type="submit">"Confirm"</button>
+                                    </ActionForm>
+                                </li>
+                            })
+                            .collect_view()}
+                    </ul>
+                }.into_view(),
+                _ => view! { <p>"No pending claims"</p> }.into_view(),
+            })}
+        </Transition>
+    }
+}
@@ -0,0 +1,60 @@
+use leptos::*;
+
+use crate::{
+    components::Avatar,
+    utils::{leaderboard::Standing, scoring::ScoringMode},
+};
+
+#[component]
+pub fn StandingsTable(
+    year: i32,
+    standings: Vec<Standing>,
+    #[prop(default = ScoringMode::default())] scoring: ScoringMode,
+) -> impl IntoView {
+    view! {
+        <table class="standings">
+            <thead>
+                <tr>
+                    <th>"#"</th>
+                    <th>"Name"</th>
+                    <th title=scoring.to_string()>"Score"</th>
+                    <th>"Stars"</th>
+                    {(1..=25).map(|day| view! { <th class="day">{day}</th> }).collect_view()}
+                </tr>
+            </thead>
+            <tbody>
+                {standings
+                    .into_iter()
+                    .map(|Standing { rank, name, username, score_label, stars, days, .. }| view! {
+                        <tr>
+                            <td>{rank}</td>
+                            <td class="name">
+                                {match username {
+                                    Some(username) => view! {
+                                        <Avatar username=username.clone()/>
+                                        <a href=format!("/code/{username}?year={year}")>{name}</a>
+                                    }.into_view(),
+                                    None => name.into_view(),
+                                }}
+                            </td>
+                            <td>{score_label}</td>
+                            <td>{stars}</td>
+                            {days
+                                .into_iter()
+                                .map(|stars| view! {
+                                    <td
+                                        class="day"
+                                        class:partial=stars == 1
+                                        class:complete=stars == 2
+                                    >
+                                        "*"
+                                    </td>
+                                })
+                                .collect_view()}
+                        </tr>
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+    }
+}
@@ -1,5 +1,13 @@
+mod admin;
 mod code;
 mod general;
+mod leaderboard;
+mod stats;
+mod teams;
 
+pub use self::admin::*;
 pub use self::code::*;
 pub use self::general::*;
+pub use self::leaderboard::*;
+pub use self::stats::*;
+pub use self::teams::*;
@@ -0,0 +1,45 @@
+use leptos::*;
+
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 240.0;
+/// Space below the bars for the labels and above them for the values.
+const MARGIN: f64 = 24.0;
+
+/// Bar chart rendered as plain SVG, so it works without any JavaScript.
+#[component]
+pub fn BarChart(
+    #[prop(into)] title: String,
+    labels: Vec<String>,
+    values: Vec<u32>,
+) -> impl IntoView {
+    let max = values.iter().copied().max().unwrap_or_default().max(1) as f64;
+    let slot = WIDTH / values.len().max(1) as f64;
+    let bar_width = slot * 0.7;
+
+    view! {
+        <figure class="bar-chart">
+            <svg viewBox=format!("0 0 {WIDTH} {HEIGHT}") role="img" aria-label=title.clone()>
+                {labels
+                    .into_iter()
+                    .zip(values)
+                    .enumerate()
+                    .map(|(index, (label, value))| {
+                        let height = (HEIGHT - 2.0 * MARGIN) * value as f64 / max;
+                        let x = slot * index as f64 + (slot - bar_width) / 2.0;
+                        let y = HEIGHT - MARGIN - height;
+                        let center = x + bar_width / 2.0;
+
+                        view! {
+                            <g>
+                                <rect x=x y=y width=bar_width height=height/>
+                                <text x=center y=y - 6.0 text-anchor="middle">{value}</text>
+                                <text x=center y=HEIGHT - 6.0 text-anchor="middle">{label}</text>
+                            </g>
+                        }
+                    })
+                    .collect_view()}
+            </svg>
+            <figcaption>{title}</figcaption>
+        </figure>
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+
+use leptos::*;
+
+use crate::utils::history::HistorySeries;
+
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 300.0;
+const MARGIN: f64 = 32.0;
+/// Number of members shown initially, the remaining ones can be toggled on.
+const INITIALLY_SHOWN: usize = 10;
+const COLORS: [&str; 8] = [
+    "#5e81ac", "#bf616a", "#a3be8c", "#d08770", "#b48ead", "#88c0d0", "#ebcb8b", "#4c566a",
+];
+
+/// Line chart of the score or rank of each member over the days of the event.
+#[component]
+pub fn HistoryChart(
+    series: Vec<HistorySeries>,
+    /// Member whose line is highlighted, i.e., the logged-in user.
+    #[prop(default = None)]
+    highlight: Option<u64>,
+) -> impl IntoView {
+    let days = series
+        .iter()
+        .map(|series| series.scores.len())
+        .max()
+        .unwrap_or_default();
+
+    let mut by_score = series.iter().collect::<Vec<_>>();
+    by_score
+        .sort_by_key(|series| std::cmp::Reverse(series.scores.iter().flatten().last().copied()));
+    let hidden = create_rw_signal(
+        by_score
+            .iter()
+            .skip(INITIALLY_SHOWN)
+            .map(|series| series.member_id)
+            .filter(|id| Some(*id) != highlight)
+            .collect::<HashSet<_>>(),
+    );
+
+    let by_rank = create_rw_signal(false);
+
+    let max_score = series
+        .iter()
+        .flat_map(|series| series.scores.iter().flatten())
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(1) as f64;
+    let max_rank = series.len().max(2) as f64;
+
+    let x = move |day: usize| {
+        MARGIN + day as f64 * (WIDTH - 2.0 * MARGIN) / days.max(2).saturating_sub(1) as f64
+    };
+    let points = move |series: &HistorySeries| {
+        (0..days)
+            .filter_map(|day| {
+                let y = if by_rank() {
+                    let rank = series.ranks[day]? as f64;
+                    MARGIN + (rank - 1.0) / (max_rank - 1.0) * (HEIGHT - 2.0 * MARGIN)
+                } else {
+                    let score = series.scores[day]? as f64;
+                    HEIGHT - MARGIN - score / max_score * (HEIGHT - 2.0 * MARGIN)
+                };
+
+                Some(format!("{:.1},{y:.1}", x(day)))
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let series = store_value(series);
+
+    let toggle = move |member_id: u64| {
+        hidden.update(|hidden| {
+            if !hidden.remove(&member_id) {
+                hidden.insert(member_id);
+            }
+        })
+    };
+
+    view! {
+        <figure class="history-chart">
+            <div class="metric">
+                <label>
+                    <input type="radio" name="metric" checked=move || !by_rank() on:change=move |_| by_rank.set(false)/>
+                    "Score"
+                </label>
+                <label>
+                    <input type="radio" name="metric" checked=move || by_rank() on:change=move |_| by_rank.set(true)/>
+                    "Rank"
+                </label>
+            </div>
+            <svg viewBox=format!("0 0 {WIDTH} {HEIGHT}") role="img" aria-label="Leaderboard history">
+                {(0..days)
+                    .map(|day| view! {
+                        <text x=x(day) y=HEIGHT - 8.0 text-anchor="middle">{day + 1}</text>
+                    })
+                    .collect_view()}
+                {move || series.with_value(|series| {
+                    series
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, series)| !hidden.with(|hidden| hidden.contains(&series.member_id)))
+                        .map(|(index, series)| view! {
+                            <polyline
+                                points=points(series)
+                                stroke=COLORS[index % COLORS.len()]
+                                class:own={Some(series.member_id) == highlight}
+                            >
+                                <title>{series.name.clone()}</title>
+                            </polyline>
+                        })
+                        .collect_view()
+                })}
+            </svg>
+            <figcaption>
+                <ul class="legend">
+                    {series.with_value(|series| {
+                        series
+                            .iter()
+                            .enumerate()
+                            .map(|(index, HistorySeries { member_id, name, .. })| {
+                                let member_id = *member_id;
+
+                                view! {
+                                    <li class:own={Some(member_id) == highlight}>
+                                        <label>
+                                            <input
+                                                type="checkbox"
+                                                checked=move || !hidden.with(|hidden| hidden.contains(&member_id))
+                                                on:change=move |_| toggle(member_id)
+                                            />
+                                            <span class="color" style=format!("background: {}", COLORS[index % COLORS.len()])></span>
+                                            {name.clone()}
+                                        </label>
+                                    </li>
+                                }
+                            })
+                            .collect_view()
+                    })}
+                </ul>
+            </figcaption>
+        </figure>
+    }
+}
@@ -0,0 +1,5 @@
+mod bar_chart;
+mod history_chart;
+
+pub use self::bar_chart::*;
+pub use self::history_chart::*;
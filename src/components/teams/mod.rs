@@ -0,0 +1,5 @@
+mod team_panel;
+mod team_standings_table;
+
+pub use self::team_panel::*;
+pub use self::team_standings_table::*;
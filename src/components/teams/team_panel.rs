@@ -0,0 +1,130 @@
+use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::functions::{
+    get_own_team, get_team_invitations, AcceptTeamInvitation, CreateTeam, InviteToTeam, LeaveTeam,
+    RemoveTeamMember, TeamData, TeamInvitation, TeamMemberData, TeamResult,
+};
+
+fn result_message<I: 'static>(
+    action: Action<I, Result<TeamResult, ServerFnError>>,
+) -> impl Fn() -> Option<String> + Copy {
+    move || {
+        action.value().get().map(|result| {
+            result
+                .unwrap_or(TeamResult::InternalServerError)
+                .to_string()
+        })
+    }
+}
+
+/// Management of the team of the current user: creating a team, inviting members, answering
+/// invitations and leaving the team.
+#[component]
+pub fn TeamPanel(
+    /// Updated whenever the membership of a team changes.
+    version: WriteSignal<usize>,
+) -> impl IntoView {
+    let create = create_server_action::<CreateTeam>();
+    let invite = create_server_action::<InviteToTeam>();
+    let accept = create_server_action::<AcceptTeamInvitation>();
+    let remove = create_server_action::<RemoveTeamMember>();
+    let leave = create_server_action::<LeaveTeam>();
+
+    let current = move || {
+        create.version().get()
+            + invite.version().get()
+            + accept.version().get()
+            + remove.version().get()
+            + leave.version().get()
+    };
+
+    create_effect(move |previous: Option<usize>| {
+        let current = current();
+        if previous.is_some_and(|previous| previous != current) {
+            version.set(current);
+        }
+        current
+    });
+
+    let team = create_resource(current, |_| get_own_team());
+    let invitations = create_resource(current, |_| get_team_invitations());
+
+    view! {
+        <div class="team-panel">
+            <h2>"Your Team"</h2>
+            <Transition fallback=move || ()>
+                {move || invitations.get().map(|invitations| {
+                    invitations
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|TeamInvitation { id, team }| view! {
+                            <div class="invitation">
+                                <span>"You are invited to join " <strong>{team}</strong></span>
+                                <ActionForm action=accept>
+                                    <input type="hidden" name="invitation" value=id.clone()/>
+                                    <button type="submit" class="primary">"Accept"</button>
+                                </ActionForm>
+                                <ActionForm action=remove>
+                                    <input type="hidden" name="membership" value=id/>
+                                    <button type="submit">"Decline"</button>
+                                </ActionForm>
+                            </div>
+                        })
+                        .collect_view()
+                })}
+                <p class="message">{result_message(accept)}</p>
+                {move || team.get().map(|team| match team {
+                    Ok(Some(TeamData { name, own, members, .. })) => view! {
+                        <h3>{name}</h3>
+                        <ul class="members">
+                            {members
+                                .into_iter()
+                                .map(|TeamMemberData { id, username, accepted }| view! {
+                                    <li>
+                                        {username}
+                                        {(!accepted).then(|| view! { <span class="pending">" (invited)"</span> })}
+                                        {own.then(|| view! {
+                                            <ActionForm action=remove>
+                                                <input type="hidden" name="membership" value=id/>
+                                                <button type="submit">"Remove"</button>
+                                            </ActionForm>
+                                        })}
+                                    </li>
+                                })
+                                .collect_view()}
+                        </ul>
+                        <p class="message">{result_message(remove)}</p>
+                        {own.then(|| view! {
+                            <ActionForm action=invite>
+                                <p class="message">{result_message(invite)}</p>
+                                <label>
+                                    <span>"Username"</span>
+                                    <input type="text" name="username" required/>
+                                </label>
+                                <button type="submit" class="primary">"Invite"</button>
+                            </ActionForm>
+                        })}
+                        <ActionForm action=leave>
+                            <p class="message">{result_message(leave)}</p>
+                            <button type="submit">
+                                {if own { "Disband team" } else { "Leave team" }}
+                            </button>
+                        </ActionForm>
+                    }.into_view(),
+                    Ok(None) => view! {
+                        <ActionForm action=create>
+                            <p class="message">{result_message(create)}</p>
+                            <label>
+                                <span>"Team name"</span>
+                                <input type="text" name="name" minlength="3" maxlength="32" required/>
+                            </label>
+                            <button type="submit" class="primary">"Create team"</button>
+                        </ActionForm>
+                    }.into_view(),
+                    Err(_) => view! { <p>"Failed to load your team"</p> }.into_view(),
+                })}
+            </Transition>
+        </div>
+    }
+}
@@ -0,0 +1,41 @@
+use leptos::*;
+
+use crate::utils::teams::{TeamMemberScore, TeamStanding};
+
+#[component]
+pub fn TeamStandingsTable(standings: Vec<TeamStanding>) -> impl IntoView {
+    view! {
+        <table class="standings">
+            <thead>
+                <tr>
+                    <th>"#"</th>
+                    <th>"Team"</th>
+                    <th>"Score"</th>
+                    <th>"Members"</th>
+                </tr>
+            </thead>
+            <tbody>
+                {standings
+                    .into_iter()
+                    .map(|TeamStanding { rank, name, score, members, .. }| view! {
+                        <tr>
+                            <td>{rank}</td>
+                            <td class="name">{name}</td>
+                            <td>{score}</td>
+                            <td class="members">
+                                {members
+                                    .into_iter()
+                                    .map(|TeamMemberScore { username, score }| view! {
+                                        <span class="member">
+                                            <a href=format!("/u/{username}")>{username}</a> " (" {score.map(|score| score.to_string()).unwrap_or_else(|| "-".to_string())} ")"
+                                        </span>
+                                    })
+                                    .collect_view()}
+                            </td>
+                        </tr>
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+    }
+}
@@ -3,8 +3,8 @@ use cfg_if::cfg_if;
 use leptos::*;
 
 use crate::functions::{
-    Login, LoginResult, Logout, Register, RegistrationResult, ResendVerificationMail,
-    VerificationResult, Verify,
+    get_user_role, Login, LoginResult, Logout, Register, RegistrationResult,
+    ResendVerificationMail, Role, VerificationResult, Verify,
 };
 
 cfg_if! {
@@ -21,6 +21,7 @@ pub struct AuthContext {
     pub verify: Action<Verify, Result<VerificationResult, ServerFnError>>,
     pub resend_verification_email: Action<ResendVerificationMail, Result<(), ServerFnError>>,
     pub user: Resource<(usize, usize, usize), Result<Option<String>, ServerFnError>>,
+    pub role: Resource<(usize, usize, usize), Result<Option<Role>, ServerFnError>>,
 }
 
 impl AuthContext {
@@ -43,6 +44,17 @@ impl AuthContext {
             move |_| get_user_id(),
         );
 
+        let role = create_resource(
+            move || {
+                (
+                    login.version().get(),
+                    logout.version().get(),
+                    register.version().get(),
+                )
+            },
+            move |_| get_user_role(),
+        );
+
         AuthContext {
             login,
             logout,
@@ -50,6 +62,7 @@ impl AuthContext {
             verify,
             resend_verification_email,
             user,
+            role,
         }
     }
 }
@@ -1,3 +1,5 @@
 mod auth;
+mod preferences;
 
 pub use self::auth::*;
+pub use self::preferences::*;
@@ -0,0 +1,71 @@
+use leptos::*;
+use leptos_meta::*;
+
+use crate::{
+    functions::{get_preferences, Preferences, PreferencesResult, SetPreferences},
+    hooks::use_auth,
+};
+
+#[derive(Clone, Copy)]
+pub struct PreferencesContext {
+    pub set_preferences: Action<SetPreferences, Result<PreferencesResult, ServerFnError>>,
+    pub preferences: Resource<(usize, usize, usize), Result<Preferences, ServerFnError>>,
+}
+
+impl PreferencesContext {
+    #[tracing::instrument(level = "trace")]
+    fn new() -> Self {
+        let auth = use_auth();
+        let set_preferences = create_server_action::<SetPreferences>();
+
+        // blocking, so the theme is known before the head is sent and the page does not flash
+        let preferences = create_blocking_resource(
+            move || {
+                (
+                    set_preferences.version().get(),
+                    auth.login.version().get(),
+                    auth.logout.version().get(),
+                )
+            },
+            |_| get_preferences(),
+        );
+
+        PreferencesContext {
+            set_preferences,
+            preferences,
+        }
+    }
+}
+
+/// Apply the preferences to the document, i.e., the theme, the highlighter theme and the tab
+/// width.
+#[component]
+fn PreferencesMeta() -> impl IntoView {
+    let PreferencesContext { preferences, .. } = expect_context::<PreferencesContext>();
+
+    view! {
+        <Suspense fallback=move || view! { <Meta name="color-scheme" content="light dark"/> }>
+            {move || preferences.get().map(|preferences| {
+                let Preferences { theme, highlighter, tab_width, .. } = preferences.unwrap_or_default();
+
+                view! {
+                    <Html class=theme.class()/>
+                    <Meta name="color-scheme" content=theme.color_scheme()/>
+                    <Style id="preferences">{format!(":root {{ --tab-width: {tab_width}; }}")}</Style>
+                    {highlighter.stylesheet().map(|href| view! { <Stylesheet id="highlighter" href=href/> })}
+                }
+            })}
+        </Suspense>
+    }
+}
+
+/// Provide a PreferencesContext for use in child components.
+#[component]
+pub fn PreferencesContextProvider(children: Children) -> impl IntoView {
+    provide_context(PreferencesContext::new());
+
+    view! {
+        <PreferencesMeta/>
+        {children()}
+    }
+}
@@ -0,0 +1,411 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::functions::{Preferences, Reaction, Role, TokenScope, Visibility};
+
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, Duration, Utc};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::AuditEvent,
+    hooks::{record_audit_event, use_identity, use_preferences, use_user},
+    model::{Solution, User},
+    repository::{
+        ApiTokenRepository, AuditLogRepository, MemberLinkRepository, ReactionRepository,
+        ReportRepository, SessionRepository, TeamMemberRepository, TeamRepository, VoteRepository,
+    },
+    utils::password::verify_password,
+};
+
+/// Number of days between requesting the deletion of an account and its actual deletion.
+pub const DELETION_GRACE_DAYS: i64 = 14;
+
+/// Everything stored about a user, as offered for download.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountExport {
+    /// Point in time (RFC 3339) the export was created.
+    pub exported_at: String,
+    pub account: AccountExportData,
+    pub sessions: Vec<SessionExport>,
+    pub api_tokens: Vec<ApiTokenExport>,
+    pub solutions: Vec<SolutionExport>,
+    pub votes: Vec<VoteExport>,
+    pub reactions: Vec<ReactionExport>,
+    pub reports: Vec<ReportExport>,
+    pub member_link: Option<MemberLinkExport>,
+    pub teams: Vec<TeamExport>,
+    pub audit_log: Vec<AuditLogExport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountExportData {
+    pub id: String,
+    pub username: String,
+    pub email: String,
+    pub email_verified: bool,
+    pub default_visibility: Visibility,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub favourite_languages: Vec<String>,
+    pub preferences: Preferences,
+    pub deletion_requested_at: Option<String>,
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionExport {
+    pub id: String,
+    pub created_at: String,
+}
+
+/// An API token without its hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiTokenExport {
+    pub name: String,
+    pub scope: TokenScope,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolutionExport {
+    pub id: String,
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+    pub code: String,
+    pub writeup: Option<String>,
+    pub visibility: Option<Visibility>,
+    pub created_at: String,
+    /// Name of the file the code is stored in within the ZIP archive.
+    pub file_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteExport {
+    pub solution: String,
+    pub year: i32,
+    pub day: u8,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReactionExport {
+    pub solution: String,
+    pub reaction: Reaction,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportExport {
+    pub solution: String,
+    pub reason: String,
+    pub resolved: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberLinkExport {
+    pub member_id: u64,
+    pub confirmed: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamExport {
+    pub team: String,
+    /// Whether the user owns the team.
+    pub owner: bool,
+    /// Whether the user accepted the invitation.
+    pub accepted: bool,
+    pub created_at: String,
+}
+
+/// Entry of the audit log concerning the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditLogExport {
+    pub kind: String,
+    pub event: String,
+    /// Admin who caused the event, if it was not the user themselves.
+    pub actor: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountStatus {
+    /// Point in time the account will be deleted, formatted in the time zone of the user.
+    pub deletion_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum AccountResult {
+    Ok,
+    NotLoggedIn,
+    WrongPassword,
+    InternalServerError,
+}
+
+impl Display for AccountResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use AccountResult::*;
+
+        match self {
+            Ok => f.write_str("Success"),
+            NotLoggedIn => f.write_str("You need to be logged in"),
+            WrongPassword => f.write_str("Wrong password"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Collect everything stored about a user.
+#[cfg(feature = "ssr")]
+pub async fn collect_account_export(user: &User) -> Result<AccountExport, ServerFnError> {
+    let to_error = |e: surrealdb::Error| ServerFnError::ServerError(e.to_string());
+
+    let sessions = SessionRepository::get_by_user(&user.id)
+        .await
+        .map_err(to_error)?
+        .into_iter()
+        .filter_map(|session| {
+            Some(SessionExport {
+                id: session.id()?,
+                created_at: session.created_at,
+            })
+        })
+        .collect();
+
+    let api_tokens = ApiTokenRepository::get_by_user(&user.id)
+        .await
+        .map_err(to_error)?
+        .into_iter()
+        .map(|token| ApiTokenExport {
+            name: token.name,
+            scope: token.scope,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+        })
+        .collect();
+
+    let solutions = Solution::get_all_by_author(&user.username)
+        .await
+        .into_iter()
+        .map(|solution| SolutionExport {
+            file_name: format!("solutions/{}/{}", solution.year, solution.file_name()),
+            id: solution.id,
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            language: solution.language,
+            code: solution.code,
+            writeup: solution.writeup,
+            visibility: solution.visibility,
+            created_at: solution.created_at,
+        })
+        .collect();
+
+    let votes = VoteRepository::get_by_user(&user.id)
+        .await
+        .map_err(to_error)?
+        .into_iter()
+        .map(|vote| VoteExport {
+            solution: vote.solution,
+            year: vote.year,
+            day: vote.day,
+            created_at: vote.created_at,
+        })
+        .collect();
+
+    let reactions = ReactionRepository::get_by_user(&user.id)
+        .await
+        .map_err(to_error)?
+        .into_iter()
+        .map(|reaction| ReactionExport {
+            solution: reaction.solution,
+            reaction: reaction.reaction,
+            created_at: reaction.created_at,
+        })
+        .collect();
+
+    let reports = ReportRepository::get_by_reporter(&user.id)
+        .await
+        .map_err(to_error)?
+        .into_iter()
+        .map(|report| ReportExport {
+            solution: report.solution,
+            reason: report.reason,
+            resolved: report.resolved,
+            created_at: report.created_at,
+        })
+        .collect();
+
+    let member_link = MemberLinkRepository::get_by_user(&user.id)
+        .await
+        .map_err(to_error)?
+        .map(|link| MemberLinkExport {
+            member_id: link.member_id,
+            confirmed: link.confirmed,
+            created_at: link.created_at,
+        });
+
+    let mut teams = vec![];
+    for membership in TeamMemberRepository::get_by_user(&user.id)
+        .await
+        .map_err(to_error)?
+    {
+        let Some(team) = TeamRepository::get_by_id(&membership.team)
+            .await
+            .map_err(to_error)?
+        else {
+            continue;
+        };
+
+        teams.push(TeamExport {
+            team: team.name,
+            owner: team.owner == user.id,
+            accepted: membership.accepted,
+            created_at: membership.created_at,
+        });
+    }
+
+    let audit_log = AuditLogRepository::get_all_by_user(&user.id)
+        .await
+        .map_err(to_error)?
+        .into_iter()
+        .map(|entry| AuditLogExport {
+            event: entry.event.to_string(),
+            kind: entry.kind,
+            actor: entry.actor,
+            ip: entry.ip,
+            user_agent: entry.user_agent,
+            created_at: entry.created_at,
+        })
+        .collect();
+
+    let User {
+        id,
+        username,
+        email,
+        email_verified,
+        default_visibility,
+        display_name,
+        bio,
+        favourite_languages,
+        preferences,
+        deletion_requested_at,
+        role,
+        ..
+    } = user.clone();
+
+    Ok(AccountExport {
+        exported_at: Utc::now().to_rfc3339(),
+        account: AccountExportData {
+            id,
+            username,
+            email,
+            email_verified,
+            default_visibility,
+            display_name,
+            bio,
+            favourite_languages,
+            preferences,
+            deletion_requested_at,
+            role,
+        },
+        sessions,
+        api_tokens,
+        solutions,
+        votes,
+        reactions,
+        reports,
+        member_link,
+        teams,
+        audit_log,
+    })
+}
+
+/// Get everything stored about the current user.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_account_export() -> Result<Option<AccountExport>, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(None);
+    };
+
+    let export = collect_account_export(&user).await?;
+    record_audit_event(&user, None, AuditEvent::AccountExported).await;
+
+    Ok(Some(export))
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_account_status() -> Result<Option<AccountStatus>, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(None);
+    };
+
+    let preferences = use_preferences().await;
+
+    let deletion_at = user
+        .deletion_requested_at
+        .as_deref()
+        .and_then(|requested_at| DateTime::parse_from_rfc3339(requested_at).ok())
+        .map(|requested_at| {
+            preferences
+                .format_time(requested_at.with_timezone(&Utc) + Duration::days(DELETION_GRACE_DAYS))
+        });
+
+    Ok(Some(AccountStatus { deletion_at }))
+}
+
+/// Request the deletion of the current account. The account is deleted after the grace period,
+/// until then the user can log in again and cancel the deletion in the settings.
+#[tracing::instrument(level = "trace", skip(password))]
+#[server]
+pub async fn request_account_deletion(password: String) -> Result<AccountResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(AccountResult::NotLoggedIn);
+    };
+
+    let Ok(true) = verify_password(&password, &user.password) else {
+        return Ok(AccountResult::WrongPassword);
+    };
+
+    if let Err(e) = user.set_deletion_requested(true).await {
+        tracing::error!("failed to request account deletion: {e:?}");
+        return Ok(AccountResult::InternalServerError);
+    }
+
+    record_audit_event(&user, None, AuditEvent::AccountDeletionRequested).await;
+
+    if let Ok(identity) = use_identity() {
+        identity.logout();
+    }
+
+    leptos_actix::redirect("/");
+    Ok(AccountResult::Ok)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn cancel_account_deletion() -> Result<AccountResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(AccountResult::NotLoggedIn);
+    };
+
+    if let Err(e) = user.set_deletion_requested(false).await {
+        tracing::error!("failed to cancel account deletion: {e:?}");
+        return Ok(AccountResult::InternalServerError);
+    }
+
+    record_audit_event(&user, None, AuditEvent::AccountDeletionCancelled).await;
+
+    Ok(AccountResult::Ok)
+}
@@ -0,0 +1,335 @@
+use std::{fmt::Display, str::FromStr};
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::AuditEvent,
+    hooks::{record_audit_event, use_user, use_user_with_role},
+    model::{Session, User},
+    repository::{LeaderboardConfigRepository, SessionRepository, UserRepository},
+    services::leaderboard::{MAX_INTERVAL_MINUTES, MIN_INTERVAL_MINUTES},
+};
+
+#[cfg(feature = "ssr")]
+use super::auth::{create_jwt, send_verification_mail};
+
+/// Role of a user. Each role includes the permissions of the ones before it.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Role {
+    #[default]
+    User,
+    /// Can moderate content of other users.
+    Moderator,
+    /// Can manage users and the site.
+    Admin,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::User, Role::Moderator, Role::Admin];
+
+    /// Identifier of this role used in forms.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Role::ALL.into_iter().find(|role| role.key() == s).ok_or(())
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::User => f.write_str("User"),
+            Role::Moderator => f.write_str("Moderator"),
+            Role::Admin => f.write_str("Admin"),
+        }
+    }
+}
+
+/// A user as listed in the admin area.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminUser {
+    pub username: String,
+    pub email: String,
+    pub email_verified: bool,
+    pub role: Role,
+    pub disabled: bool,
+    pub deletion_requested: bool,
+}
+
+/// Leaderboard settings as shown in the admin area. Unset values fall back to the environment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardConfig {
+    pub url: Option<String>,
+    pub poll_interval: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum AdminResult {
+    Ok,
+    NotAllowed,
+    UnknownUser,
+    UnknownRole,
+    /// Admins cannot demote or disable themselves, so the site cannot lose its last admin.
+    OwnAccount,
+    AlreadyVerified,
+    InvalidUrl,
+    /// The poll interval is out of bounds; holds the minimum and maximum in minutes.
+    InvalidPollInterval(i64, i64),
+    InternalServerError,
+}
+
+impl Display for AdminResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use AdminResult::*;
+
+        match self {
+            Ok => f.write_str("Success"),
+            NotAllowed => f.write_str("You are not allowed to do this"),
+            UnknownUser => f.write_str("Unknown user"),
+            UnknownRole => f.write_str("Unknown role"),
+            OwnAccount => f.write_str("You cannot change your own account"),
+            AlreadyVerified => f.write_str("The email address is already verified"),
+            InvalidUrl => f.write_str("The URL needs to start with http:// or https://"),
+            InvalidPollInterval(minimum, maximum) => write!(
+                f,
+                "The poll interval needs to be between {minimum} and {maximum} minutes"
+            ),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Get the role of the current user, if logged in.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_user_role() -> Result<Option<Role>, ServerFnError> {
+    Ok(use_user().await.map(|user| user.role))
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_admin_users() -> Result<Option<Vec<AdminUser>>, ServerFnError> {
+    if use_user_with_role(Role::Admin).await.is_none() {
+        return Ok(None);
+    }
+
+    let mut users: Vec<_> = UserRepository::get_all()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .into_iter()
+        .map(|user| AdminUser {
+            username: user.username,
+            email: user.email,
+            email_verified: user.email_verified,
+            role: user.role,
+            disabled: user.disabled,
+            deletion_requested: user.deletion_requested_at.is_some(),
+        })
+        .collect();
+    users.sort_by_key(|user| user.username.to_lowercase());
+
+    Ok(Some(users))
+}
+
+/// Get the admin and the user to manage, unless the admin tries to manage their own account.
+#[cfg(feature = "ssr")]
+async fn managed_user(username: &str) -> Result<(User, User), AdminResult> {
+    let Some(admin) = use_user_with_role(Role::Admin).await else {
+        return Err(AdminResult::NotAllowed);
+    };
+
+    let Some(user) = User::get_by_username(username).await else {
+        return Err(AdminResult::UnknownUser);
+    };
+
+    if user.id == admin.id {
+        return Err(AdminResult::OwnAccount);
+    }
+
+    Ok((admin, user))
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn set_user_role(username: String, role: String) -> Result<AdminResult, ServerFnError> {
+    let (admin, user) = match managed_user(&username).await {
+        Ok(users) => users,
+        Err(result) => return Ok(result),
+    };
+
+    let Ok(role) = role.parse() else {
+        return Ok(AdminResult::UnknownRole);
+    };
+
+    if let Err(e) = user.set_role(role).await {
+        tracing::error!("failed to set role: {e:?}");
+        return Ok(AdminResult::InternalServerError);
+    }
+
+    record_audit_event(&user, Some(&admin), AuditEvent::RoleChanged(role)).await;
+
+    Ok(AdminResult::Ok)
+}
+
+/// Disable or re-enable an account. Disabling it also ends all of its sessions.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn set_user_disabled(
+    username: String,
+    disabled: bool,
+) -> Result<AdminResult, ServerFnError> {
+    let (admin, user) = match managed_user(&username).await {
+        Ok(users) => users,
+        Err(result) => return Ok(result),
+    };
+
+    if let Err(e) = user.set_disabled(disabled).await {
+        tracing::error!("failed to set disabled: {e:?}");
+        return Ok(AdminResult::InternalServerError);
+    }
+
+    let event = if disabled {
+        AuditEvent::AccountDisabled
+    } else {
+        AuditEvent::AccountEnabled
+    };
+    record_audit_event(&user, Some(&admin), event).await;
+
+    if disabled {
+        let sessions = SessionRepository::get_by_user(&user.id)
+            .await
+            .unwrap_or_default();
+
+        for session in sessions.iter().filter_map(|session| session.id()) {
+            Session::destroy(&session).await;
+        }
+    }
+
+    Ok(AdminResult::Ok)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn admin_resend_verification(username: String) -> Result<AdminResult, ServerFnError> {
+    let Some(admin) = use_user_with_role(Role::Admin).await else {
+        return Ok(AdminResult::NotAllowed);
+    };
+
+    let Some(user) = User::get_by_username(&username).await else {
+        return Ok(AdminResult::UnknownUser);
+    };
+
+    if user.email_verified {
+        return Ok(AdminResult::AlreadyVerified);
+    }
+
+    let sent = create_jwt(&user.username)
+        .and_then(|token| send_verification_mail(user.username.clone(), user.email.clone(), token));
+
+    if let Err(e) = sent {
+        tracing::error!("failed to send verification mail: {e:?}");
+        return Ok(AdminResult::InternalServerError);
+    }
+
+    record_audit_event(&user, Some(&admin), AuditEvent::VerificationMailSent).await;
+
+    Ok(AdminResult::Ok)
+}
+
+/// Mark the email address of a user as verified without them following the link.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn admin_verify_email(username: String) -> Result<AdminResult, ServerFnError> {
+    let Some(admin) = use_user_with_role(Role::Admin).await else {
+        return Ok(AdminResult::NotAllowed);
+    };
+
+    let Some(user) = User::get_by_username(&username).await else {
+        return Ok(AdminResult::UnknownUser);
+    };
+
+    if user.email_verified {
+        return Ok(AdminResult::AlreadyVerified);
+    }
+
+    user.verify_email().await;
+    record_audit_event(&user, Some(&admin), AuditEvent::EmailVerified).await;
+
+    Ok(AdminResult::Ok)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_leaderboard_config() -> Result<Option<LeaderboardConfig>, ServerFnError> {
+    if use_user_with_role(Role::Admin).await.is_none() {
+        return Ok(None);
+    }
+
+    let config = LeaderboardConfigRepository::get()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .unwrap_or_default();
+
+    Ok(Some(LeaderboardConfig {
+        url: config.url,
+        poll_interval: config.poll_interval,
+    }))
+}
+
+/// Store the leaderboard settings. Empty values fall back to the environment.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn set_leaderboard_config(
+    url: String,
+    poll_interval: String,
+) -> Result<AdminResult, ServerFnError> {
+    let Some(admin) = use_user_with_role(Role::Admin).await else {
+        return Ok(AdminResult::NotAllowed);
+    };
+
+    let url = Some(url.trim().to_string()).filter(|url| !url.is_empty());
+    if url
+        .as_ref()
+        .is_some_and(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+    {
+        return Ok(AdminResult::InvalidUrl);
+    }
+
+    let poll_interval = match poll_interval.trim() {
+        "" => None,
+        minutes => match minutes.parse::<i64>() {
+            Ok(minutes) if (MIN_INTERVAL_MINUTES..=MAX_INTERVAL_MINUTES).contains(&minutes) => {
+                Some(minutes)
+            }
+            _ => {
+                return Ok(AdminResult::InvalidPollInterval(
+                    MIN_INTERVAL_MINUTES,
+                    MAX_INTERVAL_MINUTES,
+                ))
+            }
+        },
+    };
+
+    if let Err(e) = LeaderboardConfigRepository::save(url, poll_interval).await {
+        tracing::error!("failed to save leaderboard configuration: {e:?}");
+        return Ok(AdminResult::InternalServerError);
+    }
+
+    record_audit_event(&admin, None, AuditEvent::LeaderboardConfigChanged).await;
+
+    Ok(AdminResult::Ok)
+}
@@ -0,0 +1,210 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "ssr")]
+use utoipa::OpenApi;
+
+#[cfg(feature = "ssr")]
+use crate::handlers::ApiDoc;
+
+/// Documentation of the REST API, extracted from its OpenAPI document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiDocs {
+    pub title: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub endpoints: Vec<ApiEndpoint>,
+    pub schemas: Vec<ApiSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiEndpoint {
+    /// HTTP method in upper case.
+    pub method: String,
+    pub path: String,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub parameters: Vec<ApiParameter>,
+    /// Name of the schema of the request body, if any.
+    pub request_body: Option<String>,
+    pub responses: Vec<ApiResponse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiParameter {
+    pub name: String,
+    /// Where the parameter is passed, e.g., `path` or `query`.
+    pub location: String,
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiResponse {
+    pub status: String,
+    pub description: String,
+    /// Name of the schema of the response body, if any. Arrays are suffixed with `[]`.
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiSchema {
+    pub name: String,
+    /// JSON schema of the type, pretty printed.
+    pub definition: String,
+}
+
+/// Get the name of a schema referenced by `$ref`, also within an array.
+fn schema_name(schema: &Value) -> Option<String> {
+    if let Some(reference) = schema["$ref"].as_str() {
+        return reference.rsplit('/').next().map(|name| name.to_string());
+    }
+
+    if schema["type"] == "array" {
+        return schema_name(&schema["items"]).map(|name| format!("{name}[]"));
+    }
+
+    None
+}
+
+fn json_body(content: &Value) -> Option<String> {
+    schema_name(&content["content"]["application/json"]["schema"])
+}
+
+fn string(value: &Value) -> Option<String> {
+    value.as_str().map(|value| value.to_string())
+}
+
+impl ApiDocs {
+    /// Extract the documentation from an OpenAPI 3 document.
+    pub fn from_spec(spec: &Value) -> ApiDocs {
+        let mut endpoints = vec![];
+
+        for (path, operations) in spec["paths"].as_object().into_iter().flatten() {
+            for (method, operation) in operations.as_object().into_iter().flatten() {
+                endpoints.push(ApiEndpoint {
+                    method: method.to_uppercase(),
+                    path: path.clone(),
+                    summary: string(&operation["summary"]),
+                    description: string(&operation["description"]),
+                    parameters: operation["parameters"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|parameter| ApiParameter {
+                            name: string(&parameter["name"]).unwrap_or_default(),
+                            location: string(&parameter["in"]).unwrap_or_default(),
+                            required: parameter["required"].as_bool().unwrap_or(false),
+                            description: string(&parameter["description"]),
+                        })
+                        .collect(),
+                    request_body: json_body(&operation["requestBody"]),
+                    responses: operation["responses"]
+                        .as_object()
+                        .into_iter()
+                        .flatten()
+                        .map(|(status, response)| ApiResponse {
+                            status: status.clone(),
+                            description: string(&response["description"]).unwrap_or_default(),
+                            body: json_body(response),
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        let schemas = spec["components"]["schemas"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, schema)| ApiSchema {
+                name: name.clone(),
+                definition: serde_json::to_string_pretty(schema).unwrap_or_default(),
+            })
+            .collect();
+
+        ApiDocs {
+            title: string(&spec["info"]["title"]).unwrap_or_default(),
+            version: string(&spec["info"]["version"]).unwrap_or_default(),
+            description: string(&spec["info"]["description"]),
+            endpoints,
+            schemas,
+        }
+    }
+}
+
+/// Get the documentation of the REST API.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_api_docs() -> Result<ApiDocs, ServerFnError> {
+    let spec = serde_json::to_value(ApiDoc::openapi())
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(ApiDocs::from_spec(&spec))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_from_spec() {
+        let spec = json!({
+            "info": { "title": "API", "version": "1.0.0" },
+            "paths": {
+                "/api/v1/solutions/{year}/{day}": {
+                    "get": {
+                        "summary": "Get the solutions of a day.",
+                        "parameters": [
+                            { "name": "year", "in": "path", "required": true, "description": "Year" },
+                            { "name": "author", "in": "query" }
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "Solutions",
+                                "content": { "application/json": { "schema": {
+                                    "type": "array",
+                                    "items": { "$ref": "#/components/schemas/ApiSolution" }
+                                } } }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": { "schemas": { "ApiSolution": { "type": "object" } } }
+        });
+
+        let docs = ApiDocs::from_spec(&spec);
+
+        assert_eq!(docs.title, "API");
+        assert_eq!(docs.description, None);
+        assert_eq!(docs.endpoints.len(), 1);
+
+        let endpoint = &docs.endpoints[0];
+        assert_eq!(endpoint.method, "GET");
+        assert_eq!(endpoint.path, "/api/v1/solutions/{year}/{day}");
+        assert_eq!(
+            endpoint.parameters[0],
+            ApiParameter {
+                name: "year".to_string(),
+                location: "path".to_string(),
+                required: true,
+                description: Some("Year".to_string()),
+            }
+        );
+        assert!(!endpoint.parameters[1].required);
+        assert_eq!(endpoint.request_body, None);
+        assert_eq!(
+            endpoint.responses,
+            vec![ApiResponse {
+                status: "200".to_string(),
+                description: "Solutions".to_string(),
+                body: Some("ApiSolution[]".to_string()),
+            }]
+        );
+        assert_eq!(docs.schemas[0].name, "ApiSolution");
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use std::collections::BTreeSet;
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::{AuditEvent, Role},
+    hooks::{record_audit_event, use_user_with_role},
+    repository::{EventYearRepository, LeaderboardSnapshotRepository, SolutionRepository},
+    utils::calendar::event_ended,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YearSummary {
+    pub year: i32,
+    /// Whether the year is read-only.
+    pub frozen: bool,
+    /// Whether December of this year is over.
+    pub ended: bool,
+    pub solutions: usize,
+    /// Number of members of the latest leaderboard snapshot.
+    pub participants: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveYearData {
+    pub summary: YearSummary,
+    /// Whether the current user is allowed to freeze the year.
+    pub can_manage: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ArchiveResult {
+    Ok,
+    NotAllowed,
+    NotEnded,
+    InternalServerError,
+}
+
+impl Display for ArchiveResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ArchiveResult::*;
+
+        match self {
+            Ok => f.write_str("Saved"),
+            NotAllowed => f.write_str("You are not allowed to manage the archive"),
+            NotEnded => f.write_str("A year can only be frozen after December is over"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Whether the given year is frozen, i.e., read-only.
+#[cfg(feature = "ssr")]
+pub async fn is_frozen(year: i32) -> bool {
+    match EventYearRepository::get(year).await {
+        Ok(state) => state.is_some_and(|state| state.frozen),
+        Err(e) => {
+            tracing::error!("failed to get state of event {year}: {e:?}");
+            false
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn get_summary(year: i32, solutions: usize) -> Result<YearSummary, ServerFnError> {
+    let participants = LeaderboardSnapshotRepository::get_latest(year)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .map(|snapshot| snapshot.leaderboard.members.len());
+
+    Ok(YearSummary {
+        year,
+        frozen: is_frozen(year).await,
+        ended: event_ended(year),
+        solutions,
+        participants,
+    })
+}
+
+/// Get all past years that have solutions or a leaderboard, newest first.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_archive_years() -> Result<Vec<YearSummary>, ServerFnError> {
+    let solutions = SolutionRepository::count_by_year()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let snapshots = LeaderboardSnapshotRepository::count_by_year()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let years = solutions
+        .iter()
+        .chain(snapshots.iter())
+        .map(|count| count.year)
+        .filter(|year| event_ended(*year))
+        .collect::<BTreeSet<_>>();
+
+    let mut summaries = vec![];
+    for year in years.into_iter().rev() {
+        let count = solutions
+            .iter()
+            .find(|count| count.year == year)
+            .map(|count| count.count)
+            .unwrap_or_default();
+
+        summaries.push(get_summary(year, count).await?);
+    }
+
+    Ok(summaries)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_archive_year(year: i32) -> Result<ArchiveYearData, ServerFnError> {
+    let can_manage = use_user_with_role(Role::Admin).await.is_some();
+
+    let solutions = SolutionRepository::count_by_year()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .into_iter()
+        .find(|count| count.year == year)
+        .map(|count| count.count)
+        .unwrap_or_default();
+
+    Ok(ArchiveYearData {
+        summary: get_summary(year, solutions).await?,
+        can_manage,
+    })
+}
+
+/// Freeze (or unfreeze) a year. Only years whose December is over can be frozen.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn set_year_frozen(year: i32, frozen: bool) -> Result<ArchiveResult, ServerFnError> {
+    let Some(admin) = use_user_with_role(Role::Admin).await else {
+        return Ok(ArchiveResult::NotAllowed);
+    };
+
+    if frozen && !event_ended(year) {
+        return Ok(ArchiveResult::NotEnded);
+    }
+
+    if let Err(e) = EventYearRepository::set_frozen(year, frozen).await {
+        tracing::error!("failed to freeze year: {e:?}");
+        return Ok(ArchiveResult::InternalServerError);
+    }
+
+    record_audit_event(&admin, None, AuditEvent::YearFrozen { year, frozen }).await;
+
+    Ok(ArchiveResult::Ok)
+}
@@ -0,0 +1,278 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use super::{LoginResult, ModerationAction, Role, TokenScope};
+
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::Preferences,
+    hooks::{use_preferences, use_user, use_user_with_role},
+    repository::AuditLogRepository,
+};
+
+/// Number of entries shown when searching the audit log.
+#[cfg(feature = "ssr")]
+const AUDIT_LOG_LENGTH: usize = 200;
+
+/// Number of entries a user sees of their own account.
+#[cfg(feature = "ssr")]
+const OWN_AUDIT_LOG_LENGTH: usize = 50;
+
+/// Security-relevant event recorded in the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AuditEvent {
+    LoginSucceeded,
+    LoginFailed(LoginResult),
+    Logout,
+    Registered,
+    EmailVerified,
+    VerificationMailSent,
+    PasswordChanged,
+    SessionRevoked,
+    ApiTokenCreated {
+        name: String,
+        scope: TokenScope,
+    },
+    ApiTokenRevoked(String),
+    AccountDeletionRequested,
+    AccountDeletionCancelled,
+    AccountExported,
+    RoleChanged(Role),
+    AccountDisabled,
+    AccountEnabled,
+    LeaderboardConfigChanged,
+    LeaderboardRefreshed(i32),
+    LeaderboardUploaded(i32),
+    YearFrozen {
+        year: i32,
+        frozen: bool,
+    },
+    MemberLinkConfirmed(u64),
+    SolutionModerated {
+        action: ModerationAction,
+        solution: String,
+    },
+}
+
+impl AuditEvent {
+    /// Identifiers of all kinds of events, as returned by [`AuditEvent::kind`].
+    pub const KINDS: [&'static str; 22] = [
+        "login-succeeded",
+        "login-failed",
+        "logout",
+        "registered",
+        "email-verified",
+        "verification-mail-sent",
+        "password-changed",
+        "session-revoked",
+        "api-token-created",
+        "api-token-revoked",
+        "account-deletion-requested",
+        "account-deletion-cancelled",
+        "account-exported",
+        "role-changed",
+        "account-disabled",
+        "account-enabled",
+        "leaderboard-config-changed",
+        "leaderboard-refreshed",
+        "leaderboard-uploaded",
+        "year-frozen",
+        "member-link-confirmed",
+        "solution-moderated",
+    ];
+
+    /// Identifier of the kind of this event, used for filtering.
+    pub fn kind(&self) -> &'static str {
+        use AuditEvent::*;
+
+        match self {
+            LoginSucceeded => "login-succeeded",
+            LoginFailed(_) => "login-failed",
+            Logout => "logout",
+            Registered => "registered",
+            EmailVerified => "email-verified",
+            VerificationMailSent => "verification-mail-sent",
+            PasswordChanged => "password-changed",
+            SessionRevoked => "session-revoked",
+            ApiTokenCreated { .. } => "api-token-created",
+            ApiTokenRevoked(_) => "api-token-revoked",
+            AccountDeletionRequested => "account-deletion-requested",
+            AccountDeletionCancelled => "account-deletion-cancelled",
+            AccountExported => "account-exported",
+            RoleChanged(_) => "role-changed",
+            AccountDisabled => "account-disabled",
+            AccountEnabled => "account-enabled",
+            LeaderboardConfigChanged => "leaderboard-config-changed",
+            LeaderboardRefreshed(_) => "leaderboard-refreshed",
+            LeaderboardUploaded(_) => "leaderboard-uploaded",
+            YearFrozen { .. } => "year-frozen",
+            MemberLinkConfirmed(_) => "member-link-confirmed",
+            SolutionModerated { .. } => "solution-moderated",
+        }
+    }
+}
+
+impl Display for AuditEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use AuditEvent::*;
+
+        match self {
+            LoginSucceeded => f.write_str("Logged in"),
+            LoginFailed(result) => write!(f, "Failed login: {result}"),
+            Logout => f.write_str("Logged out"),
+            Registered => f.write_str("Registered"),
+            EmailVerified => f.write_str("Verified email address"),
+            VerificationMailSent => f.write_str("Sent verification mail"),
+            PasswordChanged => f.write_str("Changed password"),
+            SessionRevoked => f.write_str("Revoked a session"),
+            ApiTokenCreated { name, scope } => {
+                write!(f, "Created API token '{name}' ({scope})")
+            }
+            ApiTokenRevoked(name) => write!(f, "Revoked API token '{name}'"),
+            AccountDeletionRequested => f.write_str("Requested account deletion"),
+            AccountDeletionCancelled => f.write_str("Cancelled account deletion"),
+            AccountExported => f.write_str("Exported account data"),
+            RoleChanged(role) => write!(f, "Role changed to {role}"),
+            AccountDisabled => f.write_str("Account disabled"),
+            AccountEnabled => f.write_str("Account enabled"),
+            LeaderboardConfigChanged => f.write_str("Changed leaderboard configuration"),
+            LeaderboardRefreshed(year) => write!(f, "Refreshed leaderboard {year}"),
+            LeaderboardUploaded(year) => write!(f, "Uploaded leaderboard {year}"),
+            YearFrozen { year, frozen: true } => write!(f, "Archived {year}"),
+            YearFrozen {
+                year,
+                frozen: false,
+            } => write!(f, "Unarchived {year}"),
+            MemberLinkConfirmed(member_id) => write!(f, "Confirmed member link #{member_id}"),
+            SolutionModerated { action, solution } => write!(f, "{action}: {solution}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub username: String,
+    /// Admin who caused the event, if it was not the user themselves.
+    pub actor: Option<String>,
+    pub event: AuditEvent,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    /// Point in time of the event, formatted in the time zone of the viewer.
+    pub created_at: String,
+}
+
+#[cfg(feature = "ssr")]
+fn to_entry(entry: AuditLogRepository, preferences: &Preferences) -> AuditEntry {
+    AuditEntry {
+        created_at: DateTime::parse_from_rfc3339(&entry.created_at)
+            .map(|time| preferences.format_time(time.with_timezone(&Utc)))
+            .unwrap_or_default(),
+        username: entry.username,
+        actor: entry.actor,
+        event: entry.event,
+        ip: entry.ip,
+        user_agent: entry.user_agent,
+    }
+}
+
+/// Search the audit log. Empty filters match every entry.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_audit_log(
+    username: String,
+    kind: String,
+) -> Result<Option<Vec<AuditEntry>>, ServerFnError> {
+    if use_user_with_role(Role::Admin).await.is_none() {
+        return Ok(None);
+    }
+
+    let preferences = use_preferences().await;
+
+    let username = Some(username.trim().to_string()).filter(|username| !username.is_empty());
+    let kind = Some(kind).filter(|kind| !kind.is_empty());
+
+    let entries = AuditLogRepository::search(username, kind, AUDIT_LOG_LENGTH)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(Some(
+        entries
+            .into_iter()
+            .map(|entry| to_entry(entry, &preferences))
+            .collect(),
+    ))
+}
+
+/// Get the newest audit log entries concerning the current user.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_own_audit_log() -> Result<Option<Vec<AuditEntry>>, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(None);
+    };
+
+    let preferences = use_preferences().await;
+
+    let entries = AuditLogRepository::get_by_user(&user.id, OWN_AUDIT_LOG_LENGTH)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(Some(
+        entries
+            .into_iter()
+            .map(|entry| to_entry(entry, &preferences))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kinds_complete() {
+        let events = [
+            AuditEvent::LoginSucceeded,
+            AuditEvent::LoginFailed(LoginResult::WrongCredentials),
+            AuditEvent::Logout,
+            AuditEvent::Registered,
+            AuditEvent::EmailVerified,
+            AuditEvent::VerificationMailSent,
+            AuditEvent::PasswordChanged,
+            AuditEvent::SessionRevoked,
+            AuditEvent::ApiTokenCreated {
+                name: "editor".to_string(),
+                scope: TokenScope::Write,
+            },
+            AuditEvent::ApiTokenRevoked("editor".to_string()),
+            AuditEvent::AccountDeletionRequested,
+            AuditEvent::AccountDeletionCancelled,
+            AuditEvent::AccountExported,
+            AuditEvent::RoleChanged(Role::Admin),
+            AuditEvent::AccountDisabled,
+            AuditEvent::AccountEnabled,
+            AuditEvent::LeaderboardConfigChanged,
+            AuditEvent::LeaderboardRefreshed(2023),
+            AuditEvent::LeaderboardUploaded(2023),
+            AuditEvent::YearFrozen {
+                year: 2023,
+                frozen: true,
+            },
+            AuditEvent::MemberLinkConfirmed(1),
+            AuditEvent::SolutionModerated {
+                action: ModerationAction::Hide,
+                solution: "alice 2023/1/1".to_string(),
+            },
+        ];
+
+        assert_eq!(events.len(), AuditEvent::KINDS.len());
+        for (event, kind) in events.iter().zip(AuditEvent::KINDS) {
+            assert_eq!(event.kind(), kind);
+        }
+    }
+}
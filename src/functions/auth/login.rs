@@ -7,15 +7,21 @@ use leptos::*;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ssr")]
-use crate::model::{LoginError, User};
+use crate::{
+    functions::AuditEvent,
+    hooks::record_audit_event,
+    model::{LoginError, User},
+    services::audit::Subject,
+};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LoginResult {
     Ok,
     InternalServerError,
     WrongCredentials,
     VerifyEmail,
     AlreadyLoggedIn,
+    AccountDisabled,
 }
 
 impl Display for LoginResult {
@@ -28,6 +34,7 @@ impl Display for LoginResult {
             WrongCredentials => f.write_str("Wrong Credentials"),
             VerifyEmail => f.write_str("Verify your Email before logging in"),
             AlreadyLoggedIn => f.write_str("You are already logged in"),
+            AccountDisabled => f.write_str("Your account has been disabled"),
         }
     }
 }
@@ -52,19 +59,31 @@ pub async fn login(
     let user: Option<User> = User::get_by_username(&username).await;
 
     let Some(mut user) = user else {
-        return Ok(LoginResult::WrongCredentials);
+        let result = LoginResult::WrongCredentials;
+        record_audit_event(Subject::unknown(&username), None, AuditEvent::LoginFailed(result)).await;
+        return Ok(result);
     };
 
     if !user.email_verified {
-        return Ok(LoginResult::VerifyEmail);
+        let result = LoginResult::VerifyEmail;
+        record_audit_event(&user, None, AuditEvent::LoginFailed(result)).await;
+        return Ok(result);
     }
 
-    match user.login(&password, &req).await {
-        Err(LoginError::Internal) => return Ok(LoginResult::InternalServerError),
-        Err(LoginError::PasswordMismatch) => return Ok(LoginResult::WrongCredentials),
-        Ok(_) => (),
+    let result = match user.login(&password, &req).await {
+        Err(LoginError::Internal) => LoginResult::InternalServerError,
+        Err(LoginError::PasswordMismatch) => LoginResult::WrongCredentials,
+        Err(LoginError::Disabled) => LoginResult::AccountDisabled,
+        Ok(_) => LoginResult::Ok,
     };
 
+    if result != LoginResult::Ok {
+        record_audit_event(&user, None, AuditEvent::LoginFailed(result)).await;
+        return Ok(result);
+    }
+
+    record_audit_event(&user, None, AuditEvent::LoginSucceeded).await;
+
     leptos_actix::redirect("/");
     return Ok(LoginResult::Ok);
 }
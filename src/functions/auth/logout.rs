@@ -1,7 +1,11 @@
 use leptos::*;
 
 #[cfg(feature = "ssr")]
-use crate::{hooks::use_identity, model::Session};
+use crate::{
+    functions::AuditEvent,
+    hooks::{record_audit_event, use_identity, use_user},
+    model::Session,
+};
 
 #[tracing::instrument(level = "trace")]
 #[server]
@@ -10,6 +14,10 @@ pub async fn logout() -> Result<(), ServerFnError> {
         return Ok(());
     };
 
+    if let Some(user) = use_user().await {
+        record_audit_event(&user, None, AuditEvent::Logout).await;
+    }
+
     let session_id = identity.id().expect("session did not have an id");
     Session::destroy(&session_id).await;
 
@@ -17,7 +17,7 @@ if #[cfg(feature = "ssr")] {
     use crate::services::{mail::Mail, jwt, jwt::VerifyJWT};
 
     #[tracing::instrument(level = "trace")]
-    fn create_jwt(username: &str) -> Result<String, Box<dyn Error>> {
+    pub(super) fn create_jwt(username: &str) -> Result<String, Box<dyn Error>> {
         tracing::debug!("creating jwt");
         let claims = VerifyJWT {
             sub: username.to_string(),
@@ -27,7 +27,7 @@ if #[cfg(feature = "ssr")] {
     }
 
     #[tracing::instrument(level = "trace")]
-    fn send_verification_mail(username: String, email: String, token: String) -> Result<(), Box<dyn Error>> {
+    pub(super) fn send_verification_mail(username: String, email: String, token: String) -> Result<(), Box<dyn Error>> {
         tracing::debug!("sending verification mail for '{username}' to '{email}'");
         let mail = Mail {
             subject: Some("Registration Mail".into()),
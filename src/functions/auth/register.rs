@@ -5,7 +5,11 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ssr")]
 use crate::{
-    functions::auth::{create_jwt, send_verification_mail},
+    functions::{
+        auth::{create_jwt, send_verification_mail},
+        AuditEvent,
+    },
+    hooks::record_audit_event,
     model::User,
     utils::password::hash_password,
 };
@@ -68,6 +72,10 @@ pub async fn register(
         return Ok(e);
     };
 
+    if let Some(user) = User::get_by_username(&username).await {
+        record_audit_event(&user, None, AuditEvent::Registered).await;
+    }
+
     if send_verification_mail(username, email, token).is_err() {
         return Ok(RegistrationResult::InternalServerError);
     }
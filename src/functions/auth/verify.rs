@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ssr")]
 use crate::{
+    functions::AuditEvent,
+    hooks::record_audit_event,
     model::User,
     services::{jwt, jwt::VerifyJWT},
 };
@@ -59,6 +61,7 @@ pub async fn verify(token: String) -> Result<VerificationResult, ServerFnError>
     };
 
     user.verify_email().await;
+    record_audit_event(&user, None, AuditEvent::EmailVerified).await;
 
     Ok(VerificationResult::Ok)
 }
@@ -74,6 +77,8 @@ pub async fn resend_verification_mail(username: String) -> Result<(), ServerFnEr
         return Ok(());
     };
 
-    let _ = send_verification_mail(username, user.email, token);
+    if send_verification_mail(username, user.email.clone(), token).is_ok() {
+        record_audit_event(&user, None, AuditEvent::VerificationMailSent).await;
+    }
     Ok(())
 }
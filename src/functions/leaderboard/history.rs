@@ -0,0 +1,46 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::history::HistorySeries;
+
+#[cfg(feature = "ssr")]
+use crate::{
+    hooks::use_user,
+    repository::{LeaderboardSnapshotRepository, MemberLinkRepository},
+    utils::history::history,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryData {
+    pub series: Vec<HistorySeries>,
+    /// Member ID the current user is linked to, if any.
+    pub own_member: Option<u64>,
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_leaderboard_history(year: i32) -> Result<HistoryData, ServerFnError> {
+    let snapshots = LeaderboardSnapshotRepository::get_all(year)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .into_iter()
+        .filter_map(|snapshot| {
+            let fetched_at = chrono::DateTime::parse_from_rfc3339(&snapshot.fetched_at).ok()?;
+            Some((fetched_at.with_timezone(&chrono::Utc), snapshot.leaderboard))
+        })
+        .collect::<Vec<_>>();
+
+    let own_member = match use_user().await {
+        Some(user) => MemberLinkRepository::get_by_user(&user.id)
+            .await
+            .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+            .filter(|link| link.confirmed)
+            .map(|link| link.member_id),
+        None => None,
+    };
+
+    Ok(HistoryData {
+        series: history(year, &snapshots),
+        own_member,
+    })
+}
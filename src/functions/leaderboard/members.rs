@@ -0,0 +1,278 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::{AuditEvent, Role},
+    hooks::{record_audit_event, use_user, use_user_with_role},
+    model::User,
+    repository::{LeaderboardSnapshotRepository, MemberLinkRepository},
+    services::audit::Subject,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberLinkData {
+    pub member_id: u64,
+    pub code: String,
+    pub confirmed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingMemberLink {
+    pub id: String,
+    pub username: String,
+    pub member_id: u64,
+    /// Current name of the member on the leaderboard, if the member is part of it.
+    pub member_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum MemberLinkResult {
+    Ok,
+    NotLoggedIn,
+    NotAllowed,
+    AlreadyClaimed,
+    NotFound,
+    ProofMissing,
+    InternalServerError,
+}
+
+impl Display for MemberLinkResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use MemberLinkResult::*;
+
+        match self {
+            Ok => f.write_str("Success"),
+            NotLoggedIn => f.write_str("You need to be logged in"),
+            NotAllowed => f.write_str("You are not allowed to confirm member links"),
+            AlreadyClaimed => f.write_str("This member is already linked to another account"),
+            NotFound => f.write_str("Member not found on the leaderboard"),
+            ProofMissing => f.write_str(
+                "The code was not found in your Advent of Code name (the leaderboard might not be refreshed yet)",
+            ),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_member_link() -> Result<Option<MemberLinkData>, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(None);
+    };
+
+    let link = MemberLinkRepository::get_by_user(&user.id)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(link.map(
+        |MemberLinkRepository {
+             member_id,
+             code,
+             confirmed,
+             ..
+         }| MemberLinkData {
+            member_id,
+            code,
+            confirmed,
+        },
+    ))
+}
+
+/// Whether a user other than the given one holds a confirmed link to the member ID.
+#[cfg(feature = "ssr")]
+async fn is_claimed_by_other(member_id: u64, user: &str) -> Result<bool, surrealdb::Error> {
+    Ok(MemberLinkRepository::get_confirmed()
+        .await?
+        .iter()
+        .any(|link| link.member_id == member_id && link.user != user))
+}
+
+/// Claim an Advent of Code member ID for the current user. The claim has to be proven by putting
+/// the generated code into the Advent of Code display name, or be confirmed by an admin.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn claim_member_id(member_id: u64) -> Result<MemberLinkResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(MemberLinkResult::NotLoggedIn);
+    };
+
+    if is_claimed_by_other(member_id, &user.id)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+    {
+        return Ok(MemberLinkResult::AlreadyClaimed);
+    }
+
+    let code = format!("aoc-{:06x}", OsRng.next_u32() & 0xffffff);
+
+    if let Err(e) = MemberLinkRepository::claim(user.id, user.username, member_id, code).await {
+        tracing::error!("failed to claim member id: {e:?}");
+        return Ok(MemberLinkResult::InternalServerError);
+    }
+
+    Ok(MemberLinkResult::Ok)
+}
+
+/// Confirm the claim of the current user by checking the newest leaderboard snapshot for the code.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn verify_member_link() -> Result<MemberLinkResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(MemberLinkResult::NotLoggedIn);
+    };
+
+    let Ok(Some(link)) = MemberLinkRepository::get_by_user(&user.id).await else {
+        return Ok(MemberLinkResult::NotFound);
+    };
+
+    let Ok(Some(snapshot)) = LeaderboardSnapshotRepository::get_newest().await else {
+        return Ok(MemberLinkResult::NotFound);
+    };
+
+    let Some(member) = snapshot
+        .leaderboard
+        .members
+        .values()
+        .find(|member| member.id == link.member_id)
+    else {
+        return Ok(MemberLinkResult::NotFound);
+    };
+
+    if !member
+        .name
+        .as_ref()
+        .is_some_and(|name| name.contains(&link.code))
+    {
+        return Ok(MemberLinkResult::ProofMissing);
+    }
+
+    // several users can claim the same member before any of them is confirmed
+    match is_claimed_by_other(link.member_id, &link.user).await {
+        Ok(true) => return Ok(MemberLinkResult::AlreadyClaimed),
+        Ok(false) => (),
+        Err(e) => {
+            tracing::error!("failed to check for other member links: {e:?}");
+            return Ok(MemberLinkResult::InternalServerError);
+        }
+    }
+
+    let id = link.id().expect("member link from database should have id");
+    if let Err(e) = MemberLinkRepository::confirm(&id).await {
+        tracing::error!("failed to confirm member link: {e:?}");
+        return Ok(MemberLinkResult::InternalServerError);
+    }
+
+    Ok(MemberLinkResult::Ok)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_pending_member_links() -> Result<Vec<PendingMemberLink>, ServerFnError> {
+    if use_user_with_role(Role::Admin).await.is_none() {
+        return Ok(vec![]);
+    }
+
+    let pending = MemberLinkRepository::get_pending()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let snapshot = LeaderboardSnapshotRepository::get_newest()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(pending
+        .into_iter()
+        .map(|link| PendingMemberLink {
+            id: link.id().expect("member link from database should have id"),
+            member_name: snapshot.as_ref().and_then(|snapshot| {
+                snapshot
+                    .leaderboard
+                    .members
+                    .values()
+                    .find(|member| member.id == link.member_id)
+                    .map(|member| member.display_name())
+            }),
+            username: link.username,
+            member_id: link.member_id,
+        })
+        .collect())
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn confirm_member_link(link: String) -> Result<MemberLinkResult, ServerFnError> {
+    let Some(admin) = use_user_with_role(Role::Admin).await else {
+        return Ok(MemberLinkResult::NotAllowed);
+    };
+
+    let Ok(Some(link)) = MemberLinkRepository::get_by_id(&link).await else {
+        return Ok(MemberLinkResult::NotFound);
+    };
+
+    // several users can claim the same member before any of them is confirmed
+    match is_claimed_by_other(link.member_id, &link.user).await {
+        Ok(true) => return Ok(MemberLinkResult::AlreadyClaimed),
+        Ok(false) => (),
+        Err(e) => {
+            tracing::error!("failed to check for other member links: {e:?}");
+            return Ok(MemberLinkResult::InternalServerError);
+        }
+    }
+
+    let id = link.id().expect("member link from database should have id");
+    if let Err(e) = MemberLinkRepository::confirm(&id).await {
+        tracing::error!("failed to confirm member link: {e:?}");
+        return Ok(MemberLinkResult::InternalServerError);
+    }
+
+    let subject = match User::get_by_username(&link.username).await {
+        Some(user) => Subject::from(&user),
+        None => Subject::unknown(&link.username),
+    };
+    record_audit_event(
+        subject,
+        Some(&admin),
+        AuditEvent::MemberLinkConfirmed(link.member_id),
+    )
+    .await;
+
+    Ok(MemberLinkResult::Ok)
+}
+
+/// Get the number of stars a user collected for a day, if the user is linked to a member.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_star_status(
+    username: String,
+    year: i32,
+    day: u8,
+) -> Result<Option<u8>, ServerFnError> {
+    let Ok(Some(link)) = MemberLinkRepository::get_by_username(&username).await else {
+        return Ok(None);
+    };
+
+    if !link.confirmed {
+        return Ok(None);
+    }
+
+    let Ok(Some(snapshot)) = LeaderboardSnapshotRepository::get_latest(year).await else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        snapshot
+            .leaderboard
+            .members
+            .values()
+            .find(|member| member.id == link.member_id)
+            .map(|member| member.stars_of_day(day))
+            .unwrap_or(0),
+    ))
+}
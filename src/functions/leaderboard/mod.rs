@@ -0,0 +1,9 @@
+mod history;
+mod members;
+mod standings;
+mod stats;
+
+pub use self::history::*;
+pub use self::members::*;
+pub use self::standings::*;
+pub use self::stats::*;
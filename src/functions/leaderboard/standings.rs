@@ -0,0 +1,193 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{leaderboard::Standing, scoring::ScoringMode};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::{is_frozen, AuditEvent, Role},
+    hooks::{record_audit_event, use_preferences, use_user_with_role},
+    repository::{LeaderboardSnapshotRepository, MemberLinkRepository},
+    services::leaderboard::{self, RefreshError, RefreshOutcome},
+    utils::leaderboard::Leaderboard,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardData {
+    pub year: i32,
+    /// Point in time of the snapshot the standings are computed from, formatted in the time zone
+    /// of the viewer.
+    pub fetched_at: Option<String>,
+    pub standings: Vec<Standing>,
+    /// Whether the current user is allowed to refresh or upload the leaderboard.
+    pub can_manage: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum LeaderboardResult {
+    Ok,
+    UpToDate,
+    /// The leaderboard was requested recently; holds the minutes until the next request.
+    TooSoon(i64),
+    NotAllowed,
+    InvalidJson,
+    WrongYear,
+    FetchFailed,
+    YearFrozen,
+    InternalServerError,
+}
+
+impl Display for LeaderboardResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use LeaderboardResult::*;
+
+        match self {
+            Ok => f.write_str("Leaderboard updated"),
+            UpToDate => f.write_str("Leaderboard is already up to date"),
+            TooSoon(minutes) => write!(
+                f,
+                "The leaderboard was fetched recently, try again in {minutes} minutes"
+            ),
+            NotAllowed => f.write_str("You are not allowed to manage the leaderboard"),
+            InvalidJson => f.write_str("Invalid leaderboard JSON"),
+            WrongYear => f.write_str("The leaderboard belongs to another year"),
+            FetchFailed => f.write_str("Failed to fetch the leaderboard"),
+            YearFrozen => f.write_str("This year is archived and read-only"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Store a new snapshot of the leaderboard for the given year.
+#[cfg(feature = "ssr")]
+async fn store_snapshot(year: i32, leaderboard: Leaderboard) -> LeaderboardResult {
+    if leaderboard.year().is_some_and(|event| event != year) {
+        return LeaderboardResult::WrongYear;
+    }
+
+    if is_frozen(year).await {
+        return LeaderboardResult::YearFrozen;
+    }
+
+    if let Err(e) = LeaderboardSnapshotRepository::create(year, leaderboard).await {
+        tracing::error!("failed to store leaderboard snapshot: {e:?}");
+        return LeaderboardResult::InternalServerError;
+    }
+
+    LeaderboardResult::Ok
+}
+
+/// Compute the standings of a year from its latest snapshot, together with the point in time
+/// (RFC 3339) the snapshot was fetched. Members are annotated with the users they are linked to.
+#[cfg(feature = "ssr")]
+pub async fn get_standings(
+    year: i32,
+    scoring: ScoringMode,
+) -> Result<Option<(String, Vec<Standing>)>, surrealdb::Error> {
+    let Some(snapshot) = LeaderboardSnapshotRepository::get_latest(year).await? else {
+        return Ok(None);
+    };
+
+    let links = MemberLinkRepository::get_confirmed().await?;
+
+    let standings = snapshot
+        .leaderboard
+        .standings(scoring.strategy().as_ref())
+        .into_iter()
+        .map(|standing| Standing {
+            username: links
+                .iter()
+                .find(|link| link.member_id == standing.member_id)
+                .map(|link| link.username.clone()),
+            ..standing
+        })
+        .collect();
+
+    Ok(Some((snapshot.fetched_at, standings)))
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_leaderboard(
+    year: i32,
+    scoring: ScoringMode,
+) -> Result<LeaderboardData, ServerFnError> {
+    let can_manage = use_user_with_role(Role::Admin).await.is_some();
+    let preferences = use_preferences().await;
+
+    let standings = get_standings(year, scoring)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(match standings {
+        Some((fetched_at, standings)) => LeaderboardData {
+            year,
+            standings,
+            fetched_at: Some(
+                chrono::DateTime::parse_from_rfc3339(&fetched_at)
+                    .map(|fetched_at| {
+                        preferences.format_time(fetched_at.with_timezone(&chrono::Utc))
+                    })
+                    .unwrap_or(fetched_at),
+            ),
+            can_manage,
+        },
+        None => LeaderboardData {
+            year,
+            fetched_at: None,
+            standings: vec![],
+            can_manage,
+        },
+    })
+}
+
+/// Fetch the leaderboard of the given year from the configured URL. This is subject to the same
+/// rate limit as the automatic refresh.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn refresh_leaderboard(year: i32) -> Result<LeaderboardResult, ServerFnError> {
+    let Some(admin) = use_user_with_role(Role::Admin).await else {
+        return Ok(LeaderboardResult::NotAllowed);
+    };
+
+    if is_frozen(year).await {
+        return Ok(LeaderboardResult::YearFrozen);
+    }
+
+    record_audit_event(&admin, None, AuditEvent::LeaderboardRefreshed(year)).await;
+
+    Ok(match leaderboard::refresh(year).await {
+        Ok(RefreshOutcome::Updated) => LeaderboardResult::Ok,
+        Ok(RefreshOutcome::NotModified) => LeaderboardResult::UpToDate,
+        Err(RefreshError::TooSoon(minutes)) => LeaderboardResult::TooSoon(minutes),
+        Err(e) => {
+            tracing::error!("failed to refresh leaderboard: {e}");
+            LeaderboardResult::FetchFailed
+        }
+    })
+}
+
+/// Store a leaderboard uploaded as JSON, e.g., when it cannot be fetched directly.
+#[tracing::instrument(level = "trace", skip(json))]
+#[server]
+pub async fn upload_leaderboard(
+    year: i32,
+    json: String,
+) -> Result<LeaderboardResult, ServerFnError> {
+    let Some(admin) = use_user_with_role(Role::Admin).await else {
+        return Ok(LeaderboardResult::NotAllowed);
+    };
+
+    let Ok(leaderboard) = Leaderboard::from_json(&json) else {
+        return Ok(LeaderboardResult::InvalidJson);
+    };
+
+    let result = store_snapshot(year, leaderboard).await;
+    if matches!(result, LeaderboardResult::Ok) {
+        record_audit_event(&admin, None, AuditEvent::LeaderboardUploaded(year)).await;
+    }
+
+    Ok(result)
+}
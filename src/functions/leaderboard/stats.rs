@@ -0,0 +1,25 @@
+use leptos::*;
+
+use crate::utils::stats::DayStats;
+
+#[cfg(feature = "ssr")]
+use chrono::Utc;
+
+#[cfg(feature = "ssr")]
+use crate::{repository::LeaderboardSnapshotRepository, utils::calendar::is_unlocked};
+
+/// Get the statistics of a single day, computed from the latest leaderboard snapshot of its year.
+/// There are no statistics before the puzzle unlocks.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_day_stats(year: i32, day: u8) -> Result<Option<DayStats>, ServerFnError> {
+    if !is_unlocked(year, day, Utc::now()) {
+        return Ok(None);
+    }
+
+    let snapshot = LeaderboardSnapshotRepository::get_latest(year)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(snapshot.and_then(|snapshot| DayStats::from_leaderboard(&snapshot.leaderboard, year, day)))
+}
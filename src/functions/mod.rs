@@ -1,3 +1,31 @@
+mod account;
+mod admin;
+mod api_docs;
+mod archive;
+mod audit;
 mod auth;
+mod leaderboard;
+mod moderation;
+mod preferences;
+mod profile;
+mod search;
+mod security;
+mod solutions;
+mod teams;
+mod tokens;
 
+pub use self::account::*;
+pub use self::admin::*;
+pub use self::api_docs::*;
+pub use self::archive::*;
+pub use self::audit::*;
 pub use self::auth::*;
+pub use self::leaderboard::*;
+pub use self::moderation::*;
+pub use self::preferences::*;
+pub use self::profile::*;
+pub use self::search::*;
+pub use self::security::*;
+pub use self::solutions::*;
+pub use self::teams::*;
+pub use self::tokens::*;
@@ -0,0 +1,405 @@
+use std::{fmt::Display, str::FromStr};
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::{filter_visible, AuditEvent, Role},
+    hooks::{record_audit_event, use_preferences, use_user, use_user_with_role},
+    model::{Solution, User},
+    repository::{ModerationActionRepository, ReportRepository},
+    services::audit::Subject,
+};
+
+/// Maximum length of the reason given for a report or a moderation action.
+pub const MAX_REASON_LENGTH: usize = 1000;
+
+/// Number of moderation actions shown in the admin area.
+const MODERATION_LOG_LENGTH: usize = 50;
+
+/// Action a moderator can take on a reported solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModerationAction {
+    /// Hide the solution from everybody but its author.
+    Hide,
+    /// Show a hidden solution again.
+    Restore,
+    /// Delete the solution together with its votes and reactions.
+    Delete,
+    /// Close the reports without changing the solution.
+    Dismiss,
+}
+
+impl ModerationAction {
+    pub const ALL: [ModerationAction; 4] = [
+        ModerationAction::Hide,
+        ModerationAction::Restore,
+        ModerationAction::Delete,
+        ModerationAction::Dismiss,
+    ];
+
+    /// Identifier of this action used in forms.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ModerationAction::Hide => "hide",
+            ModerationAction::Restore => "restore",
+            ModerationAction::Delete => "delete",
+            ModerationAction::Dismiss => "dismiss",
+        }
+    }
+}
+
+impl FromStr for ModerationAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ModerationAction::ALL
+            .into_iter()
+            .find(|action| action.key() == s)
+            .ok_or(())
+    }
+}
+
+impl Display for ModerationAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModerationAction::Hide => f.write_str("Hide"),
+            ModerationAction::Restore => f.write_str("Restore"),
+            ModerationAction::Delete => f.write_str("Delete"),
+            ModerationAction::Dismiss => f.write_str("Dismiss reports"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportEntry {
+    /// Username of the reporter, if the account still exists.
+    pub reporter: Option<String>,
+    pub reason: String,
+    pub created_at: String,
+}
+
+/// A solution in the moderation queue, either because it was reported or because it is hidden.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModerationItem {
+    pub solution: String,
+    pub author: String,
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+    pub code: String,
+    pub hidden_reason: Option<String>,
+    /// Open reports of the solution.
+    pub reports: Vec<ReportEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModerationLogEntry {
+    pub moderator: String,
+    pub action: ModerationAction,
+    pub subject: String,
+    pub reason: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ReportResult {
+    Ok,
+    NotLoggedIn,
+    NotFound,
+    AlreadyReported,
+    ReasonMissing,
+    ReasonTooLong,
+    InternalServerError,
+}
+
+impl Display for ReportResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ReportResult::*;
+
+        match self {
+            Ok => f.write_str("Thank you, a moderator will look at it"),
+            NotLoggedIn => f.write_str("You need to be logged in to report solutions"),
+            NotFound => f.write_str("Solution not found"),
+            AlreadyReported => f.write_str("You already reported this solution"),
+            ReasonMissing => f.write_str("Please give a reason"),
+            ReasonTooLong => write!(
+                f,
+                "The reason may have at most {MAX_REASON_LENGTH} characters"
+            ),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ModerationResult {
+    Ok,
+    NotAllowed,
+    NotFound,
+    UnknownAction,
+    ReasonMissing,
+    ReasonTooLong,
+    InternalServerError,
+}
+
+impl Display for ModerationResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ModerationResult::*;
+
+        match self {
+            Ok => f.write_str("Done"),
+            NotAllowed => f.write_str("You are not allowed to moderate"),
+            NotFound => f.write_str("Solution not found"),
+            UnknownAction => f.write_str("Unknown action"),
+            ReasonMissing => f.write_str("Please give a reason"),
+            ReasonTooLong => write!(
+                f,
+                "The reason may have at most {MAX_REASON_LENGTH} characters"
+            ),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Report a solution to the moderators.
+#[tracing::instrument(level = "trace", skip(reason))]
+#[server]
+pub async fn report_solution(
+    solution: String,
+    reason: String,
+) -> Result<ReportResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(ReportResult::NotLoggedIn);
+    };
+
+    let reason = reason.trim().to_string();
+    if reason.is_empty() {
+        return Ok(ReportResult::ReasonMissing);
+    }
+    if reason.chars().count() > MAX_REASON_LENGTH {
+        return Ok(ReportResult::ReasonTooLong);
+    }
+
+    let Some(solution) = Solution::get_by_id(&solution).await else {
+        return Ok(ReportResult::NotFound);
+    };
+
+    // nobody can report what they cannot see
+    let Some(solution) = filter_visible(vec![solution], Some(&user)).await.pop() else {
+        return Ok(ReportResult::NotFound);
+    };
+
+    match ReportRepository::is_reported_by(&solution.id, &user.id).await {
+        Ok(true) => return Ok(ReportResult::AlreadyReported),
+        Ok(false) => (),
+        Err(e) => {
+            tracing::error!("failed to check for existing report: {e:?}");
+            return Ok(ReportResult::InternalServerError);
+        }
+    }
+
+    if let Err(e) = ReportRepository::create(solution.id, user.id, reason).await {
+        tracing::error!("failed to create report: {e:?}");
+        return Ok(ReportResult::InternalServerError);
+    }
+
+    Ok(ReportResult::Ok)
+}
+
+/// Get the reported and the hidden solutions, oldest report first.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_moderation_queue() -> Result<Option<Vec<ModerationItem>>, ServerFnError> {
+    if use_user_with_role(Role::Moderator).await.is_none() {
+        return Ok(None);
+    }
+
+    let preferences = use_preferences().await;
+    let format_time = |time: &str| {
+        DateTime::parse_from_rfc3339(time)
+            .map(|time| preferences.format_time(time.with_timezone(&Utc)))
+            .unwrap_or_default()
+    };
+
+    let reports = ReportRepository::get_open()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let mut items: Vec<ModerationItem> = vec![];
+    let mut reporters: Vec<(String, Option<String>)> = vec![];
+
+    for report in reports {
+        let reporter = match reporters.iter().find(|(id, _)| *id == report.reporter) {
+            Some((_, name)) => name.clone(),
+            None => {
+                let name = User::get_by_id(&report.reporter)
+                    .await
+                    .map(|user| user.username);
+                reporters.push((report.reporter.clone(), name.clone()));
+                name
+            }
+        };
+
+        let entry = ReportEntry {
+            reporter,
+            reason: report.reason,
+            created_at: format_time(&report.created_at),
+        };
+
+        if let Some(item) = items
+            .iter_mut()
+            .find(|item| item.solution == report.solution)
+        {
+            item.reports.push(entry);
+            continue;
+        }
+
+        let Some(solution) = Solution::get_by_id(&report.solution).await else {
+            continue;
+        };
+
+        items.push(ModerationItem {
+            solution: solution.id,
+            author: solution.author,
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            language: solution.language,
+            code: solution.code,
+            hidden_reason: solution.hidden_reason,
+            reports: vec![entry],
+        });
+    }
+
+    for solution in Solution::get_hidden().await {
+        if items.iter().any(|item| item.solution == solution.id) {
+            continue;
+        }
+
+        items.push(ModerationItem {
+            solution: solution.id,
+            author: solution.author,
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            language: solution.language,
+            code: solution.code,
+            hidden_reason: solution.hidden_reason,
+            reports: vec![],
+        });
+    }
+
+    Ok(Some(items))
+}
+
+/// Hide, restore or delete a solution, or dismiss its reports. Every action closes the open
+/// reports of the solution and is recorded together with the reason.
+#[tracing::instrument(level = "trace", skip(reason))]
+#[server]
+pub async fn moderate_solution(
+    solution: String,
+    action: String,
+    reason: String,
+) -> Result<ModerationResult, ServerFnError> {
+    let Some(moderator) = use_user_with_role(Role::Moderator).await else {
+        return Ok(ModerationResult::NotAllowed);
+    };
+
+    let Ok(action) = action.parse::<ModerationAction>() else {
+        return Ok(ModerationResult::UnknownAction);
+    };
+
+    let reason = reason.trim().to_string();
+    if reason.is_empty() {
+        return Ok(ModerationResult::ReasonMissing);
+    }
+    if reason.chars().count() > MAX_REASON_LENGTH {
+        return Ok(ModerationResult::ReasonTooLong);
+    }
+
+    let Some(solution) = Solution::get_by_id(&solution).await else {
+        return Ok(ModerationResult::NotFound);
+    };
+
+    let result = match action {
+        ModerationAction::Hide => solution.set_hidden(Some(reason.clone())).await,
+        ModerationAction::Restore => solution.set_hidden(None).await,
+        ModerationAction::Delete => solution.delete().await,
+        ModerationAction::Dismiss => Ok(()),
+    };
+
+    if let Err(e) = result {
+        tracing::error!("failed to moderate solution: {e:?}");
+        return Ok(ModerationResult::InternalServerError);
+    }
+
+    if let Err(e) = ReportRepository::resolve(&solution.id).await {
+        tracing::error!("failed to resolve reports: {e:?}");
+    }
+
+    if let Err(e) = ModerationActionRepository::create(
+        moderator.id.clone(),
+        moderator.username.clone(),
+        action,
+        solution.id.clone(),
+        solution.subject(),
+        reason,
+    )
+    .await
+    {
+        tracing::error!("failed to record moderation action: {e:?}");
+        return Ok(ModerationResult::InternalServerError);
+    }
+
+    let author = match User::get_by_username(&solution.author).await {
+        Some(author) => Subject::from(&author),
+        None => Subject::unknown(&solution.author),
+    };
+    record_audit_event(
+        author,
+        Some(&moderator),
+        AuditEvent::SolutionModerated {
+            action,
+            solution: solution.subject(),
+        },
+    )
+    .await;
+
+    Ok(ModerationResult::Ok)
+}
+
+/// Get the most recent moderation actions, newest first.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_moderation_log() -> Result<Option<Vec<ModerationLogEntry>>, ServerFnError> {
+    if use_user_with_role(Role::Moderator).await.is_none() {
+        return Ok(None);
+    }
+
+    let preferences = use_preferences().await;
+
+    let actions = ModerationActionRepository::get_recent(MODERATION_LOG_LENGTH)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(Some(
+        actions
+            .into_iter()
+            .map(|action| ModerationLogEntry {
+                created_at: DateTime::parse_from_rfc3339(&action.created_at)
+                    .map(|time| preferences.format_time(time.with_timezone(&Utc)))
+                    .unwrap_or_default(),
+                moderator: action.moderator_name,
+                action: action.action,
+                subject: action.subject,
+                reason: action.reason,
+            })
+            .collect(),
+    ))
+}
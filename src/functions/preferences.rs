@@ -0,0 +1,344 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use actix_web::{
+    cookie::{time::Duration, Cookie, SameSite},
+    http::header::{HeaderValue, SET_COOKIE},
+};
+
+#[cfg(feature = "ssr")]
+use crate::hooks::{use_preferences, use_user, PREFERENCES_COOKIE};
+
+/// Tab widths that can be chosen for displaying code.
+pub const TAB_WIDTHS: [u8; 4] = [2, 3, 4, 8];
+
+/// Color scheme of the site.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Follow the preference of the operating system.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    /// Identifier of this theme used in forms and cookies.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    /// Value of the `color-scheme` meta tag.
+    pub fn color_scheme(&self) -> &'static str {
+        match self {
+            Theme::System => "light dark",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    /// Class of the `html` element forcing this theme, if any.
+    pub fn class(&self) -> &'static str {
+        match self {
+            Theme::System => "",
+            Theme::Light => "theme-light",
+            Theme::Dark => "theme-dark",
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Theme::ALL
+            .into_iter()
+            .find(|theme| theme.key() == s)
+            .ok_or(())
+    }
+}
+
+impl Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Theme::System => f.write_str("System"),
+            Theme::Light => f.write_str("Light"),
+            Theme::Dark => f.write_str("Dark"),
+        }
+    }
+}
+
+/// Theme of the syntax highlighter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HighlighterTheme {
+    /// The theme bundled with the site's stylesheet.
+    #[default]
+    Default,
+    Okaidia,
+    Tomorrow,
+    SolarizedLight,
+    Twilight,
+}
+
+impl HighlighterTheme {
+    pub const ALL: [HighlighterTheme; 5] = [
+        HighlighterTheme::Default,
+        HighlighterTheme::Okaidia,
+        HighlighterTheme::Tomorrow,
+        HighlighterTheme::SolarizedLight,
+        HighlighterTheme::Twilight,
+    ];
+
+    /// Identifier of this theme used in forms and cookies.
+    pub fn key(&self) -> &'static str {
+        match self {
+            HighlighterTheme::Default => "default",
+            HighlighterTheme::Okaidia => "okaidia",
+            HighlighterTheme::Tomorrow => "tomorrow",
+            HighlighterTheme::SolarizedLight => "solarizedlight",
+            HighlighterTheme::Twilight => "twilight",
+        }
+    }
+
+    /// URL of the Prism stylesheet of this theme. The default theme is part of the site's
+    /// stylesheet.
+    pub fn stylesheet(&self) -> Option<String> {
+        match self {
+            HighlighterTheme::Default => None,
+            theme => Some(format!(
+                "https://unpkg.com/prismjs@1.29.0/themes/prism-{}.min.css",
+                theme.key()
+            )),
+        }
+    }
+}
+
+impl FromStr for HighlighterTheme {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HighlighterTheme::ALL
+            .into_iter()
+            .find(|theme| theme.key() == s)
+            .ok_or(())
+    }
+}
+
+impl Display for HighlighterTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HighlighterTheme::Default => f.write_str("Default"),
+            HighlighterTheme::Okaidia => f.write_str("Okaidia"),
+            HighlighterTheme::Tomorrow => f.write_str("Tomorrow Night"),
+            HighlighterTheme::SolarizedLight => f.write_str("Solarized Light"),
+            HighlighterTheme::Twilight => f.write_str("Twilight"),
+        }
+    }
+}
+
+/// Display preferences of a user. Anonymous visitors keep them in a cookie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub theme: Theme,
+    pub highlighter: HighlighterTheme,
+    pub tab_width: u8,
+    /// IANA name of the time zone timestamps are displayed in.
+    pub time_zone: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            highlighter: HighlighterTheme::default(),
+            tab_width: 4,
+            time_zone: "UTC".to_string(),
+        }
+    }
+}
+
+impl Preferences {
+    /// Format a point in time in the preferred time zone.
+    pub fn format_time(&self, time: DateTime<Utc>) -> String {
+        let time_zone = self.time_zone.parse::<Tz>().unwrap_or(Tz::UTC);
+
+        time.with_timezone(&time_zone)
+            .format("%Y-%m-%d %H:%M %Z")
+            .to_string()
+    }
+
+    /// Encode the preferences as cookie value.
+    pub fn to_cookie(&self) -> String {
+        format!(
+            "{}|{}|{}|{}",
+            self.theme.key(),
+            self.highlighter.key(),
+            self.tab_width,
+            self.time_zone
+        )
+    }
+
+    /// Decode preferences from a cookie value. Invalid or missing parts fall back to the default.
+    pub fn from_cookie(cookie: &str) -> Preferences {
+        let mut parts = cookie.split('|');
+        let default = Preferences::default();
+
+        Preferences {
+            theme: parts
+                .next()
+                .and_then(|theme| theme.parse().ok())
+                .unwrap_or(default.theme),
+            highlighter: parts
+                .next()
+                .and_then(|highlighter| highlighter.parse().ok())
+                .unwrap_or(default.highlighter),
+            tab_width: parts
+                .next()
+                .and_then(|width| width.parse().ok())
+                .filter(|width| TAB_WIDTHS.contains(width))
+                .unwrap_or(default.tab_width),
+            time_zone: parts
+                .next()
+                .filter(|time_zone| time_zone.parse::<Tz>().is_ok())
+                .map(String::from)
+                .unwrap_or(default.time_zone),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum PreferencesResult {
+    Ok,
+    InvalidTheme,
+    InvalidTabWidth,
+    InvalidTimeZone,
+    InternalServerError,
+}
+
+impl Display for PreferencesResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use PreferencesResult::*;
+
+        match self {
+            Ok => f.write_str("Saved"),
+            InvalidTheme => f.write_str("Unknown theme"),
+            InvalidTabWidth => f.write_str("Unsupported tab width"),
+            InvalidTimeZone => f.write_str("Unknown time zone"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Get the preferences of the current user or, for anonymous visitors, the ones of the cookie.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_preferences() -> Result<Preferences, ServerFnError> {
+    Ok(use_preferences().await)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn set_preferences(
+    theme: String,
+    highlighter: String,
+    tab_width: u8,
+    time_zone: String,
+) -> Result<PreferencesResult, ServerFnError> {
+    let (Ok(theme), Ok(highlighter)) = (theme.parse(), highlighter.parse()) else {
+        return Ok(PreferencesResult::InvalidTheme);
+    };
+
+    if !TAB_WIDTHS.contains(&tab_width) {
+        return Ok(PreferencesResult::InvalidTabWidth);
+    }
+
+    if time_zone.parse::<Tz>().is_err() {
+        return Ok(PreferencesResult::InvalidTimeZone);
+    }
+
+    let preferences = Preferences {
+        theme,
+        highlighter,
+        tab_width,
+        time_zone,
+    };
+
+    if let Some(user) = use_user().await {
+        if let Err(e) = user.set_preferences(preferences.clone()).await {
+            tracing::error!("failed to save preferences: {e:?}");
+            return Ok(PreferencesResult::InternalServerError);
+        }
+    }
+
+    // the cookie is set for logged in users as well, so the preferences survive a logout
+    let cookie = Cookie::build(PREFERENCES_COOKIE, preferences.to_cookie())
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .max_age(Duration::days(365))
+        .finish();
+
+    if let (Some(response), Ok(value)) = (
+        use_context::<leptos_actix::ResponseOptions>(),
+        HeaderValue::from_str(&cookie.to_string()),
+    ) {
+        response.insert_header(SET_COOKIE, value);
+    }
+
+    Ok(PreferencesResult::Ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_cookie_roundtrip() {
+        let preferences = Preferences {
+            theme: Theme::Dark,
+            highlighter: HighlighterTheme::Okaidia,
+            tab_width: 2,
+            time_zone: "Europe/Berlin".to_string(),
+        };
+
+        assert_eq!(
+            Preferences::from_cookie(&preferences.to_cookie()),
+            preferences
+        );
+    }
+
+    #[test]
+    fn test_invalid_cookie() {
+        let preferences = Preferences::from_cookie("dark|unknown|5");
+
+        assert_eq!(preferences.theme, Theme::Dark);
+        assert_eq!(preferences.highlighter, HighlighterTheme::Default);
+        assert_eq!(preferences.tab_width, 4);
+        assert_eq!(preferences.time_zone, "UTC");
+    }
+
+    #[test]
+    fn test_format_time() {
+        let time = Utc.with_ymd_and_hms(2023, 12, 1, 5, 0, 0).unwrap();
+        let preferences = Preferences {
+            time_zone: "Europe/Berlin".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(preferences.format_time(time), "2023-12-01 06:00 CET");
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, TimeZone, Utc};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    hooks::{use_preferences, use_user},
+    model::{Solution, User},
+    repository::{LeaderboardSnapshotRepository, MemberLinkRepository},
+    utils::{calendar::DAYS, markdown::render_markdown},
+};
+
+#[cfg(feature = "ssr")]
+use super::{filter_visible, Preferences, LANGUAGES};
+
+const MAX_DISPLAY_NAME_LENGTH: usize = 64;
+const MAX_BIO_LENGTH: usize = 2000;
+#[cfg(feature = "ssr")]
+const RECENT_ACTIVITY: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct YearStars {
+    pub year: i32,
+    pub stars: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct ProfileSolution {
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActivityKind {
+    /// A star was collected on Advent of Code.
+    Star,
+    /// A solution was submitted on this site.
+    Solution { language: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Activity {
+    /// Point in time formatted in the time zone of the viewer.
+    pub at: String,
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub kind: ActivityKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileData {
+    pub username: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    /// Bio rendered from Markdown.
+    pub bio_html: Option<String>,
+    pub favourite_languages: Vec<String>,
+    /// Advent of Code member the user is (confirmed to be) linked to.
+    pub member_id: Option<u64>,
+    /// Stars per year according to the latest leaderboard snapshots, newest year first.
+    pub stars: Vec<YearStars>,
+    /// Solutions visible to the current user, newest first.
+    pub solutions: Vec<ProfileSolution>,
+    pub activity: Vec<Activity>,
+    /// Whether the profile belongs to the current user.
+    pub own: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ProfileResult {
+    Ok,
+    NotLoggedIn,
+    DisplayNameTooLong,
+    BioTooLong,
+    UnknownLanguage,
+    InternalServerError,
+}
+
+impl Display for ProfileResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ProfileResult::*;
+
+        match self {
+            Ok => f.write_str("Saved"),
+            NotLoggedIn => f.write_str("You need to be logged in"),
+            DisplayNameTooLong => write!(
+                f,
+                "The display name must not be longer than {MAX_DISPLAY_NAME_LENGTH} characters"
+            ),
+            BioTooLong => write!(
+                f,
+                "The bio must not be longer than {MAX_BIO_LENGTH} characters"
+            ),
+            UnknownLanguage => f.write_str("Unknown language"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Collect the stars of a member per year together with the points in time they were collected.
+#[cfg(feature = "ssr")]
+async fn get_stars(
+    member_id: u64,
+    preferences: &Preferences,
+) -> Result<(Vec<YearStars>, Vec<(DateTime<Utc>, Activity)>), ServerFnError> {
+    let years = LeaderboardSnapshotRepository::count_by_year()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let mut stars = vec![];
+    let mut activity = vec![];
+
+    for year in years.into_iter().map(|count| count.year) {
+        let Some(snapshot) = LeaderboardSnapshotRepository::get_latest(year)
+            .await
+            .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        else {
+            continue;
+        };
+
+        let Some(member) = snapshot
+            .leaderboard
+            .members
+            .values()
+            .find(|member| member.id == member_id)
+        else {
+            continue;
+        };
+
+        stars.push(YearStars {
+            year,
+            stars: member.stars,
+        });
+
+        for day in 1..=DAYS {
+            for part in 1..=2 {
+                let Some(at) = member
+                    .star(day, part)
+                    .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+                else {
+                    continue;
+                };
+
+                activity.push((
+                    at,
+                    Activity {
+                        at: preferences.format_time(at),
+                        year,
+                        day,
+                        part,
+                        kind: ActivityKind::Star,
+                    },
+                ));
+            }
+        }
+    }
+
+    stars.sort_by_key(|stars| -stars.year);
+
+    Ok((stars, activity))
+}
+
+/// Collect the profile of a user as seen by the viewer.
+#[cfg(feature = "ssr")]
+pub async fn collect_profile(
+    user: User,
+    viewer: Option<&User>,
+    preferences: &Preferences,
+) -> Result<ProfileData, ServerFnError> {
+    let own = viewer.is_some_and(|viewer| viewer.id == user.id);
+
+    let member_id = MemberLinkRepository::get_by_user(&user.id)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .filter(|link| link.confirmed)
+        .map(|link| link.member_id);
+
+    let (stars, mut activity) = match member_id {
+        Some(member_id) => get_stars(member_id, preferences).await?,
+        None => (vec![], vec![]),
+    };
+
+    let solutions = filter_visible(Solution::get_all_by_author(&user.username).await, viewer).await;
+
+    for solution in &solutions {
+        let Ok(at) = DateTime::parse_from_rfc3339(&solution.created_at) else {
+            continue;
+        };
+        let at = at.with_timezone(&Utc);
+
+        activity.push((
+            at,
+            Activity {
+                at: preferences.format_time(at),
+                year: solution.year,
+                day: solution.day,
+                part: solution.part,
+                kind: ActivityKind::Solution {
+                    language: solution.language.clone(),
+                },
+            },
+        ));
+    }
+
+    activity.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    let User {
+        username,
+        display_name,
+        bio,
+        favourite_languages,
+        ..
+    } = user;
+
+    Ok(ProfileData {
+        username,
+        display_name,
+        bio_html: bio.as_deref().map(render_markdown),
+        bio,
+        favourite_languages,
+        member_id,
+        stars,
+        solutions: solutions
+            .into_iter()
+            .map(|solution| ProfileSolution {
+                year: solution.year,
+                day: solution.day,
+                part: solution.part,
+                language: solution.language,
+            })
+            .collect(),
+        activity: activity
+            .into_iter()
+            .take(RECENT_ACTIVITY)
+            .map(|(_, activity)| activity)
+            .collect(),
+        own,
+    })
+}
+
+/// Get the public profile of a user. Without a username, the profile of the current user is
+/// returned.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_profile(username: String) -> Result<Option<ProfileData>, ServerFnError> {
+    let viewer = use_user().await;
+    let preferences = use_preferences().await;
+
+    let user = if username.is_empty() {
+        viewer.clone()
+    } else {
+        User::get_by_username(&username).await
+    };
+
+    let Some(user) = user else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        collect_profile(user, viewer.as_ref(), &preferences).await?,
+    ))
+}
+
+/// Update the profile of the current user. The favourite languages are given comma-separated.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn update_profile(
+    display_name: String,
+    bio: String,
+    favourite_languages: String,
+) -> Result<ProfileResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(ProfileResult::NotLoggedIn);
+    };
+
+    let display_name = display_name.trim().to_string();
+    if display_name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+        return Ok(ProfileResult::DisplayNameTooLong);
+    }
+
+    let bio = bio.trim().to_string();
+    if bio.chars().count() > MAX_BIO_LENGTH {
+        return Ok(ProfileResult::BioTooLong);
+    }
+
+    let mut languages = vec![];
+    for language in favourite_languages
+        .split(',')
+        .map(|language| language.trim().to_lowercase())
+        .filter(|language| !language.is_empty())
+    {
+        if !LANGUAGES.contains(&language.as_str()) {
+            return Ok(ProfileResult::UnknownLanguage);
+        }
+        if !languages.contains(&language) {
+            languages.push(language);
+        }
+    }
+
+    if let Err(e) = user
+        .update_profile(
+            Some(display_name).filter(|name| !name.is_empty()),
+            Some(bio).filter(|bio| !bio.is_empty()),
+            languages,
+        )
+        .await
+    {
+        tracing::error!("failed to update profile: {e:?}");
+        return Ok(ProfileResult::InternalServerError);
+    }
+
+    Ok(ProfileResult::Ok)
+}
@@ -0,0 +1,189 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::filter_visible, hooks::use_user, model::Solution, repository::SolutionRepository,
+};
+
+/// Lines of context shown around the first match within a search hit.
+#[cfg(feature = "ssr")]
+const CONTEXT_LINES: usize = 2;
+
+/// Maximum number of hits returned, the ones with the most occurrences first.
+#[cfg(feature = "ssr")]
+const MAX_HITS: usize = 100;
+
+/// Number of matching solutions loaded from the database at once.
+#[cfg(feature = "ssr")]
+const PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFilter {
+    pub year: Option<i32>,
+    pub day: Option<u8>,
+    pub language: Option<String>,
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub solution: String,
+    pub author: String,
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+    /// Excerpt of the code (or write-up) around the first match.
+    pub excerpt: String,
+    /// Whether the excerpt is taken from the write-up instead of the code.
+    pub in_writeup: bool,
+    /// Total number of occurrences of all terms.
+    pub occurrences: usize,
+}
+
+/// Extract the lines around the first line containing any of the given terms.
+#[cfg(feature = "ssr")]
+fn excerpt(text: &str, terms: &[String]) -> Option<String> {
+    let lines = text.lines().collect::<Vec<_>>();
+
+    let index = lines.iter().position(|line| {
+        let line = line.to_lowercase();
+        terms.iter().any(|term| line.contains(term.as_str()))
+    })?;
+
+    let start = index.saturating_sub(CONTEXT_LINES);
+    let end = (index + CONTEXT_LINES + 1).min(lines.len());
+
+    Some(lines[start..end].join("\n"))
+}
+
+#[cfg(feature = "ssr")]
+fn count_occurrences(text: &str, terms: &[String]) -> usize {
+    let text = text.to_lowercase();
+
+    terms
+        .iter()
+        .map(|term| text.matches(term.as_str()).count())
+        .sum()
+}
+
+/// Search through the code and write-ups of all solutions visible to the current user.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn search(query: String, filter: SearchFilter) -> Result<Vec<SearchHit>, ServerFnError> {
+    let terms = query
+        .split_whitespace()
+        .map(|term| term.to_lowercase())
+        .collect::<Vec<_>>();
+
+    if terms.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let SearchFilter {
+        year,
+        day,
+        language,
+        author,
+    } = filter;
+
+    let user = use_user().await;
+    let viewer = user.as_ref().map(|user| user.username.clone());
+
+    // page through the matches until enough of them are visible to the user
+    let mut solutions = vec![];
+    let mut start = 0;
+
+    while solutions.len() < MAX_HITS {
+        let page = SolutionRepository::search(
+            &terms,
+            year,
+            day,
+            language.clone(),
+            author.clone(),
+            viewer.clone(),
+            start,
+            PAGE_SIZE,
+        )
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+        let is_last = page.len() < PAGE_SIZE;
+        start += page.len();
+
+        let page = page.into_iter().map(Solution::from).collect();
+        solutions.extend(filter_visible(page, user.as_ref()).await);
+
+        if is_last {
+            break;
+        }
+    }
+
+    let mut hits = solutions
+        .into_iter()
+        .map(|solution| {
+            let writeup = solution.writeup.clone().unwrap_or_default();
+            let (excerpt, in_writeup) = match excerpt(&solution.code, &terms) {
+                Some(excerpt) => (excerpt, false),
+                None => (excerpt(&writeup, &terms).unwrap_or_default(), true),
+            };
+
+            SearchHit {
+                occurrences: count_occurrences(&solution.code, &terms)
+                    + count_occurrences(&writeup, &terms),
+                excerpt,
+                in_writeup,
+                solution: solution.id,
+                author: solution.author,
+                year: solution.year,
+                day: solution.day,
+                part: solution.part,
+                language: solution.language,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    hits.sort_by(|a, b| b.occurrences.cmp(&a.occurrences));
+    hits.truncate(MAX_HITS);
+
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(terms: &[&str]) -> Vec<String> {
+        terms.iter().map(|term| term.to_string()).collect()
+    }
+
+    #[test]
+    fn test_excerpt() {
+        let text = "use std::io;\n\nfn main() {\n    let graph = parse();\n    dijkstra(&graph);\n}\n\nfn parse() {}";
+
+        assert_eq!(
+            excerpt(text, &terms(&["dijkstra"])),
+            Some("fn main() {\n    let graph = parse();\n    dijkstra(&graph);\n}\n".to_string())
+        );
+        // the first line matching any of the terms wins
+        assert_eq!(
+            excerpt(text, &terms(&["io", "dijkstra"])),
+            Some("use std::io;\n\nfn main() {".to_string())
+        );
+        assert_eq!(excerpt(text, &terms(&["nom"])), None);
+    }
+
+    #[test]
+    fn test_count_occurrences() {
+        let text = "Dijkstra with a BinaryHeap, dijkstra again";
+
+        assert_eq!(count_occurrences(text, &terms(&["dijkstra"])), 2);
+        assert_eq!(
+            count_occurrences(text, &terms(&["dijkstra", "binaryheap"])),
+            3
+        );
+        assert_eq!(count_occurrences(text, &terms(&["nom"])), 0);
+        assert_eq!(count_occurrences("", &terms(&["dijkstra"])), 0);
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::AuditEvent,
+    hooks::{record_audit_event, use_identity, use_preferences, use_user},
+    model::Session,
+    repository::SessionRepository,
+    utils::password::{hash_password, verify_password},
+};
+
+/// A session the current user is logged in with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionData {
+    pub id: String,
+    /// Point in time of the login, formatted in the time zone of the user.
+    pub created_at: String,
+    /// Whether this is the session of the current request.
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SecurityResult {
+    Ok,
+    NotLoggedIn,
+    WrongPassword,
+    PasswordEmpty,
+    PasswordsDoNotMatch,
+    NotFound,
+    InternalServerError,
+}
+
+impl Display for SecurityResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use SecurityResult::*;
+
+        match self {
+            Ok => f.write_str("Saved"),
+            NotLoggedIn => f.write_str("You need to be logged in"),
+            WrongPassword => f.write_str("Wrong password"),
+            PasswordEmpty => f.write_str("The new password must not be empty"),
+            PasswordsDoNotMatch => f.write_str("Passwords do not match"),
+            NotFound => f.write_str("Session not found"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Change the password of the current user. All other sessions are ended.
+#[tracing::instrument(level = "trace", skip(current, password, password_confirm))]
+#[server]
+pub async fn change_password(
+    current: String,
+    password: String,
+    password_confirm: String,
+) -> Result<SecurityResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(SecurityResult::NotLoggedIn);
+    };
+
+    let Ok(true) = verify_password(&current, &user.password) else {
+        return Ok(SecurityResult::WrongPassword);
+    };
+
+    if password.is_empty() {
+        return Ok(SecurityResult::PasswordEmpty);
+    }
+
+    if password != password_confirm {
+        return Ok(SecurityResult::PasswordsDoNotMatch);
+    }
+
+    if let Err(e) = user.set_password(hash_password(password)?).await {
+        tracing::error!("failed to change password: {e:?}");
+        return Ok(SecurityResult::InternalServerError);
+    }
+
+    record_audit_event(&user, None, AuditEvent::PasswordChanged).await;
+
+    let current_session = use_identity().ok().and_then(|identity| identity.id().ok());
+    let sessions = SessionRepository::get_by_user(&user.id)
+        .await
+        .unwrap_or_default();
+
+    for session in sessions.iter().filter_map(|session| session.id()) {
+        if Some(&session) != current_session.as_ref() {
+            Session::destroy(&session).await;
+        }
+    }
+
+    Ok(SecurityResult::Ok)
+}
+
+/// Get the sessions the current user is logged in with, newest first.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_sessions() -> Result<Option<Vec<SessionData>>, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(None);
+    };
+
+    let preferences = use_preferences().await;
+    let current_session = use_identity().ok().and_then(|identity| identity.id().ok());
+
+    let mut sessions = SessionRepository::get_by_user(&user.id)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+    sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    Ok(Some(
+        sessions
+            .into_iter()
+            .filter_map(|session| {
+                let id = session.id()?;
+
+                Some(SessionData {
+                    current: Some(&id) == current_session.as_ref(),
+                    created_at: DateTime::parse_from_rfc3339(&session.created_at)
+                        .map(|time| preferences.format_time(time.with_timezone(&Utc)))
+                        .unwrap_or_default(),
+                    id,
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// End a session of the current user, e.g., on a lost device.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn revoke_session(session: String) -> Result<SecurityResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(SecurityResult::NotLoggedIn);
+    };
+
+    let owned = SessionRepository::get_by_user(&user.id)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .iter()
+        .any(|own| own.id().as_ref() == Some(&session));
+
+    if !owned {
+        return Ok(SecurityResult::NotFound);
+    }
+
+    Session::destroy(&session).await;
+    record_audit_event(&user, None, AuditEvent::SessionRevoked).await;
+
+    Ok(SecurityResult::Ok)
+}
@@ -0,0 +1,57 @@
+mod reaction;
+mod solution;
+mod visibility;
+mod vote;
+
+pub use self::reaction::*;
+pub use self::solution::*;
+pub use self::visibility::*;
+pub use self::vote::*;
+
+use cfg_if::cfg_if;
+
+cfg_if! {
+if #[cfg(feature = "ssr")] {
+    use std::collections::HashMap;
+    use crate::repository::{ReactionRepository, VoteRepository};
+
+    /// Count the reactions on a solution, marking the ones of the given user.
+    fn count_reactions(solution: &str, reactions: &[ReactionRepository], user: Option<&str>) -> Vec<ReactionCount> {
+        Reaction::ALL
+            .into_iter()
+            .map(|kind| {
+                let matching = reactions
+                    .iter()
+                    .filter(|reaction| reaction.solution == solution && reaction.reaction == kind)
+                    .collect::<Vec<_>>();
+
+                ReactionCount {
+                    reaction: kind,
+                    count: matching.len(),
+                    reacted: matching.iter().any(|reaction| Some(reaction.user.as_str()) == user),
+                }
+            })
+            .collect()
+    }
+
+    /// Determine the favourite solutions among the given votes. On a tie, all solutions with the
+    /// most votes are favourites.
+    fn favourites(votes: &[VoteRepository]) -> Vec<String> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+
+        for vote in votes {
+            *counts.entry(vote.solution.as_str()).or_default() += 1;
+        }
+
+        let Some(max) = counts.values().max().copied() else {
+            return vec![];
+        };
+
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count == max)
+            .map(|(solution, _)| solution.to_string())
+            .collect()
+    }
+}
+}
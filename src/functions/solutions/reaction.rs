@@ -0,0 +1,116 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::is_frozen, hooks::use_user, model::Solution, repository::ReactionRepository,
+};
+
+#[cfg(feature = "ssr")]
+use super::filter_visible;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Reaction {
+    #[default]
+    ThumbsUp,
+    Heart,
+    Rocket,
+    Eyes,
+    Laugh,
+    MindBlown,
+}
+
+impl Reaction {
+    pub const ALL: [Reaction; 6] = [
+        Reaction::ThumbsUp,
+        Reaction::Heart,
+        Reaction::Rocket,
+        Reaction::Eyes,
+        Reaction::Laugh,
+        Reaction::MindBlown,
+    ];
+
+    pub fn emoji(&self) -> &'static str {
+        use Reaction::*;
+
+        match self {
+            ThumbsUp => "👍",
+            Heart => "❤️",
+            Rocket => "🚀",
+            Eyes => "👀",
+            Laugh => "😄",
+            MindBlown => "🤯",
+        }
+    }
+}
+
+impl Display for Reaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.emoji())
+    }
+}
+
+/// Number of reactions of a single kind on a solution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReactionCount {
+    pub reaction: Reaction,
+    pub count: usize,
+    /// Whether the current user is among the ones who reacted.
+    pub reacted: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ReactionResult {
+    Ok,
+    NotLoggedIn,
+    NotFound,
+    YearFrozen,
+    InternalServerError,
+}
+
+impl Display for ReactionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ReactionResult::*;
+
+        match self {
+            Ok => f.write_str("Reaction saved"),
+            NotLoggedIn => f.write_str("You need to be logged in to react"),
+            NotFound => f.write_str("Solution not found"),
+            YearFrozen => f.write_str("This year is archived and read-only"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn toggle_reaction(
+    solution: String,
+    reaction: Reaction,
+) -> Result<ReactionResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(ReactionResult::NotLoggedIn);
+    };
+
+    let Some(solution) = Solution::get_by_id(&solution).await else {
+        return Ok(ReactionResult::NotFound);
+    };
+
+    // nobody can react to what they cannot see
+    let Some(solution) = filter_visible(vec![solution], Some(&user)).await.pop() else {
+        return Ok(ReactionResult::NotFound);
+    };
+
+    if is_frozen(solution.year).await {
+        return Ok(ReactionResult::YearFrozen);
+    }
+
+    if let Err(e) = ReactionRepository::toggle(user.id, solution.id, reaction).await {
+        tracing::error!("failed to toggle reaction: {e:?}");
+        return Ok(ReactionResult::InternalServerError);
+    }
+
+    Ok(ReactionResult::Ok)
+}
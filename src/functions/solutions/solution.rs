@@ -0,0 +1,319 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use super::{ReactionCount, Visibility};
+
+#[cfg(feature = "ssr")]
+use chrono::Utc;
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::is_frozen,
+    hooks::use_user,
+    model::{Solution, User},
+    repository::{ReactionRepository, VoteRepository},
+    utils::{calendar::is_unlocked, markdown::render_markdown},
+};
+
+#[cfg(feature = "ssr")]
+use super::{count_reactions, favourites, filter_visible};
+
+/// Languages a solution can be submitted in. The identifiers match the ones of the highlighter.
+pub const LANGUAGES: [&str; 14] = [
+    "rust",
+    "python",
+    "haskell",
+    "java",
+    "kotlin",
+    "scala",
+    "c",
+    "cpp",
+    "csharp",
+    "go",
+    "javascript",
+    "typescript",
+    "swift",
+    "clike",
+];
+
+/// Get the file extension commonly used for source files of a language.
+pub fn file_extension(language: &str) -> &'static str {
+    match language {
+        "rust" => "rs",
+        "python" => "py",
+        "haskell" => "hs",
+        "java" => "java",
+        "kotlin" => "kt",
+        "scala" => "scala",
+        "c" => "c",
+        "cpp" => "cpp",
+        "csharp" => "cs",
+        "go" => "go",
+        "javascript" => "js",
+        "typescript" => "ts",
+        "swift" => "swift",
+        _ => "txt",
+    }
+}
+
+/// Get the language of a source file from its extension, the reverse of [`file_extension`].
+/// Languages without an extension of their own cannot be recognized.
+pub fn language_of_extension(extension: &str) -> Option<&'static str> {
+    // the extension of unknown languages
+    if extension == file_extension("") {
+        return None;
+    }
+
+    LANGUAGES
+        .into_iter()
+        .find(|language| file_extension(language) == extension)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolutionData {
+    pub id: String,
+    pub author: String,
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+    pub code: String,
+    /// Markdown source of the write-up attached to this solution.
+    pub writeup: Option<String>,
+    /// Sanitized HTML rendering of the write-up.
+    pub writeup_html: Option<String>,
+    /// Visibility override of this solution. Only sent to the author.
+    pub visibility: Option<Visibility>,
+    /// Whether the current user is the author of this solution.
+    pub own: bool,
+    pub reactions: Vec<ReactionCount>,
+    pub votes: usize,
+    /// Whether the current user voted for this solution.
+    pub voted: bool,
+    /// Whether this solution is the favourite solution of its day.
+    pub favourite: bool,
+    /// Reason a moderator hid this solution for. Only sent to the author.
+    pub hidden_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SubmissionResult {
+    Ok,
+    NotLoggedIn,
+    InvalidDay,
+    Locked,
+    InvalidLanguage,
+    InvalidVisibility,
+    YearFrozen,
+    InternalServerError,
+}
+
+impl Display for SubmissionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use SubmissionResult::*;
+
+        match self {
+            Ok => f.write_str("Solution submitted"),
+            NotLoggedIn => f.write_str("You need to be logged in to submit solutions"),
+            InvalidDay => f.write_str("There is no such day"),
+            Locked => f.write_str("This puzzle is not unlocked yet"),
+            InvalidLanguage => f.write_str("Unsupported language"),
+            InvalidVisibility => f.write_str("Invalid visibility"),
+            YearFrozen => f.write_str("This year is archived and read-only"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_solutions(
+    author: String,
+    year: i32,
+    day: u8,
+) -> Result<Vec<SolutionData>, ServerFnError> {
+    let user = use_user().await;
+    let user_id = user.as_ref().map(|user| user.id.clone());
+
+    let solutions = filter_visible(
+        Solution::get_by_author(&author, year, day).await,
+        user.as_ref(),
+    )
+    .await;
+
+    let reactions = ReactionRepository::get_by_solutions(
+        solutions
+            .iter()
+            .map(|solution| solution.id.clone())
+            .collect(),
+    )
+    .await
+    .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let votes = VoteRepository::get_by_day(year, day)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let favourites = favourites(&votes);
+
+    Ok(solutions
+        .into_iter()
+        .map(|solution| {
+            let solution_votes = votes
+                .iter()
+                .filter(|vote| vote.solution == solution.id)
+                .collect::<Vec<_>>();
+
+            let Solution {
+                id,
+                author,
+                year,
+                day,
+                part,
+                language,
+                code,
+                writeup,
+                visibility,
+                hidden_reason,
+                ..
+            } = solution;
+
+            let own = user.as_ref().is_some_and(|user| user.username == author);
+
+            SolutionData {
+                visibility: visibility.filter(|_| own),
+                hidden_reason: hidden_reason.filter(|_| own),
+                own,
+                writeup_html: writeup.as_deref().map(render_markdown),
+                writeup,
+                reactions: count_reactions(&id, &reactions, user_id.as_deref()),
+                votes: solution_votes.len(),
+                voted: solution_votes
+                    .iter()
+                    .any(|vote| Some(&vote.user) == user_id.as_ref()),
+                favourite: favourites.contains(&id),
+                id,
+                author,
+                year,
+                day,
+                part,
+                language,
+                code,
+            }
+        })
+        .collect())
+}
+
+/// Validate and store a new solution of the given user.
+#[cfg(feature = "ssr")]
+#[allow(clippy::too_many_arguments)]
+pub async fn save_solution(
+    user: &User,
+    year: i32,
+    day: u8,
+    part: u8,
+    language: String,
+    code: String,
+    writeup: Option<String>,
+    visibility: Option<Visibility>,
+) -> SubmissionResult {
+    if !(1..=25).contains(&day) || !(1..=2).contains(&part) {
+        return SubmissionResult::InvalidDay;
+    }
+
+    if !is_unlocked(year, day, Utc::now()) {
+        return SubmissionResult::Locked;
+    }
+
+    if !LANGUAGES.contains(&language.as_str()) {
+        return SubmissionResult::InvalidLanguage;
+    }
+
+    if visibility.is_some_and(|visibility| !visibility.is_valid()) {
+        return SubmissionResult::InvalidVisibility;
+    }
+
+    if is_frozen(year).await {
+        return SubmissionResult::YearFrozen;
+    }
+
+    if let Err(e) = (Solution {
+        author: user.username.clone(),
+        year,
+        day,
+        part,
+        language,
+        code,
+        writeup: writeup.filter(|writeup| !writeup.trim().is_empty()),
+        visibility,
+        ..Default::default()
+    })
+    .save()
+    .await
+    {
+        tracing::error!("failed to save solution: {e:?}");
+        return SubmissionResult::InternalServerError;
+    }
+
+    SubmissionResult::Ok
+}
+
+#[tracing::instrument(level = "trace", skip(code, writeup))]
+#[server]
+pub async fn submit_solution(
+    year: i32,
+    day: u8,
+    part: u8,
+    language: String,
+    code: String,
+    writeup: String,
+    visibility: String,
+    hours: u32,
+) -> Result<SubmissionResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(SubmissionResult::NotLoggedIn);
+    };
+
+    let visibility = match visibility.as_str() {
+        "" => None,
+        kind => match Visibility::from_form(kind, hours) {
+            Some(visibility) => Some(visibility),
+            None => return Ok(SubmissionResult::InvalidVisibility),
+        },
+    };
+
+    Ok(save_solution(
+        &user,
+        year,
+        day,
+        part,
+        language,
+        code,
+        Some(writeup),
+        visibility,
+    )
+    .await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_of_extension() {
+        for language in LANGUAGES
+            .into_iter()
+            .filter(|language| *language != "clike")
+        {
+            assert_eq!(
+                language_of_extension(file_extension(language)),
+                Some(language)
+            );
+        }
+
+        assert_eq!(language_of_extension("txt"), None);
+        assert_eq!(language_of_extension("md"), None);
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Duration, Utc};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::calendar::{is_unlocked, unlock_time};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::is_frozen,
+    hooks::use_user,
+    model::{Solution, User},
+};
+
+/// Who is allowed to see a solution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub enum Visibility {
+    /// Visible to everyone.
+    #[default]
+    Public,
+    /// Visible to logged in users only.
+    Members,
+    /// Visible to the author only.
+    Private,
+    /// Visible to everyone once the given amount of hours has passed since the puzzle unlocked.
+    Delayed(u32),
+    /// Visible to everyone once the global leaderboard of the day closed.
+    ///
+    /// The global leaderboard is not tracked by this site, so it is considered closed once the
+    /// puzzle of the next day unlocks.
+    AfterLeaderboard,
+}
+
+impl Visibility {
+    pub const KINDS: [&'static str; 5] = [
+        "public",
+        "members",
+        "private",
+        "delayed",
+        "after-leaderboard",
+    ];
+
+    /// Longest delay of a delayed visibility, a year.
+    pub const MAX_DELAY_HOURS: u32 = 366 * 24;
+
+    /// Construct a visibility from the values of a form.
+    pub fn from_form(kind: &str, hours: u32) -> Option<Visibility> {
+        use Visibility::*;
+
+        match kind {
+            "public" => Some(Public),
+            "members" => Some(Members),
+            "private" => Some(Private),
+            "delayed" => Some(Delayed(hours)).filter(Visibility::is_valid),
+            "after-leaderboard" => Some(AfterLeaderboard),
+            _ => None,
+        }
+    }
+
+    /// Check, whether the delay of this visibility is within [`Visibility::MAX_DELAY_HOURS`].
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Visibility::Delayed(hours) => *hours <= Self::MAX_DELAY_HOURS,
+            _ => true,
+        }
+    }
+
+    /// Identifier of this visibility used in forms.
+    pub fn kind(&self) -> &'static str {
+        use Visibility::*;
+
+        match self {
+            Public => "public",
+            Members => "members",
+            Private => "private",
+            Delayed(_) => "delayed",
+            AfterLeaderboard => "after-leaderboard",
+        }
+    }
+
+    /// Check, whether a solution for the given day with this visibility can be seen by somebody
+    /// other than its author. Nothing is visible before the puzzle unlocks.
+    pub fn is_visible(&self, year: i32, day: u8, logged_in: bool, now: DateTime<Utc>) -> bool {
+        use Visibility::*;
+
+        if !is_unlocked(year, day, now) {
+            return false;
+        }
+
+        match self {
+            Public => true,
+            Members => logged_in,
+            Private => false,
+            // delays beyond the maximum would overflow, they are never visible
+            Delayed(hours) => {
+                self.is_valid()
+                    && unlock_time(year, day)
+                        .and_then(|unlock| {
+                            unlock.checked_add_signed(Duration::hours(i64::from(*hours)))
+                        })
+                        .is_some_and(|visible_from| now >= visible_from)
+            }
+            AfterLeaderboard => {
+                unlock_time(year, day).is_some_and(|unlock| now >= unlock + Duration::days(1))
+            }
+        }
+    }
+}
+
+impl Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Visibility::*;
+
+        match self {
+            Public => f.write_str("Public"),
+            Members => f.write_str("Members only"),
+            Private => f.write_str("Private"),
+            Delayed(hours) => write!(f, "Public {hours} hours after unlock"),
+            AfterLeaderboard => f.write_str("Public after the global leaderboard closed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum VisibilityResult {
+    Ok,
+    NotLoggedIn,
+    NotFound,
+    InvalidVisibility,
+    YearFrozen,
+    InternalServerError,
+}
+
+impl Display for VisibilityResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use VisibilityResult::*;
+
+        match self {
+            Ok => f.write_str("Visibility saved"),
+            NotLoggedIn => f.write_str("You need to be logged in to change visibilities"),
+            NotFound => f.write_str("Solution not found"),
+            InvalidVisibility => f.write_str("Invalid visibility"),
+            YearFrozen => f.write_str("This year is archived and read-only"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Set the default visibility of all solutions of the current user.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn set_default_visibility(
+    kind: String,
+    hours: u32,
+) -> Result<VisibilityResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(VisibilityResult::NotLoggedIn);
+    };
+
+    let Some(visibility) = Visibility::from_form(&kind, hours) else {
+        return Ok(VisibilityResult::InvalidVisibility);
+    };
+
+    if let Err(e) = user.set_default_visibility(visibility).await {
+        tracing::error!("failed to set default visibility: {e:?}");
+        return Ok(VisibilityResult::InternalServerError);
+    }
+
+    Ok(VisibilityResult::Ok)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_default_visibility() -> Result<Option<Visibility>, ServerFnError> {
+    Ok(use_user().await.map(|user| user.default_visibility))
+}
+
+/// Override the visibility of a single solution. An empty `kind` resets it to the default
+/// visibility of the author.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn set_solution_visibility(
+    solution: String,
+    kind: String,
+    hours: u32,
+) -> Result<VisibilityResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(VisibilityResult::NotLoggedIn);
+    };
+
+    let Some(solution) = Solution::get_by_id(&solution).await else {
+        return Ok(VisibilityResult::NotFound);
+    };
+
+    if solution.author != user.username {
+        return Ok(VisibilityResult::NotFound);
+    }
+
+    if is_frozen(solution.year).await {
+        return Ok(VisibilityResult::YearFrozen);
+    }
+
+    let visibility = match kind.as_str() {
+        "" => None,
+        kind => match Visibility::from_form(kind, hours) {
+            Some(visibility) => Some(visibility),
+            None => return Ok(VisibilityResult::InvalidVisibility),
+        },
+    };
+
+    if let Err(e) = solution.set_visibility(visibility).await {
+        tracing::error!("failed to set visibility of solution: {e:?}");
+        return Ok(VisibilityResult::InternalServerError);
+    }
+
+    Ok(VisibilityResult::Ok)
+}
+
+/// Remove all solutions the viewer is not allowed to see. Solutions hidden by a moderator are
+/// only visible to their author.
+#[cfg(feature = "ssr")]
+pub async fn filter_visible(solutions: Vec<Solution>, viewer: Option<&User>) -> Vec<Solution> {
+    let now = Utc::now();
+    let mut defaults: Vec<(String, Visibility)> = vec![];
+    let mut visible = vec![];
+
+    for solution in solutions {
+        if viewer.is_some_and(|viewer| viewer.username == solution.author) {
+            visible.push(solution);
+            continue;
+        }
+
+        if solution.hidden_reason.is_some() {
+            continue;
+        }
+
+        let default = match defaults
+            .iter()
+            .find(|(author, _)| *author == solution.author)
+        {
+            Some((_, default)) => *default,
+            None => {
+                let default = User::get_by_username(&solution.author)
+                    .await
+                    .map(|author| author.default_visibility)
+                    .unwrap_or(Visibility::Private);
+                defaults.push((solution.author.clone(), default));
+                default
+            }
+        };
+
+        if solution.visibility.unwrap_or(default).is_visible(
+            solution.year,
+            solution.day,
+            viewer.is_some(),
+            now,
+        ) {
+            visible.push(solution);
+        }
+    }
+
+    visible
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visibility_before_unlock() {
+        let now = unlock_time(2023, 2).unwrap() - Duration::seconds(1);
+
+        assert!(Visibility::Public.is_visible(2023, 1, false, now));
+        assert!(!Visibility::Public.is_visible(2023, 2, true, now));
+    }
+
+    #[test]
+    fn test_visibility_members() {
+        let now = unlock_time(2023, 1).unwrap();
+
+        assert!(Visibility::Members.is_visible(2023, 1, true, now));
+        assert!(!Visibility::Members.is_visible(2023, 1, false, now));
+    }
+
+    #[test]
+    fn test_visibility_private() {
+        let now = unlock_time(2023, 25).unwrap() + Duration::days(365);
+
+        assert!(!Visibility::Private.is_visible(2023, 1, true, now));
+    }
+
+    #[test]
+    fn test_visibility_delayed() {
+        let unlock = unlock_time(2023, 3).unwrap();
+
+        assert!(!Visibility::Delayed(2).is_visible(2023, 3, true, unlock + Duration::hours(1)));
+        assert!(Visibility::Delayed(2).is_visible(2023, 3, false, unlock + Duration::hours(2)));
+    }
+
+    #[test]
+    fn test_visibility_delayed_overflow() {
+        let now = unlock_time(2023, 3).unwrap() + Duration::days(365);
+
+        assert!(!Visibility::Delayed(u32::MAX).is_visible(2023, 3, true, now));
+        assert!(!Visibility::Delayed(u32::MAX).is_valid());
+        assert!(Visibility::Delayed(Visibility::MAX_DELAY_HOURS).is_valid());
+    }
+
+    #[test]
+    fn test_visibility_after_leaderboard() {
+        let unlock = unlock_time(2023, 3).unwrap();
+
+        assert!(!Visibility::AfterLeaderboard.is_visible(2023, 3, true, unlock));
+        assert!(Visibility::AfterLeaderboard.is_visible(
+            2023,
+            3,
+            true,
+            unlock_time(2023, 4).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_visibility_from_form() {
+        for kind in Visibility::KINDS {
+            assert_eq!(Visibility::from_form(kind, 3).unwrap().kind(), kind);
+        }
+
+        assert_eq!(
+            Visibility::from_form("delayed", 3),
+            Some(Visibility::Delayed(3))
+        );
+        assert_eq!(Visibility::from_form("unknown", 3), None);
+        assert_eq!(Visibility::from_form("delayed", u32::MAX), None);
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::is_frozen,
+    hooks::use_user,
+    model::Solution,
+    repository::{ReactionRepository, VoteRepository},
+};
+
+#[cfg(feature = "ssr")]
+use super::{favourites, filter_visible};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum VoteResult {
+    Ok,
+    NotLoggedIn,
+    NotFound,
+    OwnSolution,
+    YearFrozen,
+    InternalServerError,
+}
+
+impl Display for VoteResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use VoteResult::*;
+
+        match self {
+            Ok => f.write_str("Vote saved"),
+            NotLoggedIn => f.write_str("You need to be logged in to vote"),
+            NotFound => f.write_str("Solution not found"),
+            OwnSolution => f.write_str("You can not vote for your own solution"),
+            YearFrozen => f.write_str("This year is archived and read-only"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Summary of the solutions of an author for a single day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorOverview {
+    pub author: String,
+    pub reactions: usize,
+    pub votes: usize,
+    pub favourite: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HallOfFameEntry {
+    pub day: u8,
+    pub part: u8,
+    pub author: String,
+    pub solution: String,
+    pub votes: usize,
+}
+
+/// Vote for the favourite solution of a day. Each user has a single vote per day.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn vote_solution(solution: String) -> Result<VoteResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(VoteResult::NotLoggedIn);
+    };
+
+    let Some(solution) = Solution::get_by_id(&solution).await else {
+        return Ok(VoteResult::NotFound);
+    };
+
+    // nobody can vote for what they cannot see
+    let Some(solution) = filter_visible(vec![solution], Some(&user)).await.pop() else {
+        return Ok(VoteResult::NotFound);
+    };
+
+    if solution.author == user.username {
+        return Ok(VoteResult::OwnSolution);
+    }
+
+    if is_frozen(solution.year).await {
+        return Ok(VoteResult::YearFrozen);
+    }
+
+    if let Err(e) = VoteRepository::cast(user.id, solution.id, solution.year, solution.day).await {
+        tracing::error!("failed to cast vote: {e:?}");
+        return Ok(VoteResult::InternalServerError);
+    }
+
+    Ok(VoteResult::Ok)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_day_overview(year: i32, day: u8) -> Result<Vec<AuthorOverview>, ServerFnError> {
+    let user = use_user().await;
+    let solutions = filter_visible(Solution::get_by_day(year, day).await, user.as_ref()).await;
+
+    let reactions = ReactionRepository::get_by_solutions(
+        solutions
+            .iter()
+            .map(|solution| solution.id.clone())
+            .collect(),
+    )
+    .await
+    .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let votes = VoteRepository::get_by_day(year, day)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let favourites = favourites(&votes);
+
+    let mut overview: Vec<AuthorOverview> = vec![];
+
+    for solution in solutions {
+        let index = match overview
+            .iter()
+            .position(|entry| entry.author == solution.author)
+        {
+            Some(index) => index,
+            None => {
+                overview.push(AuthorOverview {
+                    author: solution.author.clone(),
+                    reactions: 0,
+                    votes: 0,
+                    favourite: false,
+                });
+                overview.len() - 1
+            }
+        };
+
+        let entry = &mut overview[index];
+        entry.reactions += reactions
+            .iter()
+            .filter(|reaction| reaction.solution == solution.id)
+            .count();
+        entry.votes += votes
+            .iter()
+            .filter(|vote| vote.solution == solution.id)
+            .count();
+        entry.favourite |= favourites.contains(&solution.id);
+    }
+
+    Ok(overview)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_hall_of_fame(year: i32) -> Result<Vec<HallOfFameEntry>, ServerFnError> {
+    let votes = VoteRepository::get_by_year(year)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let user = use_user().await;
+    let mut entries = vec![];
+
+    for day in 1..=25 {
+        let votes = votes
+            .iter()
+            .filter(|vote| vote.day == day)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for id in favourites(&votes) {
+            let Some(solution) = Solution::get_by_id(&id).await else {
+                continue;
+            };
+
+            let Some(solution) = filter_visible(vec![solution], user.as_ref()).await.pop() else {
+                continue;
+            };
+
+            entries.push(HallOfFameEntry {
+                day,
+                part: solution.part,
+                author: solution.author,
+                votes: votes.iter().filter(|vote| vote.solution == id).count(),
+                solution: id,
+            });
+        }
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,406 @@
+use std::fmt::Display;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::teams::{Aggregation, TeamStanding};
+
+#[cfg(feature = "ssr")]
+use std::collections::HashMap;
+
+#[cfg(feature = "ssr")]
+use crate::{
+    hooks::use_user,
+    repository::{
+        LeaderboardSnapshotRepository, MemberLinkRepository, TeamMemberRepository, TeamRepository,
+        UserRepository,
+    },
+    utils::{
+        scoring::LocalScore,
+        teams::{team_standings, TeamMemberScore},
+    },
+};
+
+const MIN_NAME_LENGTH: usize = 3;
+const MAX_NAME_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamMemberData {
+    pub id: String,
+    pub username: String,
+    /// Whether the invitation was accepted.
+    pub accepted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamData {
+    pub id: String,
+    pub name: String,
+    /// Whether the current user owns the team.
+    pub own: bool,
+    pub members: Vec<TeamMemberData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamInvitation {
+    pub id: String,
+    pub team: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum TeamResult {
+    Ok,
+    NotLoggedIn,
+    InvalidName,
+    NameTaken,
+    AlreadyInTeam,
+    NotInTeam,
+    UnknownUser,
+    AlreadyInvited,
+    NotAllowed,
+    NotFound,
+    InternalServerError,
+}
+
+impl Display for TeamResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TeamResult::*;
+
+        match self {
+            Ok => f.write_str("Success"),
+            NotLoggedIn => f.write_str("You need to be logged in"),
+            InvalidName => write!(
+                f,
+                "Team names must have between {MIN_NAME_LENGTH} and {MAX_NAME_LENGTH} characters"
+            ),
+            NameTaken => f.write_str("A team with this name already exists"),
+            AlreadyInTeam => f.write_str("You are already member of a team"),
+            NotInTeam => f.write_str("You are not member of a team"),
+            UnknownUser => f.write_str("No user with this name exists"),
+            AlreadyInvited => f.write_str("This user is already invited to or member of your team"),
+            NotAllowed => f.write_str("Only the owner of a team can do this"),
+            NotFound => f.write_str("Not found"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Get the accepted membership of a user, i.e., the team the user is part of.
+#[cfg(feature = "ssr")]
+async fn get_membership(user: &str) -> Result<Option<TeamMemberRepository>, ServerFnError> {
+    Ok(TeamMemberRepository::get_by_user(user)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .into_iter()
+        .find(|membership| membership.accepted))
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_team_leaderboard(
+    year: i32,
+    aggregation: Aggregation,
+) -> Result<Vec<TeamStanding>, ServerFnError> {
+    let teams = TeamRepository::get_all()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let memberships = TeamMemberRepository::get_accepted()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let links = MemberLinkRepository::get_confirmed()
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .into_iter()
+        .map(|link| (link.user, link.member_id))
+        .collect::<HashMap<_, _>>();
+
+    let scores = LeaderboardSnapshotRepository::get_latest(year)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .map(|snapshot| {
+            snapshot
+                .leaderboard
+                .standings(&LocalScore)
+                .into_iter()
+                .filter_map(|standing| Some((standing.member_id, standing.score?)))
+                .collect::<HashMap<_, _>>()
+        })
+        .unwrap_or_default();
+
+    let teams = teams
+        .into_iter()
+        .filter_map(|team| {
+            let id = team.id()?;
+            let members = memberships
+                .iter()
+                .filter(|membership| membership.team == id)
+                .map(|membership| TeamMemberScore {
+                    username: membership.username.clone(),
+                    score: links
+                        .get(&membership.user)
+                        .and_then(|member_id| scores.get(member_id))
+                        .copied(),
+                })
+                .collect();
+
+            Some((id, team.name, members))
+        })
+        .collect();
+
+    Ok(team_standings(teams, aggregation))
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_own_team() -> Result<Option<TeamData>, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(None);
+    };
+
+    let Some(membership) = get_membership(&user.id).await? else {
+        return Ok(None);
+    };
+
+    let Some(team) = TeamRepository::get_by_id(&membership.team)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+    else {
+        return Ok(None);
+    };
+
+    let members = TeamMemberRepository::get_by_team(&membership.team)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .into_iter()
+        .filter_map(|member| {
+            Some(TeamMemberData {
+                id: member.id()?,
+                username: member.username,
+                accepted: member.accepted,
+            })
+        })
+        .collect();
+
+    Ok(Some(TeamData {
+        id: membership.team,
+        own: team.owner == user.id,
+        name: team.name,
+        members,
+    }))
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_team_invitations() -> Result<Vec<TeamInvitation>, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(vec![]);
+    };
+
+    let memberships = TeamMemberRepository::get_by_user(&user.id)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    let mut invitations = vec![];
+    for membership in memberships.into_iter().filter(|m| !m.accepted) {
+        let team = TeamRepository::get_by_id(&membership.team)
+            .await
+            .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+        if let (Some(id), Some(team)) = (membership.id(), team) {
+            invitations.push(TeamInvitation {
+                id,
+                team: team.name,
+            });
+        }
+    }
+
+    Ok(invitations)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn create_team(name: String) -> Result<TeamResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(TeamResult::NotLoggedIn);
+    };
+
+    let name = name.trim().to_string();
+    if !(MIN_NAME_LENGTH..=MAX_NAME_LENGTH).contains(&name.chars().count()) {
+        return Ok(TeamResult::InvalidName);
+    }
+
+    if get_membership(&user.id).await?.is_some() {
+        return Ok(TeamResult::AlreadyInTeam);
+    }
+
+    if let Ok(Some(_)) = TeamRepository::get_by_name(&name).await {
+        return Ok(TeamResult::NameTaken);
+    }
+
+    let team = match TeamRepository::create(name, user.id.clone()).await {
+        Ok(Some(team)) => team.id().expect("team from database should have id"),
+        Ok(None) => return Ok(TeamResult::InternalServerError),
+        Err(e) => {
+            tracing::error!("failed to create team: {e:?}");
+            return Ok(TeamResult::InternalServerError);
+        }
+    };
+
+    if let Err(e) = TeamMemberRepository::create(team, user.id, user.username, true).await {
+        tracing::error!("failed to add owner to team: {e:?}");
+        return Ok(TeamResult::InternalServerError);
+    }
+
+    Ok(TeamResult::Ok)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn invite_to_team(username: String) -> Result<TeamResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(TeamResult::NotLoggedIn);
+    };
+
+    let Some(membership) = get_membership(&user.id).await? else {
+        return Ok(TeamResult::NotInTeam);
+    };
+
+    let Ok(Some(team)) = TeamRepository::get_by_id(&membership.team).await else {
+        return Ok(TeamResult::NotFound);
+    };
+
+    if team.owner != user.id {
+        return Ok(TeamResult::NotAllowed);
+    }
+
+    let Ok(Some(invitee)) = UserRepository::get_by_username(username.trim()).await else {
+        return Ok(TeamResult::UnknownUser);
+    };
+    let invitee_id = invitee.id().expect("user from database should have id");
+
+    let members = TeamMemberRepository::get_by_team(&membership.team)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    if members.iter().any(|member| member.user == invitee_id) {
+        return Ok(TeamResult::AlreadyInvited);
+    }
+
+    if let Err(e) =
+        TeamMemberRepository::create(membership.team, invitee_id, invitee.username, false).await
+    {
+        tracing::error!("failed to invite user to team: {e:?}");
+        return Ok(TeamResult::InternalServerError);
+    }
+
+    Ok(TeamResult::Ok)
+}
+
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn accept_team_invitation(invitation: String) -> Result<TeamResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(TeamResult::NotLoggedIn);
+    };
+
+    let Ok(Some(membership)) = TeamMemberRepository::get_by_id(&invitation).await else {
+        return Ok(TeamResult::NotFound);
+    };
+
+    if membership.user != user.id || membership.accepted {
+        return Ok(TeamResult::NotFound);
+    }
+
+    if get_membership(&user.id).await?.is_some() {
+        return Ok(TeamResult::AlreadyInTeam);
+    }
+
+    let id = membership
+        .id()
+        .expect("membership from database should have id");
+
+    if let Err(e) = TeamMemberRepository::accept(&id, &user.id).await {
+        tracing::error!("failed to accept team invitation: {e:?}");
+        return Ok(TeamResult::InternalServerError);
+    }
+
+    Ok(TeamResult::Ok)
+}
+
+/// Remove a member from a team or revoke an invitation. Users can decline their own invitations,
+/// the owner of a team can remove everybody else.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn remove_team_member(membership: String) -> Result<TeamResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(TeamResult::NotLoggedIn);
+    };
+
+    let Ok(Some(member)) = TeamMemberRepository::get_by_id(&membership).await else {
+        return Ok(TeamResult::NotFound);
+    };
+
+    let is_owner = TeamRepository::get_by_id(&member.team)
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|team| team.owner == user.id);
+
+    let allowed = if member.user == user.id {
+        !member.accepted
+    } else {
+        is_owner
+    };
+
+    if !allowed {
+        return Ok(TeamResult::NotAllowed);
+    }
+
+    let id = member
+        .id()
+        .expect("membership from database should have id");
+
+    if let Err(e) = TeamMemberRepository::delete(&id).await {
+        tracing::error!("failed to remove team member: {e:?}");
+        return Ok(TeamResult::InternalServerError);
+    }
+
+    Ok(TeamResult::Ok)
+}
+
+/// Leave the current team. When the owner leaves, the team is disbanded.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn leave_team() -> Result<TeamResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(TeamResult::NotLoggedIn);
+    };
+
+    let Some(membership) = get_membership(&user.id).await? else {
+        return Ok(TeamResult::NotInTeam);
+    };
+
+    let is_owner = TeamRepository::get_by_id(&membership.team)
+        .await
+        .ok()
+        .flatten()
+        .is_some_and(|team| team.owner == user.id);
+
+    let result = if is_owner {
+        TeamRepository::delete(&membership.team).await
+    } else {
+        let id = membership
+            .id()
+            .expect("membership from database should have id");
+        TeamMemberRepository::delete(&id).await
+    };
+
+    if let Err(e) = result {
+        tracing::error!("failed to leave team: {e:?}");
+        return Ok(TeamResult::InternalServerError);
+    }
+
+    Ok(TeamResult::Ok)
+}
@@ -0,0 +1,201 @@
+use std::{fmt::Display, str::FromStr};
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "ssr")]
+use crate::{
+    functions::AuditEvent,
+    hooks::{record_audit_event, use_preferences, use_user},
+    repository::ApiTokenRepository,
+    utils::token::{generate_token, hash_token},
+};
+
+/// Maximum length of the name of an API token.
+pub const MAX_TOKEN_NAME_LENGTH: usize = 64;
+
+/// What an API token is allowed to do. Each scope includes the permissions of the ones before it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TokenScope {
+    /// Read solutions, leaderboards and profiles.
+    #[default]
+    Read,
+    /// Additionally submit solutions.
+    Write,
+}
+
+impl TokenScope {
+    pub const ALL: [TokenScope; 2] = [TokenScope::Read, TokenScope::Write];
+
+    /// Identifier of this scope used in forms.
+    pub fn key(&self) -> &'static str {
+        match self {
+            TokenScope::Read => "read",
+            TokenScope::Write => "write",
+        }
+    }
+}
+
+impl FromStr for TokenScope {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TokenScope::ALL
+            .into_iter()
+            .find(|scope| scope.key() == s)
+            .ok_or(())
+    }
+}
+
+impl Display for TokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenScope::Read => f.write_str("Read"),
+            TokenScope::Write => f.write_str("Read and write"),
+        }
+    }
+}
+
+/// An API token as listed in the settings. The token itself is only shown once on creation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiTokenData {
+    pub id: String,
+    pub name: String,
+    pub scope: TokenScope,
+    /// Point in time of the creation, formatted in the time zone of the user.
+    pub created_at: String,
+    /// Point in time of the last request with this token, formatted in the time zone of the user.
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TokenResult {
+    /// The token was created; holds the token, which cannot be retrieved again.
+    Created(String),
+    Revoked,
+    NotLoggedIn,
+    NameMissing,
+    NameTooLong,
+    UnknownScope,
+    NotFound,
+    InternalServerError,
+}
+
+impl Display for TokenResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TokenResult::*;
+
+        match self {
+            Created(_) => f.write_str("Token created. Copy it now, it will not be shown again"),
+            Revoked => f.write_str("Token revoked"),
+            NotLoggedIn => f.write_str("You need to be logged in"),
+            NameMissing => f.write_str("Please give the token a name"),
+            NameTooLong => write!(
+                f,
+                "The name may have at most {MAX_TOKEN_NAME_LENGTH} characters"
+            ),
+            UnknownScope => f.write_str("Unknown scope"),
+            NotFound => f.write_str("Token not found"),
+            InternalServerError => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// Get the API tokens of the current user, oldest first.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn get_api_tokens() -> Result<Option<Vec<ApiTokenData>>, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(None);
+    };
+
+    let preferences = use_preferences().await;
+    let format_time = |time: &str| {
+        DateTime::parse_from_rfc3339(time)
+            .map(|time| preferences.format_time(time.with_timezone(&Utc)))
+            .unwrap_or_default()
+    };
+
+    let tokens = ApiTokenRepository::get_by_user(&user.id)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?;
+
+    Ok(Some(
+        tokens
+            .into_iter()
+            .filter_map(|token| {
+                Some(ApiTokenData {
+                    id: token.id()?,
+                    created_at: format_time(&token.created_at),
+                    last_used_at: token.last_used_at.as_deref().map(format_time),
+                    name: token.name,
+                    scope: token.scope,
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// Create a new API token for the current user.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn create_api_token(name: String, scope: String) -> Result<TokenResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(TokenResult::NotLoggedIn);
+    };
+
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Ok(TokenResult::NameMissing);
+    }
+    if name.chars().count() > MAX_TOKEN_NAME_LENGTH {
+        return Ok(TokenResult::NameTooLong);
+    }
+
+    let Ok(scope) = scope.parse::<TokenScope>() else {
+        return Ok(TokenResult::UnknownScope);
+    };
+
+    let token = generate_token();
+
+    if let Err(e) =
+        ApiTokenRepository::create(user.id.clone(), name.clone(), hash_token(&token), scope).await
+    {
+        tracing::error!("failed to create API token: {e:?}");
+        return Ok(TokenResult::InternalServerError);
+    }
+
+    record_audit_event(&user, None, AuditEvent::ApiTokenCreated { name, scope }).await;
+
+    Ok(TokenResult::Created(token))
+}
+
+/// Revoke an API token of the current user. Requests with the token fail immediately.
+#[tracing::instrument(level = "trace")]
+#[server]
+pub async fn revoke_api_token(token: String) -> Result<TokenResult, ServerFnError> {
+    let Some(user) = use_user().await else {
+        return Ok(TokenResult::NotLoggedIn);
+    };
+
+    let Some(owned) = ApiTokenRepository::get_by_user(&user.id)
+        .await
+        .map_err(|e| ServerFnError::ServerError(e.to_string()))?
+        .into_iter()
+        .find(|own| own.id().as_ref() == Some(&token))
+    else {
+        return Ok(TokenResult::NotFound);
+    };
+
+    if let Err(e) = ApiTokenRepository::delete(&token).await {
+        tracing::error!("failed to revoke API token: {e:?}");
+        return Ok(TokenResult::InternalServerError);
+    }
+
+    record_audit_event(&user, None, AuditEvent::ApiTokenRevoked(owned.name)).await;
+
+    Ok(TokenResult::Revoked)
+}
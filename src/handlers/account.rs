@@ -0,0 +1,99 @@
+use std::io::{Cursor, Write};
+
+use actix_web::{
+    get,
+    http::header::{ContentDisposition, DispositionParam, DispositionType},
+    web, HttpRequest, HttpResponse,
+};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    functions::{collect_account_export, AccountExport, AuditEvent},
+    hooks::use_request_user,
+    services::audit::{self, Subject},
+    utils::avatar::avatar_path,
+};
+
+/// Download everything stored about the current user as `json` or as `zip` archive, which
+/// additionally contains the code of all solutions and the avatar as separate files.
+#[tracing::instrument(level = "trace", skip(req))]
+#[get("/account/export.{format}")]
+pub async fn export_account(req: HttpRequest, path: web::Path<String>) -> HttpResponse {
+    let format = path.into_inner();
+
+    let Some(user) = use_request_user(&req).await else {
+        return HttpResponse::Unauthorized().finish();
+    };
+
+    let export = match collect_account_export(&user).await {
+        Ok(export) => export,
+        Err(e) => {
+            tracing::error!("failed to export account of '{}': {e:?}", user.id);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let Ok(json) = serde_json::to_string_pretty(&export) else {
+        return HttpResponse::InternalServerError().finish();
+    };
+
+    let (content_type, body) = match format.as_str() {
+        "json" => ("application/json", json.into_bytes()),
+        "zip" => {
+            let avatar = std::fs::read(avatar_path(&user.id)).ok();
+            match create_zip(&json, &export, avatar.as_deref()) {
+                Ok(zip) => ("application/zip", zip),
+                Err(e) => {
+                    tracing::error!("failed to create data export archive: {e:?}");
+                    return HttpResponse::InternalServerError().finish();
+                }
+            }
+        }
+        _ => return HttpResponse::NotFound().finish(),
+    };
+
+    audit::record(
+        Some(&req),
+        Subject::from(&user),
+        None,
+        AuditEvent::AccountExported,
+    )
+    .await;
+
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "{username}-data.{format}",
+                username = user.username
+            ))],
+        })
+        .body(body)
+}
+
+/// Create a ZIP archive containing the export as `account.json` together with the files of all
+/// solutions and the avatar.
+fn create_zip(
+    json: &str,
+    export: &AccountExport,
+    avatar: Option<&[u8]>,
+) -> zip::result::ZipResult<Vec<u8>> {
+    let mut archive = ZipWriter::new(Cursor::new(vec![]));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    archive.start_file("account.json", options)?;
+    archive.write_all(json.as_bytes())?;
+
+    for solution in &export.solutions {
+        archive.start_file(solution.file_name.as_str(), options)?;
+        archive.write_all(solution.code.as_bytes())?;
+    }
+
+    if let Some(avatar) = avatar {
+        archive.start_file("avatar.png", options)?;
+        archive.write_all(avatar)?;
+    }
+
+    Ok(archive.finish()?.into_inner())
+}
@@ -0,0 +1,433 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse, Scope};
+use serde::Deserialize;
+use utoipa::{
+    openapi::security::{Http, HttpAuthScheme, SecurityScheme},
+    IntoParams, Modify, OpenApi,
+};
+
+use crate::{
+    functions::{
+        collect_profile, filter_visible, get_standings, save_solution, ProfileSolution,
+        SubmissionResult, TokenScope, Visibility, YearStars,
+    },
+    hooks::use_api_user,
+    model::{Solution, User},
+    utils::{
+        api::{ApiError, ApiLeaderboard, ApiProfile, ApiSolution, NewSolution, API_V1},
+        leaderboard::Standing,
+        scoring::ScoringMode,
+    },
+};
+
+/// Declare the handlers of the REST API once for both the routes and the OpenAPI document, so
+/// neither can miss a handler of the other.
+macro_rules! api_routes {
+    ($($handler:ident),* $(,)?) => {
+        /// Routes of the REST API. Every request but the one for the OpenAPI document needs an API
+        /// token, see [`use_api_user`].
+        ///
+        /// The scope needs to be registered before the catch-all route of the server functions,
+        /// which would otherwise take over all requests below `/api`.
+        pub fn api_v1() -> Scope {
+            web::scope(API_V1)
+                .service(openapi_json)
+                $(.service($handler))*
+        }
+
+        /// OpenAPI document of the REST API, generated from the handlers below and the types
+        /// they exchange.
+        #[derive(OpenApi)]
+        #[openapi(
+            info(
+                title = "Advent of Code Website API",
+                description = "Solutions, leaderboards and profiles. Create an API token in the settings and send it as `Authorization: Bearer <token>` header."
+            ),
+            paths($($handler),*),
+            components(schemas(
+                ApiError,
+                ApiLeaderboard,
+                ApiProfile,
+                ApiSolution,
+                NewSolution,
+                ProfileSolution,
+                Standing,
+                Visibility,
+                YearStars
+            )),
+            modifiers(&TokenSecurity),
+            security(("token" = [])),
+            tags(
+                (name = "profiles", description = "Profiles of users"),
+                (name = "leaderboard", description = "Standings of the private leaderboard"),
+                (name = "solutions", description = "Solutions of the puzzles")
+            )
+        )]
+        pub struct ApiDoc;
+    };
+}
+
+api_routes!(
+    api_own_profile,
+    api_user_profile,
+    api_leaderboard,
+    api_solutions,
+    api_submit_solution,
+);
+
+/// Adds the API token as security scheme to the OpenAPI document.
+struct TokenSecurity;
+
+impl Modify for TokenSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "token",
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+    }
+}
+
+/// Serve the OpenAPI document of the REST API.
+#[tracing::instrument(level = "trace")]
+#[get("/openapi.json")]
+pub async fn openapi_json() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
+
+/// Get the user of the API token sent with the request, if it has the required scope.
+async fn authenticate(req: &HttpRequest, scope: TokenScope) -> Result<User, HttpResponse> {
+    let Some((user, granted)) = use_api_user(req).await else {
+        return Err(
+            HttpResponse::Unauthorized().json(ApiError::new("missing or invalid API token"))
+        );
+    };
+
+    if granted < scope {
+        return Err(HttpResponse::Forbidden().json(ApiError::new(format!(
+            "the API token lacks the '{}' scope",
+            scope.key()
+        ))));
+    }
+
+    Ok(user)
+}
+
+async fn profile_response(user: User, viewer: &User) -> HttpResponse {
+    match collect_profile(user, Some(viewer), &viewer.preferences).await {
+        Ok(profile) => HttpResponse::Ok().json(ApiProfile {
+            username: profile.username,
+            display_name: profile.display_name,
+            bio: profile.bio,
+            favourite_languages: profile.favourite_languages,
+            member_id: profile.member_id,
+            stars: profile.stars,
+            solutions: profile.solutions,
+        }),
+        Err(e) => {
+            tracing::error!("failed to collect profile: {e:?}");
+            HttpResponse::InternalServerError().json(ApiError::new("Internal Server Error"))
+        }
+    }
+}
+
+/// Get the profile of the owner of the API token.
+#[tracing::instrument(level = "trace", skip(req))]
+#[utoipa::path(
+    get,
+    path = "/api/v1/profile",
+    tag = "profiles",
+    responses(
+        (status = 200, description = "Profile of the owner of the token", body = ApiProfile),
+        (status = 401, description = "Missing or invalid API token", body = ApiError)
+    )
+)]
+#[get("/profile")]
+pub async fn api_own_profile(req: HttpRequest) -> HttpResponse {
+    let viewer = match authenticate(&req, TokenScope::Read).await {
+        Ok(viewer) => viewer,
+        Err(response) => return response,
+    };
+
+    profile_response(viewer.clone(), &viewer).await
+}
+
+/// Get the profile of a user.
+///
+/// Only the solutions visible to the owner of the API token are listed.
+#[tracing::instrument(level = "trace", skip(req))]
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{username}",
+    tag = "profiles",
+    params(("username" = String, Path, description = "Name of the user")),
+    responses(
+        (status = 200, description = "Profile of the user", body = ApiProfile),
+        (status = 401, description = "Missing or invalid API token", body = ApiError),
+        (status = 404, description = "Unknown user", body = ApiError)
+    )
+)]
+#[get("/users/{username}")]
+pub async fn api_user_profile(req: HttpRequest, path: web::Path<String>) -> HttpResponse {
+    let viewer = match authenticate(&req, TokenScope::Read).await {
+        Ok(viewer) => viewer,
+        Err(response) => return response,
+    };
+
+    let Some(user) = User::get_by_username(&path.into_inner()).await else {
+        return HttpResponse::NotFound().json(ApiError::new("unknown user"));
+    };
+
+    profile_response(user, &viewer).await
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LeaderboardQuery {
+    /// Identifier of the scoring mode (`local`, `stars`, `median`, `delta` or `morning`), defaults
+    /// to the local score.
+    scoring: Option<String>,
+}
+
+/// Get the standings of a year.
+///
+/// The standings are computed from the latest snapshot of the private leaderboard. Members are
+/// annotated with the users they are linked to.
+#[tracing::instrument(level = "trace", skip(req))]
+#[utoipa::path(
+    get,
+    path = "/api/v1/leaderboard/{year}",
+    tag = "leaderboard",
+    params(("year" = i32, Path, description = "Year of the event"), LeaderboardQuery),
+    responses(
+        (status = 200, description = "Standings of the year", body = ApiLeaderboard),
+        (status = 400, description = "Unknown scoring mode", body = ApiError),
+        (status = 401, description = "Missing or invalid API token", body = ApiError)
+    )
+)]
+#[get("/leaderboard/{year}")]
+pub async fn api_leaderboard(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    query: web::Query<LeaderboardQuery>,
+) -> HttpResponse {
+    if let Err(response) = authenticate(&req, TokenScope::Read).await {
+        return response;
+    }
+
+    let year = path.into_inner();
+
+    let scoring = match &query.scoring {
+        Some(scoring) => match scoring.parse::<ScoringMode>() {
+            Ok(scoring) => scoring,
+            Err(_) => return HttpResponse::BadRequest().json(ApiError::new("unknown scoring")),
+        },
+        None => ScoringMode::default(),
+    };
+
+    match get_standings(year, scoring).await {
+        Ok(Some((fetched_at, standings))) => HttpResponse::Ok().json(ApiLeaderboard {
+            year,
+            fetched_at: Some(fetched_at),
+            standings,
+        }),
+        Ok(None) => HttpResponse::Ok().json(ApiLeaderboard {
+            year,
+            fetched_at: None,
+            standings: vec![],
+        }),
+        Err(e) => {
+            tracing::error!("failed to compute standings: {e:?}");
+            HttpResponse::InternalServerError().json(ApiError::new("Internal Server Error"))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SolutionsQuery {
+    /// Only return the solutions of this user.
+    author: Option<String>,
+}
+
+/// Get the solutions of a day visible to the owner of the API token.
+///
+/// The solutions of others are only returned once the owner of the token submitted a solution
+/// for the day themselves.
+#[tracing::instrument(level = "trace", skip(req))]
+#[utoipa::path(
+    get,
+    path = "/api/v1/solutions/{year}/{day}",
+    tag = "solutions",
+    params(
+        ("year" = i32, Path, description = "Year of the event"),
+        ("day" = u8, Path, description = "Day of the puzzle"),
+        SolutionsQuery
+    ),
+    responses(
+        (status = 200, description = "Visible solutions of the day", body = [ApiSolution]),
+        (status = 401, description = "Missing or invalid API token", body = ApiError),
+        (status = 403, description = "No own solution for the day yet", body = ApiError)
+    )
+)]
+#[get("/solutions/{year}/{day}")]
+pub async fn api_solutions(
+    req: HttpRequest,
+    path: web::Path<(i32, u8)>,
+    query: web::Query<SolutionsQuery>,
+) -> HttpResponse {
+    let viewer = match authenticate(&req, TokenScope::Read).await {
+        Ok(viewer) => viewer,
+        Err(response) => return response,
+    };
+
+    let (year, day) = path.into_inner();
+
+    let own_solutions = Solution::get_by_author(&viewer.username, year, day).await;
+
+    let solutions = match &query.author {
+        Some(author) if *author == viewer.username => own_solutions,
+        _ if own_solutions.is_empty() => {
+            return HttpResponse::Forbidden().json(ApiError::new(format!(
+                "submit your own solution for {year}/{day} before viewing the ones of others"
+            )));
+        }
+        Some(author) => Solution::get_by_author(author, year, day).await,
+        None => Solution::get_by_day(year, day).await,
+    };
+
+    let solutions: Vec<_> = filter_visible(solutions, Some(&viewer))
+        .await
+        .into_iter()
+        .map(|solution| ApiSolution {
+            id: solution.id,
+            author: solution.author,
+            year: solution.year,
+            day: solution.day,
+            part: solution.part,
+            language: solution.language,
+            code: solution.code,
+            writeup: solution.writeup,
+            created_at: solution.created_at,
+        })
+        .collect();
+
+    HttpResponse::Ok().json(solutions)
+}
+
+/// Submit a solution as the owner of the API token.
+///
+/// Needs a token with the `write` scope.
+#[tracing::instrument(level = "trace", skip(req, body))]
+#[utoipa::path(
+    post,
+    path = "/api/v1/solutions",
+    tag = "solutions",
+    request_body = NewSolution,
+    responses(
+        (status = 201, description = "Solution submitted"),
+        (status = 400, description = "Invalid day, language or visibility", body = ApiError),
+        (status = 401, description = "Missing or invalid API token", body = ApiError),
+        (status = 403, description = "Token without `write` scope, or puzzle not unlocked yet", body = ApiError),
+        (status = 409, description = "Year is archived and read-only", body = ApiError)
+    )
+)]
+#[post("/solutions")]
+pub async fn api_submit_solution(req: HttpRequest, body: web::Json<NewSolution>) -> HttpResponse {
+    let user = match authenticate(&req, TokenScope::Write).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let NewSolution {
+        year,
+        day,
+        part,
+        language,
+        code,
+        writeup,
+        visibility,
+    } = body.into_inner();
+
+    let result = save_solution(&user, year, day, part, language, code, writeup, visibility).await;
+
+    let mut response = match result {
+        SubmissionResult::Ok => return HttpResponse::Created().finish(),
+        SubmissionResult::NotLoggedIn => HttpResponse::Unauthorized(),
+        SubmissionResult::InvalidDay
+        | SubmissionResult::InvalidLanguage
+        | SubmissionResult::InvalidVisibility => HttpResponse::BadRequest(),
+        SubmissionResult::Locked => HttpResponse::Forbidden(),
+        SubmissionResult::YearFrozen => HttpResponse::Conflict(),
+        SubmissionResult::InternalServerError => HttpResponse::InternalServerError(),
+    };
+
+    response.json(ApiError::new(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::Method, test, App};
+
+    use super::*;
+
+    /// Fill in the parameters of a documented path with example values.
+    fn example_path(path: &str) -> String {
+        path.replace("{year}", "2023")
+            .replace("{day}", "1")
+            .replace("{username}", "alice")
+    }
+
+    #[actix_web::test]
+    async fn test_documented_routes_exist() {
+        let app = test::init_service(App::new().service(api_v1())).await;
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let paths = spec["paths"].as_object().unwrap();
+
+        assert!(!paths.is_empty());
+
+        for (path, operations) in paths {
+            assert!(path.starts_with(API_V1), "{path} is not below {API_V1}");
+
+            for method in operations.as_object().unwrap().keys() {
+                let req = test::TestRequest::default()
+                    .method(Method::from_bytes(method.to_uppercase().as_bytes()).unwrap())
+                    .uri(&example_path(path))
+                    .to_request();
+                let status = test::call_service(&app, req).await.status();
+
+                // without a token (or body), every route rejects the request before touching the
+                // database, but only unknown routes answer with 404 or 405
+                assert!(
+                    status != 404 && status != 405,
+                    "{method} {path} is documented but not routed"
+                );
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_undocumented_route_missing() {
+        let app = test::init_service(App::new().service(api_v1())).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("{API_V1}/unknown"))
+            .to_request();
+
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+    }
+
+    #[actix_web::test]
+    async fn test_serve_openapi_json() {
+        let app = test::init_service(App::new().service(api_v1())).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("{API_V1}/openapi.json"))
+            .to_request();
+        let spec: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(spec, serde_json::to_value(ApiDoc::openapi()).unwrap());
+        assert!(spec["components"]["securitySchemes"]["token"].is_object());
+    }
+}
@@ -0,0 +1,135 @@
+use actix_multipart::Multipart;
+use actix_web::{
+    get,
+    http::header::{CacheControl, CacheDirective, ContentType, LOCATION},
+    post, web, HttpRequest, HttpResponse,
+};
+use futures_util::StreamExt;
+
+use crate::{
+    hooks::use_request_user,
+    model::User,
+    utils::{
+        avatar::{avatar_path, process_avatar, AvatarError, MAX_AVATAR_UPLOAD},
+        identicon::identicon,
+    },
+};
+
+/// Redirect back to the profile, passing on the error (if any).
+fn back_to_profile(error: Option<AvatarError>) -> HttpResponse {
+    let location = match error {
+        Some(error) => format!("/profile?avatar_error={}", error.key()),
+        None => "/profile".to_string(),
+    };
+
+    HttpResponse::SeeOther()
+        .insert_header((LOCATION, location))
+        .finish()
+}
+
+/// Read the `avatar` field of a multipart form, aborting once it exceeds the upload limit.
+async fn read_avatar_field(mut payload: Multipart) -> Result<Vec<u8>, AvatarError> {
+    while let Some(field) = payload.next().await {
+        let Ok(mut field) = field else {
+            return Err(AvatarError::InvalidImage);
+        };
+
+        if field.name() != "avatar" {
+            continue;
+        }
+
+        let mut bytes = vec![];
+        while let Some(chunk) = field.next().await {
+            let Ok(chunk) = chunk else {
+                return Err(AvatarError::InvalidImage);
+            };
+
+            if bytes.len() + chunk.len() > MAX_AVATAR_UPLOAD {
+                return Err(AvatarError::TooLarge);
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        return Ok(bytes);
+    }
+
+    Err(AvatarError::InvalidImage)
+}
+
+/// Upload a new avatar for the current user via a `multipart/form-data` form with an `avatar`
+/// field.
+#[tracing::instrument(level = "trace", skip(req, payload))]
+#[post("/avatar")]
+pub async fn upload_avatar(req: HttpRequest, payload: Multipart) -> HttpResponse {
+    let Some(user) = use_request_user(&req).await else {
+        return HttpResponse::Unauthorized().finish();
+    };
+
+    let avatar = match read_avatar_field(payload).await {
+        Ok(bytes) => web::block(move || process_avatar(&bytes))
+            .await
+            .unwrap_or(Err(AvatarError::Internal)),
+        Err(e) => Err(e),
+    };
+
+    let avatar = match avatar {
+        Ok(avatar) => avatar,
+        Err(e) => return back_to_profile(Some(e)),
+    };
+
+    let path = avatar_path(&user.id);
+    let result = match path.parent() {
+        Some(directory) => {
+            std::fs::create_dir_all(directory).and_then(|_| std::fs::write(&path, avatar))
+        }
+        None => std::fs::write(&path, avatar),
+    };
+
+    if let Err(e) = result {
+        tracing::error!("failed to store avatar of '{}': {e:?}", user.id);
+        return back_to_profile(Some(AvatarError::Internal));
+    }
+
+    back_to_profile(None)
+}
+
+/// Remove the avatar of the current user, falling back to the identicon.
+#[tracing::instrument(level = "trace", skip(req))]
+#[post("/avatar/delete")]
+pub async fn delete_avatar(req: HttpRequest) -> HttpResponse {
+    let Some(user) = use_request_user(&req).await else {
+        return HttpResponse::Unauthorized().finish();
+    };
+
+    let path = avatar_path(&user.id);
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(&path) {
+            tracing::error!("failed to remove avatar of '{}': {e:?}", user.id);
+            return back_to_profile(Some(AvatarError::Internal));
+        }
+    }
+
+    back_to_profile(None)
+}
+
+/// Serve the avatar of a user. Users without an uploaded avatar (and unknown users) get a
+/// generated identicon.
+#[tracing::instrument(level = "trace")]
+#[get("/avatar/{username}")]
+pub async fn get_avatar(path: web::Path<String>) -> HttpResponse {
+    let username = path.into_inner();
+
+    if let Some(user) = User::get_by_username(&username).await {
+        if let Ok(avatar) = std::fs::read(avatar_path(&user.id)) {
+            return HttpResponse::Ok()
+                .content_type(ContentType::png())
+                .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+                .body(avatar);
+        }
+    }
+
+    HttpResponse::Ok()
+        .content_type("image/svg+xml")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        .body(identicon(&username))
+}
@@ -0,0 +1,127 @@
+use actix_web::{
+    get,
+    http::header::{ContentDisposition, DispositionParam, DispositionType},
+    post, web, HttpRequest, HttpResponse,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    functions::{is_frozen, AuditEvent},
+    hooks::use_request_user,
+    repository::{LeaderboardSnapshotRepository, MemberLinkRepository},
+    services::audit::{self, Subject},
+    utils::{export::LeaderboardExport, leaderboard::Leaderboard},
+};
+
+/// Store a leaderboard JSON file sent as request body, e.g., via
+/// `curl --data-binary @leaderboard.json`.
+#[tracing::instrument(level = "trace", skip(req, body))]
+#[post("/leaderboard/{year}")]
+pub async fn upload_leaderboard_file(
+    req: HttpRequest,
+    path: web::Path<i32>,
+    body: String,
+) -> HttpResponse {
+    let year = path.into_inner();
+
+    let Some(admin) = use_request_user(&req).await.filter(|user| user.is_admin()) else {
+        return HttpResponse::Forbidden().finish();
+    };
+
+    let Ok(leaderboard) = Leaderboard::from_json(&body) else {
+        return HttpResponse::BadRequest().body("invalid leaderboard JSON");
+    };
+
+    if leaderboard.year().is_some_and(|event| event != year) {
+        return HttpResponse::BadRequest().body("leaderboard belongs to another year");
+    }
+
+    if is_frozen(year).await {
+        return HttpResponse::Conflict().body("year is archived and read-only");
+    }
+
+    if let Err(e) = LeaderboardSnapshotRepository::create(year, leaderboard).await {
+        tracing::error!("failed to store leaderboard snapshot: {e:?}");
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    audit::record(
+        Some(&req),
+        Subject::from(&admin),
+        None,
+        AuditEvent::LeaderboardUploaded(year),
+    )
+    .await;
+
+    HttpResponse::Created().finish()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// Export the leaderboard as it was at this point in time (RFC 3339) instead of the latest one.
+    at: Option<String>,
+}
+
+/// Export the leaderboard of a year including the timestamps of all stars as `json` or `csv`.
+#[tracing::instrument(level = "trace")]
+#[get("/export/leaderboard/{year}.{format}")]
+pub async fn export_leaderboard(
+    path: web::Path<(i32, String)>,
+    query: web::Query<ExportQuery>,
+) -> HttpResponse {
+    let (year, format) = path.into_inner();
+
+    let snapshot = match &query.at {
+        Some(at) => {
+            let Ok(at) = DateTime::parse_from_rfc3339(at) else {
+                return HttpResponse::BadRequest().body("invalid point in time");
+            };
+            LeaderboardSnapshotRepository::get_latest_before(
+                year,
+                &at.with_timezone(&Utc).to_rfc3339(),
+            )
+            .await
+        }
+        None => LeaderboardSnapshotRepository::get_latest(year).await,
+    };
+
+    let snapshot = match snapshot {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => return HttpResponse::NotFound().finish(),
+        Err(e) => {
+            tracing::error!("failed to get leaderboard snapshot: {e:?}");
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let links = match MemberLinkRepository::get_confirmed().await {
+        Ok(links) => links,
+        Err(e) => {
+            tracing::error!("failed to get member links: {e:?}");
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let export = LeaderboardExport::new(year, snapshot.fetched_at, &snapshot.leaderboard, |id| {
+        links
+            .iter()
+            .find(|link| link.member_id == id)
+            .map(|link| link.username.clone())
+    });
+
+    let file_name = format!("leaderboard-{year}.{format}");
+    let attachment = ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![DispositionParam::Filename(file_name)],
+    };
+
+    match format.as_str() {
+        "json" => HttpResponse::Ok().insert_header(attachment).json(export),
+        "csv" => HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header(attachment)
+            .body(export.to_csv()),
+        _ => HttpResponse::NotFound().finish(),
+    }
+}
@@ -0,0 +1,17 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+if #[cfg(feature = "ssr")] {
+    mod account;
+    mod api;
+    mod avatar;
+    mod leaderboard;
+    mod solutions;
+
+    pub use self::account::*;
+    pub use self::api::*;
+    pub use self::avatar::*;
+    pub use self::leaderboard::*;
+    pub use self::solutions::*;
+}
+}
@@ -0,0 +1,110 @@
+use actix_web::{
+    get,
+    http::header::{ContentDisposition, ContentType, DispositionParam, DispositionType},
+    web, HttpRequest, HttpResponse,
+};
+use flate2::{write::GzEncoder, Compression};
+
+use crate::{functions::filter_visible, hooks::use_request_user, model::Solution};
+
+/// Serve the code of a single solution as plain text.
+#[tracing::instrument(level = "trace", skip(req))]
+#[get("/raw/{year}/{day}/{user}/{part}")]
+pub async fn raw_solution(
+    req: HttpRequest,
+    path: web::Path<(i32, u8, String, u8)>,
+) -> HttpResponse {
+    let (year, day, author, part) = path.into_inner();
+    let viewer = use_request_user(&req).await;
+
+    let solutions = Solution::get_by_author(&author, year, day).await;
+
+    let Some(solution) = filter_visible(solutions, viewer.as_ref())
+        .await
+        .into_iter()
+        .find(|solution| solution.part == part)
+    else {
+        return HttpResponse::NotFound().finish();
+    };
+
+    HttpResponse::Ok()
+        .content_type(ContentType::plaintext())
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Inline,
+            parameters: vec![DispositionParam::Filename(solution.file_name())],
+        })
+        .body(solution.code)
+}
+
+/// Bundle all solutions of a user for a year into a `.tar.gz` archive.
+#[tracing::instrument(level = "trace", skip(req))]
+#[get("/download/{year}/{user}")]
+pub async fn download_solutions(req: HttpRequest, path: web::Path<(i32, String)>) -> HttpResponse {
+    let (year, author) = path.into_inner();
+    let viewer = use_request_user(&req).await;
+
+    let solutions = filter_visible(
+        Solution::get_by_author_and_year(&author, year).await,
+        viewer.as_ref(),
+    )
+    .await;
+
+    if solutions.is_empty() {
+        return HttpResponse::NotFound().finish();
+    }
+
+    let archive = match create_archive(&format!("{author}-{year}"), &solutions) {
+        Ok(archive) => archive,
+        Err(e) => {
+            tracing::error!("failed to create archive for '{author}' ({year}): {e:?}");
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    HttpResponse::Ok()
+        .content_type("application/gzip")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "{author}-{year}.tar.gz"
+            ))],
+        })
+        .body(archive)
+}
+
+/// Create a gzipped tarball containing the given solutions within the directory `root`.
+fn create_archive(root: &str, solutions: &[Solution]) -> std::io::Result<Vec<u8>> {
+    let mut archive = tar::Builder::new(GzEncoder::new(vec![], Compression::default()));
+
+    for solution in solutions {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(solution.code.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        archive.append_data(
+            &mut header,
+            format!("{root}/{}", solution.file_name()),
+            solution.code.as_bytes(),
+        )?;
+
+        if let Some(writeup) = &solution.writeup {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(writeup.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+
+            archive.append_data(
+                &mut header,
+                format!(
+                    "{root}/day{day:02}_part{part}.md",
+                    day = solution.day,
+                    part = solution.part
+                ),
+                writeup.as_bytes(),
+            )?;
+        }
+    }
+
+    archive.into_inner()?.finish()
+}
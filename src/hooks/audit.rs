@@ -0,0 +1,21 @@
+use leptos::*;
+
+use crate::{
+    functions::AuditEvent,
+    model::User,
+    services::audit::{self, Subject},
+};
+
+/// Append an event to the audit log, taking IP address and user agent from the current request.
+///
+/// `actor` is the admin who caused the event, if it was not the user themselves.
+#[tracing::instrument(level = "trace", skip(subject, actor))]
+pub async fn record_audit_event(
+    subject: impl Into<Subject>,
+    actor: Option<&User>,
+    event: AuditEvent,
+) {
+    let req = use_context::<actix_web::HttpRequest>();
+
+    audit::record(req.as_ref(), subject.into(), actor, event).await;
+}
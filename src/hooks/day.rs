@@ -0,0 +1,37 @@
+use chrono::Utc;
+use leptos::*;
+use leptos_router::use_query_map;
+
+use crate::utils::calendar::{current_event_year, unlocked_days};
+
+/// Get the event year and day selected via the `year` and `day` query parameters.
+///
+/// Only unlocked days can be selected. Defaults to the most recently unlocked day of the current
+/// event.
+#[tracing::instrument(level = "trace")]
+pub fn use_selected_day() -> (Memo<i32>, Memo<u8>) {
+    let query = use_query_map();
+
+    let year = create_memo(move |_| {
+        query.with(|query| {
+            query
+                .get("year")
+                .and_then(|year| year.parse().ok())
+                .unwrap_or_else(current_event_year)
+        })
+    });
+
+    let day = create_memo(move |_| {
+        let unlocked = unlocked_days(year(), Utc::now()).max(1);
+
+        query.with(|query| {
+            query
+                .get("day")
+                .and_then(|day| day.parse().ok())
+                .filter(|day| (1..=unlocked).contains(day))
+                .unwrap_or(unlocked)
+        })
+    });
+
+    (year, day)
+}
@@ -1,17 +1,25 @@
 mod auth;
+mod day;
+mod preferences_context;
 
 use cfg_if::cfg_if;
 
 pub use self::auth::*;
+pub use self::day::*;
+pub use self::preferences_context::*;
 
 cfg_if! {
 if #[cfg(feature = "ssr")] {
+    mod audit;
     mod database;
     mod identity;
+    mod preferences;
     mod user;
 
+    pub use self::audit::*;
     pub use self::database::*;
     pub use self::identity::*;
+    pub use self::preferences::*;
     pub use self::user::*;
 }
 }
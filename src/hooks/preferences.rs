@@ -0,0 +1,22 @@
+use leptos::*;
+
+use crate::functions::Preferences;
+
+use super::use_user;
+
+/// Name of the cookie storing the preferences of anonymous visitors.
+pub const PREFERENCES_COOKIE: &str = "preferences";
+
+/// Get the preferences of the current user. Anonymous visitors get the preferences stored in
+/// their cookie, if any.
+#[tracing::instrument(level = "trace")]
+pub async fn use_preferences() -> Preferences {
+    if let Some(user) = use_user().await {
+        return user.preferences;
+    }
+
+    use_context::<actix_web::HttpRequest>()
+        .and_then(|req| req.cookie(PREFERENCES_COOKIE))
+        .map(|cookie| Preferences::from_cookie(cookie.value()))
+        .unwrap_or_default()
+}
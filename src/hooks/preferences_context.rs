@@ -0,0 +1,11 @@
+use leptos::*;
+
+use crate::contexts::PreferencesContext;
+
+/// Get the preferences context.
+///
+/// This function will panic if there is no PreferencesContext provided in an upper component.
+#[tracing::instrument(level = "trace")]
+pub fn use_preferences_context() -> PreferencesContext {
+    use_context::<PreferencesContext>().expect("no valid PreferencesContext given!")
+}
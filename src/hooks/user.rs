@@ -1,6 +1,13 @@
+use actix_identity::IdentityExt;
+use actix_web::{http::header::AUTHORIZATION, HttpRequest};
 use leptos::*;
 
-use crate::{model::User, repository::LoggedInRepository};
+use crate::{
+    functions::{Role, TokenScope},
+    model::User,
+    repository::{ApiTokenRepository, LoggedInRepository},
+    utils::token::hash_token,
+};
 
 use super::use_identity;
 
@@ -18,3 +25,67 @@ pub async fn use_user() -> Option<User> {
 
     LoggedInRepository::find_user_via_session(&session_id).await
 }
+
+/// Get the current user, if they have the given role (or a higher one).
+///
+/// Server functions restricted to a role can use this in place of [`use_user`], e.g.,
+/// `let Some(admin) = use_user_with_role(Role::Admin).await else { ... };`.
+#[tracing::instrument(level = "trace")]
+pub async fn use_user_with_role(role: Role) -> Option<User> {
+    use_user().await.filter(|user| user.has_role(role))
+}
+
+/// Get the user attached to a request.
+///
+/// In contrast to [`use_user`], this does not need a leptos context and can therefore be used
+/// within plain actix handlers.
+#[tracing::instrument(level = "trace", skip(req))]
+pub async fn use_request_user(req: &HttpRequest) -> Option<User> {
+    let Ok(identity) = IdentityExt::get_identity(req) else {
+        tracing::debug!("no identity attached to request");
+        return None;
+    };
+
+    let Ok(session_id) = identity.id() else {
+        tracing::error!("failed to get session id!");
+        return None;
+    };
+
+    LoggedInRepository::find_user_via_session(&session_id).await
+}
+
+/// Get the user and the scope of the API token sent as `Authorization: Bearer <token>` header.
+///
+/// Disabled accounts cannot use their tokens, just like their sessions.
+#[tracing::instrument(level = "trace", skip(req))]
+pub async fn use_api_user(req: &HttpRequest) -> Option<(User, TokenScope)> {
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))?;
+
+    let token = match ApiTokenRepository::get_by_hash(&hash_token(token.trim())).await {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            tracing::debug!("unknown API token");
+            return None;
+        }
+        Err(e) => {
+            tracing::error!("failed to look up API token: {e:?}");
+            return None;
+        }
+    };
+
+    let user = User::get_by_id(&token.user)
+        .await
+        .filter(|user| !user.disabled)?;
+
+    if let Some(id) = token.id() {
+        if let Err(e) = ApiTokenRepository::touch(&id).await {
+            tracing::error!("failed to update last use of API token: {e:?}");
+        }
+    }
+
+    Some((user, token.scope))
+}
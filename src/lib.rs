@@ -2,6 +2,7 @@ pub mod app;
 pub mod components;
 pub mod contexts;
 pub mod functions;
+pub mod handlers;
 pub mod hooks;
 pub mod model;
 pub mod repository;
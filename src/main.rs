@@ -12,7 +12,11 @@ async fn main() -> std::io::Result<()> {
         cookie::{time, Key},
         web, App, HttpServer,
     };
-    use aoc_website::{app::*, services::database};
+    use aoc_website::{
+        app::*,
+        handlers,
+        services::{account, database, leaderboard, roles},
+    };
     use leptos::*;
     use leptos_actix::{generate_route_list, LeptosRoutes};
     use tracing_subscriber::{filter, prelude::*};
@@ -34,6 +38,13 @@ async fn main() -> std::io::Result<()> {
 
     if let Err(e) = database::init_db().await {
         tracing::error!("failed to connect to DB: {e:?}");
+    } else {
+        // hand out the admin role to the users configured in the environment
+        roles::bootstrap_admins().await;
+        // keep the leaderboard up to date in the background
+        actix_web::rt::spawn(leaderboard::poll());
+        // delete accounts once their grace period is over
+        actix_web::rt::spawn(account::poll());
     };
 
     HttpServer::new(move || {
@@ -41,6 +52,8 @@ async fn main() -> std::io::Result<()> {
         let site_root = &leptos_options.site_root;
 
         App::new()
+            // the REST API needs to take precedence over the server functions below `/api`
+            .service(handlers::api_v1())
             .route("/api/{tail:.*}", leptos_actix::handle_server_fns())
             // serve JS/WASM/CSS from `pkg`
             .service(Files::new("/pkg", format!("{site_root}/pkg")))
@@ -48,6 +61,17 @@ async fn main() -> std::io::Result<()> {
             .service(Files::new("/assets", site_root))
             // serve the favicon from /favicon.ico
             .service(favicon)
+            // serve solutions as plain text and archives
+            .service(handlers::raw_solution)
+            .service(handlers::download_solutions)
+            .service(handlers::upload_leaderboard_file)
+            .service(handlers::export_leaderboard)
+            // avatars and their generated fallbacks
+            .service(handlers::upload_avatar)
+            .service(handlers::delete_avatar)
+            .service(handlers::get_avatar)
+            // data export of the current user
+            .service(handlers::export_account)
             .leptos_routes(
                 leptos_options.to_owned(),
                 routes.to_owned(),
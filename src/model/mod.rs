@@ -4,8 +4,10 @@ cfg_if! {
 if #[cfg(feature = "ssr")] {
     mod user;
     mod session;
+    mod solution;
 
     pub use self::user::*;
     pub use self::session::*;
+    pub use self::solution::*;
 }
 }
@@ -0,0 +1,200 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    functions::{file_extension, Visibility},
+    repository::SolutionRepository,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Solution {
+    pub id: String,
+    pub author: String,
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+    pub code: String,
+    pub writeup: Option<String>,
+    pub visibility: Option<Visibility>,
+    pub created_at: String,
+    /// Reason given by the moderator who hid this solution, if hidden.
+    pub hidden_reason: Option<String>,
+}
+
+impl From<SolutionRepository> for Solution {
+    fn from(solution: SolutionRepository) -> Self {
+        let id = solution
+            .id()
+            .expect("solution from database should have id");
+        let SolutionRepository {
+            author,
+            year,
+            day,
+            part,
+            language,
+            code,
+            writeup,
+            visibility,
+            created_at,
+            hidden_reason,
+            ..
+        } = solution;
+
+        Self {
+            id,
+            author,
+            year,
+            day,
+            part,
+            language,
+            code,
+            writeup,
+            visibility,
+            created_at,
+            hidden_reason,
+        }
+    }
+}
+
+impl Solution {
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_id(id: &str) -> Option<Solution> {
+        tracing::debug!("getting solution by id");
+        SolutionRepository::get_by_id(id)
+            .await
+            .ok()
+            .flatten()
+            .map(Solution::from)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_author(author: &str, year: i32, day: u8) -> Vec<Solution> {
+        tracing::debug!("getting solutions by author");
+        match SolutionRepository::get_by_author(author, year, day).await {
+            Ok(solutions) => solutions.into_iter().map(Solution::from).collect(),
+            Err(e) => {
+                tracing::error!("failed to get solutions of '{author}': {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_author_and_year(author: &str, year: i32) -> Vec<Solution> {
+        tracing::debug!("getting solutions by author and year");
+        match SolutionRepository::get_by_author_and_year(author, year).await {
+            Ok(solutions) => solutions.into_iter().map(Solution::from).collect(),
+            Err(e) => {
+                tracing::error!("failed to get solutions of '{author}' for {year}: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_all_by_author(author: &str) -> Vec<Solution> {
+        tracing::debug!("getting all solutions by author");
+        match SolutionRepository::get_all_by_author(author).await {
+            Ok(solutions) => solutions.into_iter().map(Solution::from).collect(),
+            Err(e) => {
+                tracing::error!("failed to get solutions of '{author}': {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    /// Name of the file this solution would be stored in, e.g., `day01_part2.rs`.
+    pub fn file_name(&self) -> String {
+        format!(
+            "day{day:02}_part{part}.{extension}",
+            day = self.day,
+            part = self.part,
+            extension = file_extension(&self.language)
+        )
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_day(year: i32, day: u8) -> Vec<Solution> {
+        tracing::debug!("getting solutions by day");
+        match SolutionRepository::get_by_day(year, day).await {
+            Ok(solutions) => solutions.into_iter().map(Solution::from).collect(),
+            Err(e) => {
+                tracing::error!("failed to get solutions for {year}/{day}: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn set_visibility(
+        &self,
+        visibility: Option<Visibility>,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("setting visibility of solution");
+        SolutionRepository::set_visibility(&self.id, visibility).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_hidden() -> Vec<Solution> {
+        tracing::debug!("getting hidden solutions");
+        match SolutionRepository::get_hidden().await {
+            Ok(solutions) => solutions.into_iter().map(Solution::from).collect(),
+            Err(e) => {
+                tracing::error!("failed to get hidden solutions: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    /// Short description of this solution, e.g., `alice 2023/1/2`.
+    pub fn subject(&self) -> String {
+        format!(
+            "{author} {year}/{day}/{part}",
+            author = self.author,
+            year = self.year,
+            day = self.day,
+            part = self.part
+        )
+    }
+
+    /// Hide this solution from everybody but its author, or show it again.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn set_hidden(&self, reason: Option<String>) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("setting hidden state of solution");
+        SolutionRepository::set_hidden(&self.id, reason).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn delete(&self) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("deleting solution");
+        SolutionRepository::delete(&self.id).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn save(self) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("saving solution");
+        let Solution {
+            author,
+            year,
+            day,
+            part,
+            language,
+            code,
+            writeup,
+            visibility,
+            ..
+        } = self;
+        SolutionRepository::save(author, year, day, part, language, code, writeup, visibility)
+            .await?;
+
+        Ok(())
+    }
+}
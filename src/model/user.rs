@@ -4,7 +4,11 @@ use actix_identity::Identity;
 use actix_web::{HttpMessage, HttpRequest};
 use serde::{Deserialize, Serialize};
 
-use crate::{repository::UserRepository, utils::password::verify_password};
+use crate::{
+    functions::{Preferences, Role, Visibility},
+    repository::UserRepository,
+    utils::password::verify_password,
+};
 
 use super::Session;
 
@@ -15,6 +19,16 @@ pub struct User {
     pub email: String,
     pub email_verified: bool,
     pub password: String,
+    pub default_visibility: Visibility,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub favourite_languages: Vec<String>,
+    pub preferences: Preferences,
+    /// Point in time (RFC 3339) the user asked for the account to be deleted.
+    pub deletion_requested_at: Option<String>,
+    pub role: Role,
+    /// Disabled accounts can neither log in nor use existing sessions.
+    pub disabled: bool,
     pub sessions: Vec<Session>,
 }
 
@@ -46,6 +60,14 @@ impl User {
             password,
             email,
             email_verified,
+            default_visibility,
+            display_name,
+            bio,
+            favourite_languages,
+            preferences,
+            deletion_requested_at,
+            role,
+            disabled,
             ..
         } = user;
 
@@ -55,6 +77,14 @@ impl User {
             password,
             email,
             email_verified,
+            default_visibility,
+            display_name,
+            bio,
+            favourite_languages,
+            preferences,
+            deletion_requested_at,
+            role,
+            disabled,
             sessions: vec![],
         })
     }
@@ -76,6 +106,14 @@ impl User {
             password,
             email,
             email_verified,
+            default_visibility,
+            display_name,
+            bio,
+            favourite_languages,
+            preferences,
+            deletion_requested_at,
+            role,
+            disabled,
             ..
         } = user;
 
@@ -85,6 +123,14 @@ impl User {
             password,
             email,
             email_verified,
+            default_visibility,
+            display_name,
+            bio,
+            favourite_languages,
+            preferences,
+            deletion_requested_at,
+            role,
+            disabled,
             sessions: vec![],
         })
     }
@@ -100,6 +146,82 @@ impl User {
         }
     }
 
+    /// Check, whether this user has the given role or a higher one.
+    pub fn has_role(&self, role: Role) -> bool {
+        self.role >= role
+    }
+
+    /// Check, whether this user is allowed to manage the site.
+    pub fn is_admin(&self) -> bool {
+        self.has_role(Role::Admin)
+    }
+
+    /// Store a new password, given as hash.
+    #[tracing::instrument(level = "trace", skip(password))]
+    pub async fn set_password(&self, password: String) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("setting password");
+        UserRepository::set_password(&self.id, password).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_role(&self, role: Role) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("setting role");
+        UserRepository::set_role(&self.id, role).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_disabled(&self, disabled: bool) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("setting disabled");
+        UserRepository::set_disabled(&self.id, disabled).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_default_visibility(
+        &self,
+        visibility: Visibility,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("setting default visibility");
+        UserRepository::set_default_visibility(&self.id, visibility).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_preferences(&self, preferences: Preferences) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("setting preferences");
+        UserRepository::set_preferences(&self.id, preferences).await?;
+
+        Ok(())
+    }
+
+    /// Mark the account for deletion after the grace period, or unmark it again.
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_deletion_requested(&self, requested: bool) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("setting deletion request");
+        UserRepository::set_deletion_requested(&self.id, requested).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn update_profile(
+        &self,
+        display_name: Option<String>,
+        bio: Option<String>,
+        favourite_languages: Vec<String>,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("updating profile");
+        UserRepository::update_profile(&self.id, display_name, bio, favourite_languages).await?;
+
+        Ok(())
+    }
+
     #[tracing::instrument(level = "trace", skip(password))]
     pub async fn login(&mut self, password: &str, req: &HttpRequest) -> Result<(), LoginError> {
         tracing::debug!("logging user in");
@@ -107,6 +229,10 @@ impl User {
             return Err(LoginError::PasswordMismatch);
         };
 
+        if self.disabled {
+            return Err(LoginError::Disabled);
+        }
+
         let Some(session) = Session::new(self).await else {
             tracing::error!("failed to login user ({})", self.id);
             return Err(LoginError::Internal);
@@ -129,5 +255,6 @@ impl User {
 
 pub enum LoginError {
     PasswordMismatch,
+    Disabled,
     Internal,
 }
@@ -0,0 +1,104 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{thing, Thing};
+
+use crate::{functions::TokenScope, hooks::use_database};
+
+/// Personal API token of a user. Only the hash of the token is stored.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ApiTokenRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    /// Id of the user the token belongs to.
+    pub user: String,
+    /// Name given by the user to tell their tokens apart.
+    pub name: String,
+    pub hash: String,
+    pub scope: TokenScope,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+impl ApiTokenRepository {
+    pub const TABLE: &'static str = "api_token";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_user(user: &str) -> Result<Vec<ApiTokenRepository>, surrealdb::Error> {
+        tracing::debug!("getting API tokens of '{user}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE user = $user ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace", skip(hash))]
+    pub async fn get_by_hash(hash: &str) -> Result<Option<ApiTokenRepository>, surrealdb::Error> {
+        tracing::debug!("getting API token by hash from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE hash = $hash;")
+            .bind(("table", Self::TABLE))
+            .bind(("hash", hash.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace", skip(hash))]
+    pub async fn create(
+        user: String,
+        name: String,
+        hash: String,
+        scope: TokenScope,
+    ) -> Result<Option<ApiTokenRepository>, surrealdb::Error> {
+        tracing::debug!("creating API token '{name}' in database");
+        let db = use_database().await;
+
+        db.create(Self::TABLE)
+            .content(ApiTokenRepository {
+                user,
+                name,
+                hash,
+                scope,
+                created_at: Utc::now().to_rfc3339(),
+                ..Default::default()
+            })
+            .await
+    }
+
+    /// Remember that the token was just used.
+    #[tracing::instrument(level = "trace")]
+    pub async fn touch(id: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("updating last use of API token '{id}' in database");
+        let db = use_database().await;
+
+        db.query(format!("UPDATE {id} SET last_used_at = $now"))
+            .bind(("now", Utc::now().to_rfc3339()))
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn delete(id: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("deleting API token '{id}' from database");
+        let Ok(Thing { tb, id }) = thing(id) else {
+            return Ok(());
+        };
+
+        let db = use_database().await;
+
+        let _: Option<ApiTokenRepository> = db.delete((tb, id)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::{functions::AuditEvent, hooks::use_database};
+
+/// Entry of the security audit log. The log is append-only: there is no way to delete entries,
+/// not even together with the account they concern. Deleting an account only anonymises its
+/// entries by replacing the account with [`AuditLogRepository::DELETED_USER`] and dropping their
+/// IP addresses and user agents (see [`UserRepository::delete`]).
+///
+/// [`UserRepository::delete`]: super::UserRepository::delete
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AuditLogRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    /// Id of the account the event concerns, if it exists.
+    pub user: Option<String>,
+    /// Username of the account at the time of the event, or the one given in a failed login.
+    pub username: String,
+    /// Username of the admin who caused the event, if it was not the user themselves.
+    pub actor: Option<String>,
+    /// Identifier of the kind of event, used for filtering.
+    pub kind: String,
+    pub event: AuditEvent,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: String,
+}
+
+impl AuditLogRepository {
+    pub const TABLE: &'static str = "audit_log";
+
+    /// Username recorded in place of a deleted account.
+    pub const DELETED_USER: &'static str = "[deleted]";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn create(
+        user: Option<String>,
+        username: String,
+        actor: Option<String>,
+        event: AuditEvent,
+        ip: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("appending '{}' to the audit log", event.kind());
+        let db = use_database().await;
+
+        let _: Option<AuditLogRepository> = db
+            .create(Self::TABLE)
+            .content(AuditLogRepository {
+                id: None,
+                user,
+                username,
+                actor,
+                kind: event.kind().to_string(),
+                event,
+                ip,
+                user_agent,
+                created_at: Utc::now().to_rfc3339(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the newest entries concerning the given account, newest first.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_user(
+        user: &str,
+        limit: usize,
+    ) -> Result<Vec<AuditLogRepository>, surrealdb::Error> {
+        tracing::debug!("getting audit log of '{user}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE user = $user ORDER BY created_at DESC LIMIT $limit;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.to_string()))
+            .bind(("limit", limit))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Get all entries concerning the given account, oldest first.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_all_by_user(user: &str) -> Result<Vec<AuditLogRepository>, surrealdb::Error> {
+        tracing::debug!("getting complete audit log of '{user}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE user = $user ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Get the newest entries, optionally only the ones of a username (as subject or actor) or of
+    /// a kind of event, newest first.
+    #[tracing::instrument(level = "trace")]
+    pub async fn search(
+        username: Option<String>,
+        kind: Option<String>,
+        limit: usize,
+    ) -> Result<Vec<AuditLogRepository>, surrealdb::Error> {
+        tracing::debug!("searching the audit log in the database");
+        let db = use_database().await;
+
+        let mut statement = "SELECT * FROM type::table($table) WHERE true".to_string();
+
+        if username.is_some() {
+            statement.push_str(" AND (username = $username OR actor = $username)");
+        }
+        if kind.is_some() {
+            statement.push_str(" AND kind = $kind");
+        }
+        statement.push_str(" ORDER BY created_at DESC LIMIT $limit;");
+
+        let mut result = db
+            .query(statement)
+            .bind(("table", Self::TABLE))
+            .bind(("username", username))
+            .bind(("kind", kind))
+            .bind(("limit", limit))
+            .await?;
+
+        result.take(0)
+    }
+}
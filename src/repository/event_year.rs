@@ -0,0 +1,50 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::hooks::use_database;
+
+/// State of the event of a year. A frozen year is read-only: no more solutions, votes, reactions
+/// or leaderboard updates.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct EventYearRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub year: i32,
+    pub frozen: bool,
+    pub frozen_at: Option<String>,
+}
+
+impl EventYearRepository {
+    pub const TABLE: &'static str = "event_year";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get(year: i32) -> Result<Option<EventYearRepository>, surrealdb::Error> {
+        tracing::debug!("getting state of event {year} from the database");
+        let db = use_database().await;
+
+        db.select((Self::TABLE, year.to_string())).await
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_frozen(year: i32, frozen: bool) -> Result<(), surrealdb::Error> {
+        tracing::debug!("setting frozen state of event {year} to {frozen} in database");
+        let db = use_database().await;
+
+        let _: Option<EventYearRepository> = db
+            .update((Self::TABLE, year.to_string()))
+            .content(EventYearRepository {
+                year,
+                frozen,
+                frozen_at: frozen.then(|| Utc::now().to_rfc3339()),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::{hooks::use_database, repository::YearCount, utils::leaderboard::Leaderboard};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct LeaderboardSnapshotRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub year: i32,
+    pub fetched_at: String,
+    pub leaderboard: Leaderboard,
+}
+
+impl LeaderboardSnapshotRepository {
+    pub const TABLE: &'static str = "leaderboard_snapshot";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace", skip(leaderboard))]
+    pub async fn create(
+        year: i32,
+        leaderboard: Leaderboard,
+    ) -> Result<Option<LeaderboardSnapshotRepository>, surrealdb::Error> {
+        tracing::debug!("inserting leaderboard snapshot for {year} into database");
+        let db = use_database().await;
+        let result: Option<LeaderboardSnapshotRepository> = db
+            .create(Self::TABLE)
+            .content(LeaderboardSnapshotRepository {
+                year,
+                fetched_at: Utc::now().to_rfc3339(),
+                leaderboard,
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(result)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_latest(
+        year: i32,
+    ) -> Result<Option<LeaderboardSnapshotRepository>, surrealdb::Error> {
+        tracing::debug!("getting latest leaderboard snapshot for {year} from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE year = $year ORDER BY fetched_at DESC LIMIT 1;")
+            .bind(("table", Self::TABLE))
+            .bind(("year", year))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Get the latest snapshot taken at or before the given point in time (RFC 3339).
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_latest_before(
+        year: i32,
+        at: &str,
+    ) -> Result<Option<LeaderboardSnapshotRepository>, surrealdb::Error> {
+        tracing::debug!("getting leaderboard snapshot for {year} at {at} from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE year = $year AND fetched_at <= $at ORDER BY fetched_at DESC LIMIT 1;")
+            .bind(("table", Self::TABLE))
+            .bind(("year", year))
+            .bind(("at", at.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Get the most recent snapshot, regardless of its year.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_newest() -> Result<Option<LeaderboardSnapshotRepository>, surrealdb::Error> {
+        tracing::debug!("getting newest leaderboard snapshot from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) ORDER BY fetched_at DESC LIMIT 1;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_all(
+        year: i32,
+    ) -> Result<Vec<LeaderboardSnapshotRepository>, surrealdb::Error> {
+        tracing::debug!("getting all leaderboard snapshots for {year} from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE year = $year ORDER BY fetched_at;")
+            .bind(("table", Self::TABLE))
+            .bind(("year", year))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Count the snapshots of each year.
+    #[tracing::instrument(level = "trace")]
+    pub async fn count_by_year() -> Result<Vec<YearCount>, surrealdb::Error> {
+        tracing::debug!("counting leaderboard snapshots per year in the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT year, count() AS count FROM type::table($table) GROUP BY year;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::hooks::use_database;
+
+/// Leaderboard settings managed in the admin area. Unset values fall back to the environment.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct LeaderboardConfigRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    /// URL of the private leaderboard JSON, where `{year}` is replaced by the year.
+    pub url: Option<String>,
+    /// Interval between two automatic refreshes in minutes.
+    pub poll_interval: Option<i64>,
+}
+
+impl LeaderboardConfigRepository {
+    pub const TABLE: &'static str = "leaderboard_config";
+    /// The configuration is stored in a single record with this id.
+    const RECORD: &'static str = "current";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get() -> Result<Option<LeaderboardConfigRepository>, surrealdb::Error> {
+        tracing::debug!("getting leaderboard configuration from the database");
+        let db = use_database().await;
+
+        db.select((Self::TABLE, Self::RECORD)).await
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn save(
+        url: Option<String>,
+        poll_interval: Option<i64>,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("saving leaderboard configuration in database");
+        let db = use_database().await;
+
+        let _: Option<LeaderboardConfigRepository> = db
+            .update((Self::TABLE, Self::RECORD))
+            .content(LeaderboardConfigRepository {
+                url,
+                poll_interval,
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::hooks::use_database;
+
+/// Bookkeeping of the requests made to Advent of Code for the leaderboard of a year.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct LeaderboardFetchRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub year: i32,
+    /// Point in time (RFC 3339) of the last request, regardless of its outcome.
+    pub attempted_at: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl LeaderboardFetchRepository {
+    pub const TABLE: &'static str = "leaderboard_fetch";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get(year: i32) -> Result<Option<LeaderboardFetchRepository>, surrealdb::Error> {
+        tracing::debug!("getting leaderboard fetch state for {year} from the database");
+        let db = use_database().await;
+
+        db.select((Self::TABLE, year.to_string())).await
+    }
+
+    /// Get the state of the year whose leaderboard was requested last.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_last() -> Result<Option<LeaderboardFetchRepository>, surrealdb::Error> {
+        tracing::debug!("getting the last leaderboard fetch state from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) ORDER BY attempted_at DESC LIMIT 1;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn save(
+        year: i32,
+        attempted_at: String,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("saving leaderboard fetch state for {year} in database");
+        let db = use_database().await;
+
+        let _: Option<LeaderboardFetchRepository> = db
+            .update((Self::TABLE, year.to_string()))
+            .content(LeaderboardFetchRepository {
+                year,
+                attempted_at,
+                etag,
+                last_modified,
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(())
+    }
+}
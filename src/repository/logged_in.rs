@@ -52,7 +52,13 @@ impl LoggedInRepository {
             return None;
         };
 
-        User::get_by_id(&user.id.to_string()).await
+        let user = User::get_by_id(&user.to_string()).await?;
+        if user.disabled {
+            tracing::debug!("session belongs to a disabled user");
+            return None;
+        }
+
+        Some(user)
     }
 
     #[tracing::instrument(level = "trace")]
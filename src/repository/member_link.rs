@@ -0,0 +1,143 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{thing, Thing};
+
+use crate::hooks::use_database;
+
+/// Link between a user of this site and an Advent of Code member.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct MemberLinkRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub user: String,
+    pub username: String,
+    pub member_id: u64,
+    /// Code the member has to put into their Advent of Code display name as proof.
+    pub code: String,
+    pub confirmed: bool,
+    pub created_at: String,
+}
+
+impl MemberLinkRepository {
+    pub const TABLE: &'static str = "member_link";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_id(id: &str) -> Result<Option<MemberLinkRepository>, surrealdb::Error> {
+        tracing::debug!("trying to get member link '{id}' from the database");
+        let Ok(Thing { tb, id }) = thing(id) else {
+            return Ok(None);
+        };
+
+        if tb != Self::TABLE {
+            return Ok(None);
+        }
+
+        let db = use_database().await;
+
+        db.select((tb, id)).await
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_user(user: &str) -> Result<Option<MemberLinkRepository>, surrealdb::Error> {
+        tracing::debug!("getting member link of '{user}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE user = $user;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_username(
+        username: &str,
+    ) -> Result<Option<MemberLinkRepository>, surrealdb::Error> {
+        tracing::debug!("getting member link of '{username}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE username = $username;")
+            .bind(("table", Self::TABLE))
+            .bind(("username", username.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_confirmed() -> Result<Vec<MemberLinkRepository>, surrealdb::Error> {
+        tracing::debug!("getting confirmed member links from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE confirmed = true;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_pending() -> Result<Vec<MemberLinkRepository>, surrealdb::Error> {
+        tracing::debug!("getting pending member links from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE confirmed = false ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Create a new (unconfirmed) claim of a member ID, replacing any previous link of the user.
+    #[tracing::instrument(level = "trace")]
+    pub async fn claim(
+        user: String,
+        username: String,
+        member_id: u64,
+        code: String,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("claiming member '{member_id}' for '{user}'");
+        let db = use_database().await;
+
+        db.query("BEGIN TRANSACTION;")
+            .query("DELETE type::table($table) WHERE user = $user;")
+            .query("CREATE type::table($table) CONTENT $content;")
+            .query("COMMIT TRANSACTION;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.clone()))
+            .bind((
+                "content",
+                MemberLinkRepository {
+                    user,
+                    username,
+                    member_id,
+                    code,
+                    confirmed: false,
+                    created_at: Utc::now().to_rfc3339(),
+                    ..Default::default()
+                },
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn confirm(id: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("confirming member link '{id}' in database");
+        let db = use_database().await;
+
+        db.query(format!("UPDATE {id} SET confirmed = true;"))
+            .await?;
+        Ok(())
+    }
+}
@@ -5,9 +5,37 @@ if #[cfg(feature = "ssr")] {
     mod user;
     mod session;
     mod logged_in;
+    mod solution;
+    mod reaction;
+    mod vote;
+    mod leaderboard;
+    mod leaderboard_fetch;
+    mod leaderboard_config;
+    mod member_link;
+    mod team;
+    mod team_member;
+    mod event_year;
+    mod report;
+    mod moderation_action;
+    mod audit_log;
+    mod api_token;
 
     pub use self::user::*;
     pub use self::session::*;
     pub use self::logged_in::*;
+    pub use self::solution::*;
+    pub use self::reaction::*;
+    pub use self::vote::*;
+    pub use self::leaderboard::*;
+    pub use self::leaderboard_fetch::*;
+    pub use self::leaderboard_config::*;
+    pub use self::member_link::*;
+    pub use self::team::*;
+    pub use self::team_member::*;
+    pub use self::event_year::*;
+    pub use self::report::*;
+    pub use self::moderation_action::*;
+    pub use self::audit_log::*;
+    pub use self::api_token::*;
 }
 }
@@ -0,0 +1,77 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::{functions::ModerationAction, hooks::use_database};
+
+/// Record of an action a moderator took on a solution. These records are never changed or
+/// deleted, not even together with the solution.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ModerationActionRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    /// Id of the moderator.
+    pub moderator: String,
+    /// Username of the moderator at the time of the action.
+    pub moderator_name: String,
+    pub action: ModerationAction,
+    pub solution: String,
+    /// Short description of the solution, e.g., `alice 2023/1/2`, as it may be deleted.
+    pub subject: String,
+    pub reason: String,
+    pub created_at: String,
+}
+
+impl ModerationActionRepository {
+    pub const TABLE: &'static str = "moderation_action";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    /// Get the most recent actions, newest first.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_recent(
+        limit: usize,
+    ) -> Result<Vec<ModerationActionRepository>, surrealdb::Error> {
+        tracing::debug!("getting recent moderation actions from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) ORDER BY created_at DESC LIMIT $limit;")
+            .bind(("table", Self::TABLE))
+            .bind(("limit", limit))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace", skip(reason))]
+    pub async fn create(
+        moderator: String,
+        moderator_name: String,
+        action: ModerationAction,
+        solution: String,
+        subject: String,
+        reason: String,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("recording moderation action on '{solution}' in database");
+        let db = use_database().await;
+
+        let _: Option<ModerationActionRepository> = db
+            .create(Self::TABLE)
+            .content(ModerationActionRepository {
+                id: None,
+                moderator,
+                moderator_name,
+                action,
+                solution,
+                subject,
+                reason,
+                created_at: Utc::now().to_rfc3339(),
+            })
+            .await?;
+
+        Ok(())
+    }
+}
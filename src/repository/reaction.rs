@@ -0,0 +1,94 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::{functions::Reaction, hooks::use_database};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ReactionRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub user: String,
+    pub solution: String,
+    pub reaction: Reaction,
+    pub created_at: String,
+}
+
+impl ReactionRepository {
+    pub const TABLE: &'static str = "reaction";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_solutions(
+        solutions: Vec<String>,
+    ) -> Result<Vec<ReactionRepository>, surrealdb::Error> {
+        tracing::debug!("getting reactions for {} solutions", solutions.len());
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE solution INSIDE $solutions;")
+            .bind(("table", Self::TABLE))
+            .bind(("solutions", solutions))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_user(user: &str) -> Result<Vec<ReactionRepository>, surrealdb::Error> {
+        tracing::debug!("getting reactions of '{user}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE user = $user ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Add the reaction of a user to a solution or remove it, if it already exists.
+    #[tracing::instrument(level = "trace")]
+    pub async fn toggle(
+        user: String,
+        solution: String,
+        reaction: Reaction,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("toggling reaction of '{user}' on '{solution}'");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE user = $user AND solution = $solution AND reaction = $reaction;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.clone()))
+            .bind(("solution", solution.clone()))
+            .bind(("reaction", reaction))
+            .await?;
+
+        let existing: Vec<ReactionRepository> = result.take(0)?;
+
+        if existing.is_empty() {
+            let _: Option<ReactionRepository> = db
+                .create(Self::TABLE)
+                .content(ReactionRepository {
+                    user,
+                    solution,
+                    reaction,
+                    created_at: Utc::now().to_rfc3339(),
+                    ..Default::default()
+                })
+                .await?;
+            return Ok(());
+        }
+
+        for id in existing.iter().filter_map(ReactionRepository::id) {
+            db.query(format!("DELETE {id};")).await?;
+        }
+
+        Ok(())
+    }
+}
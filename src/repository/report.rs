@@ -0,0 +1,110 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::hooks::use_database;
+
+/// A report of a solution by a user, waiting for a moderator to look at it.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ReportRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub solution: String,
+    /// Id of the reporting user.
+    pub reporter: String,
+    pub reason: String,
+    /// Whether a moderator acted on the report.
+    pub resolved: bool,
+    pub created_at: String,
+}
+
+impl ReportRepository {
+    pub const TABLE: &'static str = "report";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_open() -> Result<Vec<ReportRepository>, surrealdb::Error> {
+        tracing::debug!("getting open reports from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE resolved = false ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_reporter(
+        reporter: &str,
+    ) -> Result<Vec<ReportRepository>, surrealdb::Error> {
+        tracing::debug!("getting reports of '{reporter}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query(
+                "SELECT * FROM type::table($table) WHERE reporter = $reporter ORDER BY created_at;",
+            )
+            .bind(("table", Self::TABLE))
+            .bind(("reporter", reporter.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Check, whether the user has an open report of the solution.
+    #[tracing::instrument(level = "trace")]
+    pub async fn is_reported_by(solution: &str, reporter: &str) -> Result<bool, surrealdb::Error> {
+        tracing::debug!("checking for open report of '{solution}' by '{reporter}' in the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT VALUE id FROM type::table($table) WHERE solution = $solution AND reporter = $reporter AND resolved = false;")
+            .bind(("table", Self::TABLE))
+            .bind(("solution", solution.to_string()))
+            .bind(("reporter", reporter.to_string()))
+            .await?;
+
+        let reports: Vec<Thing> = result.take(0)?;
+        Ok(!reports.is_empty())
+    }
+
+    #[tracing::instrument(level = "trace", skip(reason))]
+    pub async fn create(
+        solution: String,
+        reporter: String,
+        reason: String,
+    ) -> Result<Option<ReportRepository>, surrealdb::Error> {
+        tracing::debug!("creating report of '{solution}' in database");
+        let db = use_database().await;
+
+        db.create(Self::TABLE)
+            .content(ReportRepository {
+                solution,
+                reporter,
+                reason,
+                resolved: false,
+                created_at: Utc::now().to_rfc3339(),
+                ..Default::default()
+            })
+            .await
+    }
+
+    /// Mark all open reports of a solution as resolved.
+    #[tracing::instrument(level = "trace")]
+    pub async fn resolve(solution: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("resolving reports of '{solution}' in database");
+        let db = use_database().await;
+
+        db.query("UPDATE type::table($table) SET resolved = true WHERE solution = $solution AND resolved = false;")
+            .bind(("table", Self::TABLE))
+            .bind(("solution", solution.to_string()))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@ use crate::hooks::use_database;
 pub struct SessionRepository {
     #[serde(skip_serializing)]
     id: Option<Thing>,
-    created_at: String,
+    pub created_at: String,
 }
 
 impl SessionRepository {
@@ -35,6 +35,22 @@ impl SessionRepository {
         Ok(result)
     }
 
+    /// Get all sessions the given user is logged in with.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_user(user_id: &str) -> Result<Vec<SessionRepository>, surrealdb::Error> {
+        tracing::debug!("getting sessions of '{user_id}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query(format!(
+                "SELECT * FROM array::flatten((SELECT VALUE ->logged_in->{table} FROM {user_id}));",
+                table = Self::TABLE
+            ))
+            .await?;
+
+        result.take(0)
+    }
+
     #[tracing::instrument(level = "trace")]
     pub async fn delete(id: &str) -> Result<(), surrealdb::Error> {
         tracing::debug!("deleting session '{id}' from database");
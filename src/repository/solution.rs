@@ -0,0 +1,322 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{thing, Thing};
+
+use crate::{functions::Visibility, hooks::use_database};
+
+use super::{ReactionRepository, ReportRepository, VoteRepository};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct SolutionRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub author: String,
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+    pub code: String,
+    #[serde(default)]
+    pub writeup: Option<String>,
+    /// Overrides the default visibility of the author, if set.
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+    pub created_at: String,
+    /// Reason given by the moderator who hid this solution from everybody but its author.
+    #[serde(default)]
+    pub hidden_reason: Option<String>,
+}
+
+/// Number of records belonging to a year.
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct YearCount {
+    pub year: i32,
+    pub count: usize,
+}
+
+impl SolutionRepository {
+    pub const TABLE: &'static str = "solution";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_id(id: &str) -> Result<Option<SolutionRepository>, surrealdb::Error> {
+        tracing::debug!("trying to get solution '{id}' from the database");
+        let Ok(Thing { tb, id }) = thing(id) else {
+            return Ok(None);
+        };
+
+        if tb != Self::TABLE {
+            return Ok(None);
+        }
+
+        let db = use_database().await;
+
+        db.select((tb, id)).await
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_author(
+        author: &str,
+        year: i32,
+        day: u8,
+    ) -> Result<Vec<SolutionRepository>, surrealdb::Error> {
+        tracing::debug!("getting solutions of '{author}' for {year}/{day} from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE author = $author AND year = $year AND day = $day ORDER BY part;")
+            .bind(("table", Self::TABLE))
+            .bind(("author", author.to_string()))
+            .bind(("year", year))
+            .bind(("day", day))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_author_and_year(
+        author: &str,
+        year: i32,
+    ) -> Result<Vec<SolutionRepository>, surrealdb::Error> {
+        tracing::debug!("getting solutions of '{author}' for {year} from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE author = $author AND year = $year ORDER BY day, part;")
+            .bind(("table", Self::TABLE))
+            .bind(("author", author.to_string()))
+            .bind(("year", year))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_all_by_author(
+        author: &str,
+    ) -> Result<Vec<SolutionRepository>, surrealdb::Error> {
+        tracing::debug!("getting all solutions of '{author}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE author = $author ORDER BY year DESC, day DESC, part;")
+            .bind(("table", Self::TABLE))
+            .bind(("author", author.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Count the solutions of each year.
+    #[tracing::instrument(level = "trace")]
+    pub async fn count_by_year() -> Result<Vec<YearCount>, surrealdb::Error> {
+        tracing::debug!("counting solutions per year in the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT year, count() AS count FROM type::table($table) GROUP BY year;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_day(
+        year: i32,
+        day: u8,
+    ) -> Result<Vec<SolutionRepository>, surrealdb::Error> {
+        tracing::debug!("getting solutions for {year}/{day} from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE year = $year AND day = $day ORDER BY author, part;")
+            .bind(("table", Self::TABLE))
+            .bind(("year", year))
+            .bind(("day", day))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Search for solutions whose code or write-up contains all of the given (lowercase) terms.
+    ///
+    /// Solutions hidden by a moderator or set to private are left out unless they are written by
+    /// the viewer. The remaining visibility rules depend on the time and the default visibility of
+    /// the author and are applied by the caller.
+    ///
+    /// Only the page of at most `limit` matches starting at `start` is returned.
+    #[tracing::instrument(level = "trace")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search(
+        terms: &[String],
+        year: Option<i32>,
+        day: Option<u8>,
+        language: Option<String>,
+        author: Option<String>,
+        viewer: Option<String>,
+        start: usize,
+        limit: usize,
+    ) -> Result<Vec<SolutionRepository>, surrealdb::Error> {
+        tracing::debug!("searching solutions in the database");
+        let db = use_database().await;
+
+        let mut statement = "SELECT * FROM type::table($table) WHERE (author = $viewer OR (hidden_reason = NONE AND visibility != 'Private'))".to_string();
+
+        if year.is_some() {
+            statement.push_str(" AND year = $year");
+        }
+        if day.is_some() {
+            statement.push_str(" AND day = $day");
+        }
+        if language.is_some() {
+            statement.push_str(" AND language = $language");
+        }
+        if author.is_some() {
+            statement.push_str(" AND author = $author");
+        }
+        for index in 0..terms.len() {
+            statement.push_str(&format!(
+                " AND (string::lowercase(code) CONTAINS $term{index} OR string::lowercase(writeup ?? '') CONTAINS $term{index})"
+            ));
+        }
+        statement.push_str(" ORDER BY year DESC, day, author, part LIMIT $limit START $start;");
+
+        let mut query = db
+            .query(statement)
+            .bind(("table", Self::TABLE))
+            .bind(("year", year))
+            .bind(("day", day))
+            .bind(("language", language))
+            .bind(("author", author))
+            .bind(("viewer", viewer))
+            .bind(("start", start))
+            .bind(("limit", limit));
+
+        for (index, term) in terms.iter().enumerate() {
+            query = query.bind((format!("term{index}"), term.clone()));
+        }
+
+        let mut result = query.await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_visibility(
+        id: &str,
+        visibility: Option<Visibility>,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("set visibility of solution '{id}' in database");
+        let db = use_database().await;
+
+        db.query(format!("UPDATE {id} SET visibility = $visibility;"))
+            .bind(("visibility", visibility))
+            .await?;
+        Ok(())
+    }
+
+    /// Get all solutions hidden by a moderator.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_hidden() -> Result<Vec<SolutionRepository>, surrealdb::Error> {
+        tracing::debug!("getting hidden solutions from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE hidden_reason != NONE ORDER BY year DESC, day, author, part;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Hide the solution with the given reason, or show it again.
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_hidden(id: &str, reason: Option<String>) -> Result<(), surrealdb::Error> {
+        tracing::debug!("set hidden state of solution '{id}' in database");
+        let db = use_database().await;
+
+        db.query(format!("UPDATE {id} SET hidden_reason = $reason;"))
+            .bind(("reason", reason))
+            .await?;
+        Ok(())
+    }
+
+    /// Delete a solution together with its votes, reactions and reports.
+    #[tracing::instrument(level = "trace")]
+    pub async fn delete(id: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("deleting solution '{id}' from database");
+        let db = use_database().await;
+
+        db.query("BEGIN TRANSACTION;")
+            .query("DELETE type::table($vote_table) WHERE solution = $solution;")
+            .query("DELETE type::table($reaction_table) WHERE solution = $solution;")
+            .query("DELETE type::table($report_table) WHERE solution = $solution;")
+            .query(format!("DELETE {id};"))
+            .query("COMMIT TRANSACTION;")
+            .bind(("solution", id.to_string()))
+            .bind(("vote_table", VoteRepository::TABLE))
+            .bind(("reaction_table", ReactionRepository::TABLE))
+            .bind(("report_table", ReportRepository::TABLE))
+            .await?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(level = "trace", skip(code, writeup))]
+    pub async fn save(
+        author: String,
+        year: i32,
+        day: u8,
+        part: u8,
+        language: String,
+        code: String,
+        writeup: Option<String>,
+        visibility: Option<Visibility>,
+    ) -> Result<Option<SolutionRepository>, surrealdb::Error> {
+        tracing::debug!("saving solution of '{author}' for {year}/{day}/{part} in database");
+        let existing = Self::get_by_author(&author, year, day)
+            .await?
+            .into_iter()
+            .find(|solution| solution.part == part);
+
+        let db = use_database().await;
+
+        if let Some(id) = existing.and_then(|solution| solution.id()) {
+            let mut result = db
+                .query(format!(
+                    "UPDATE {id} SET language = $language, code = $code, writeup = $writeup, visibility = $visibility;"
+                ))
+                .bind(("language", language))
+                .bind(("code", code))
+                .bind(("writeup", writeup))
+                .bind(("visibility", visibility))
+                .await?;
+
+            return result.take(0);
+        }
+
+        let result: Option<SolutionRepository> = db
+            .create(Self::TABLE)
+            .content(SolutionRepository {
+                author,
+                year,
+                day,
+                part,
+                language,
+                code,
+                writeup,
+                visibility,
+                created_at: Utc::now().to_rfc3339(),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(result)
+    }
+}
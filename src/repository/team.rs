@@ -0,0 +1,101 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{thing, Thing};
+
+use crate::{hooks::use_database, repository::TeamMemberRepository};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TeamRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub name: String,
+    /// User who created the team and may invite others.
+    pub owner: String,
+    pub created_at: String,
+}
+
+impl TeamRepository {
+    pub const TABLE: &'static str = "team";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_all() -> Result<Vec<TeamRepository>, surrealdb::Error> {
+        tracing::debug!("getting all teams from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) ORDER BY name;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_id(id: &str) -> Result<Option<TeamRepository>, surrealdb::Error> {
+        tracing::debug!("trying to get team '{id}' from the database");
+        let Ok(Thing { tb, id }) = thing(id) else {
+            return Ok(None);
+        };
+
+        if tb != Self::TABLE {
+            return Ok(None);
+        }
+
+        let db = use_database().await;
+
+        db.select((tb, id)).await
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_name(name: &str) -> Result<Option<TeamRepository>, surrealdb::Error> {
+        tracing::debug!("getting team '{name}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE string::lowercase(name) = string::lowercase($name);")
+            .bind(("table", Self::TABLE))
+            .bind(("name", name.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn create(
+        name: String,
+        owner: String,
+    ) -> Result<Option<TeamRepository>, surrealdb::Error> {
+        tracing::debug!("inserting team '{name}' into database");
+        let db = use_database().await;
+
+        db.create(Self::TABLE)
+            .content(TeamRepository {
+                name,
+                owner,
+                created_at: Utc::now().to_rfc3339(),
+                ..Default::default()
+            })
+            .await
+    }
+
+    /// Delete a team together with all of its memberships and invitations.
+    #[tracing::instrument(level = "trace")]
+    pub async fn delete(id: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("deleting team '{id}' from database");
+        let db = use_database().await;
+
+        db.query("BEGIN TRANSACTION;")
+            .query(format!("DELETE {id};"))
+            .query("DELETE type::table($members) WHERE team = $team;")
+            .query("COMMIT TRANSACTION;")
+            .bind(("members", TeamMemberRepository::TABLE))
+            .bind(("team", id.to_string()))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{thing, Thing};
+
+use crate::hooks::use_database;
+
+/// Membership of a user in a team. Until it is accepted, the membership is an invitation.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TeamMemberRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub team: String,
+    pub user: String,
+    pub username: String,
+    pub accepted: bool,
+    pub created_at: String,
+}
+
+impl TeamMemberRepository {
+    pub const TABLE: &'static str = "team_member";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_id(id: &str) -> Result<Option<TeamMemberRepository>, surrealdb::Error> {
+        tracing::debug!("trying to get team membership '{id}' from the database");
+        let Ok(Thing { tb, id }) = thing(id) else {
+            return Ok(None);
+        };
+
+        if tb != Self::TABLE {
+            return Ok(None);
+        }
+
+        let db = use_database().await;
+
+        db.select((tb, id)).await
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_accepted() -> Result<Vec<TeamMemberRepository>, surrealdb::Error> {
+        tracing::debug!("getting all accepted team memberships from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE accepted = true ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Get all memberships and invitations of a team.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_team(team: &str) -> Result<Vec<TeamMemberRepository>, surrealdb::Error> {
+        tracing::debug!("getting members of team '{team}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE team = $team ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .bind(("team", team.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Get all memberships and invitations of a user.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_user(user: &str) -> Result<Vec<TeamMemberRepository>, surrealdb::Error> {
+        tracing::debug!("getting team memberships of '{user}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE user = $user ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn create(
+        team: String,
+        user: String,
+        username: String,
+        accepted: bool,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("adding '{user}' to team '{team}'");
+        let db = use_database().await;
+
+        let _: Option<TeamMemberRepository> = db
+            .create(Self::TABLE)
+            .content(TeamMemberRepository {
+                team,
+                user,
+                username,
+                accepted,
+                created_at: Utc::now().to_rfc3339(),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Accept an invitation. All other invitations of the user are dropped.
+    #[tracing::instrument(level = "trace")]
+    pub async fn accept(id: &str, user: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("accepting team invitation '{id}'");
+        let db = use_database().await;
+
+        db.query("BEGIN TRANSACTION;")
+            .query("UPDATE type::thing($id) SET accepted = true;")
+            .query("DELETE type::table($table) WHERE user = $user AND accepted = false;")
+            .query("COMMIT TRANSACTION;")
+            .bind(("id", id.to_string()))
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.to_string()))
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn delete(id: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("deleting team membership '{id}' from database");
+        let db = use_database().await;
+
+        db.query("DELETE type::thing($id);")
+            .bind(("id", id.to_string()))
+            .await?;
+
+        Ok(())
+    }
+}
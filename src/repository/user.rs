@@ -1,7 +1,16 @@
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use surrealdb::sql::Thing;
+use surrealdb::sql::{thing, Thing};
 
-use crate::hooks::use_database;
+use crate::{
+    functions::{Preferences, Role, Visibility},
+    hooks::use_database,
+};
+
+use super::{
+    ApiTokenRepository, AuditLogRepository, MemberLinkRepository, ReactionRepository,
+    ReportRepository, SolutionRepository, TeamMemberRepository, TeamRepository, VoteRepository,
+};
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct UserRepository {
@@ -11,6 +20,25 @@ pub struct UserRepository {
     pub password: String,
     pub email: String,
     pub email_verified: bool,
+    #[serde(default)]
+    pub default_visibility: Visibility,
+    /// Name shown instead of the username on the profile page.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub favourite_languages: Vec<String>,
+    #[serde(default)]
+    pub preferences: Preferences,
+    /// Point in time (RFC 3339) the user asked for the account to be deleted.
+    #[serde(default)]
+    pub deletion_requested_at: Option<String>,
+    #[serde(default)]
+    pub role: Role,
+    /// Disabled accounts can neither log in nor use existing sessions.
+    #[serde(default)]
+    pub disabled: bool,
 }
 
 impl UserRepository {
@@ -28,17 +56,22 @@ impl UserRepository {
         db.select(Self::TABLE).await
     }
 
+    /// Get a user by its full id, e.g. `user:abc`.
     #[tracing::instrument(level = "trace")]
     pub async fn get_by_id(id: &str) -> Result<Option<UserRepository>, surrealdb::Error> {
-        tracing::debug!(
-            "trying to get user '{id}' from the database",
-            id = id.to_string()
-        );
+        tracing::debug!("trying to get user '{id}' from the database");
+        let Some(Thing { tb, id }) = Self::parse_id(id) else {
+            return Ok(None);
+        };
+
         let db = use_database().await;
 
-        let result: Option<UserRepository> = db.select((Self::TABLE, id.to_string())).await?;
+        db.select((tb, id)).await
+    }
 
-        Ok(result)
+    /// Parse a full user id, rejecting ids of other tables.
+    fn parse_id(id: &str) -> Option<Thing> {
+        thing(id).ok().filter(|thing| thing.tb == Self::TABLE)
     }
 
     #[tracing::instrument(level = "trace")]
@@ -70,6 +103,170 @@ impl UserRepository {
         Ok(())
     }
 
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_default_visibility(
+        user_id: &str,
+        visibility: Visibility,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("set default visibility in DB for '{user_id}'");
+        let db = use_database().await;
+
+        db.query(format!(
+            "UPDATE {user_id} SET default_visibility = $visibility"
+        ))
+        .bind(("visibility", visibility))
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_preferences(
+        user_id: &str,
+        preferences: Preferences,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("set preferences in DB for '{user_id}'");
+        let db = use_database().await;
+
+        db.query(format!("UPDATE {user_id} SET preferences = $preferences"))
+            .bind(("preferences", preferences))
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn update_profile(
+        user_id: &str,
+        display_name: Option<String>,
+        bio: Option<String>,
+        favourite_languages: Vec<String>,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("update profile in DB for '{user_id}'");
+        let db = use_database().await;
+
+        db.query(format!(
+            "UPDATE {user_id} SET display_name = $display_name, bio = $bio, favourite_languages = $favourite_languages"
+        ))
+        .bind(("display_name", display_name))
+        .bind(("bio", bio))
+        .bind(("favourite_languages", favourite_languages))
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(password))]
+    pub async fn set_password(user_id: &str, password: String) -> Result<(), surrealdb::Error> {
+        tracing::debug!("set password in DB for '{user_id}'");
+        let db = use_database().await;
+
+        db.query(format!("UPDATE {user_id} SET password = $password"))
+            .bind(("password", password))
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_role(user_id: &str, role: Role) -> Result<(), surrealdb::Error> {
+        tracing::debug!("set role in DB for '{user_id}'");
+        let db = use_database().await;
+
+        db.query(format!("UPDATE {user_id} SET role = $role"))
+            .bind(("role", role))
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_disabled(user_id: &str, disabled: bool) -> Result<(), surrealdb::Error> {
+        tracing::debug!("set disabled in DB for '{user_id}'");
+        let db = use_database().await;
+
+        db.query(format!("UPDATE {user_id} SET disabled = $disabled"))
+            .bind(("disabled", disabled))
+            .await?;
+        Ok(())
+    }
+
+    /// Mark the account for deletion, or unmark it again.
+    #[tracing::instrument(level = "trace")]
+    pub async fn set_deletion_requested(
+        user_id: &str,
+        requested: bool,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("set deletion request in DB for '{user_id}'");
+        let db = use_database().await;
+
+        db.query(format!(
+            "UPDATE {user_id} SET deletion_requested_at = $requested_at"
+        ))
+        .bind(("requested_at", requested.then(|| Utc::now().to_rfc3339())))
+        .await?;
+        Ok(())
+    }
+
+    /// Get all users who requested the deletion of their account before the given point in time
+    /// (RFC 3339).
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_deletion_requested_before(
+        before: &str,
+    ) -> Result<Vec<UserRepository>, surrealdb::Error> {
+        tracing::debug!("getting users to delete from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE deletion_requested_at != NONE AND deletion_requested_at < $before;")
+            .bind(("table", Self::TABLE))
+            .bind(("before", before.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Delete a user together with everything belonging to them: sessions, solutions (including
+    /// the votes, reactions and reports they received), votes, reactions, reports, member links,
+    /// team memberships and the teams they own.
+    ///
+    /// The entries of the audit log concerning the user are kept for the admins, but anonymised:
+    /// the user is replaced by [`AuditLogRepository::DELETED_USER`], also where they acted as an
+    /// admin, and IP addresses and user agents are dropped.
+    #[tracing::instrument(level = "trace")]
+    pub async fn delete(user_id: &str, username: &str) -> Result<(), surrealdb::Error> {
+        tracing::debug!("deleting user '{user_id}' from database");
+        let db = use_database().await;
+
+        db.query("BEGIN TRANSACTION;")
+            .query("LET $solutions = (SELECT VALUE <string> id FROM type::table($solution_table) WHERE author = $username);")
+            .query("DELETE type::table($vote_table) WHERE user = $user OR solution INSIDE $solutions;")
+            .query("DELETE type::table($reaction_table) WHERE user = $user OR solution INSIDE $solutions;")
+            .query("DELETE type::table($report_table) WHERE reporter = $user OR solution INSIDE $solutions;")
+            .query("DELETE type::table($solution_table) WHERE author = $username;")
+            .query("DELETE type::table($member_link_table) WHERE user = $user;")
+            .query("LET $teams = (SELECT VALUE <string> id FROM type::table($team_table) WHERE owner = $user);")
+            .query("DELETE type::table($team_member_table) WHERE user = $user OR team INSIDE $teams;")
+            .query("DELETE type::table($team_table) WHERE owner = $user;")
+            .query("DELETE type::table($api_token_table) WHERE user = $user;")
+            .query("UPDATE type::table($audit_log_table) SET user = NONE, username = $deleted_user, ip = NONE, user_agent = NONE WHERE user = $user OR username = $username;")
+            .query("UPDATE type::table($audit_log_table) SET actor = $deleted_user WHERE actor = $username;")
+            .query(format!("LET $sessions = array::flatten((SELECT VALUE ->logged_in->session FROM {user_id}));"))
+            .query("DELETE $sessions;")
+            .query(format!("DELETE {user_id};"))
+            .query("COMMIT TRANSACTION;")
+            .bind(("user", user_id.to_string()))
+            .bind(("username", username.to_string()))
+            .bind(("solution_table", SolutionRepository::TABLE))
+            .bind(("vote_table", VoteRepository::TABLE))
+            .bind(("reaction_table", ReactionRepository::TABLE))
+            .bind(("report_table", ReportRepository::TABLE))
+            .bind(("member_link_table", MemberLinkRepository::TABLE))
+            .bind(("team_table", TeamRepository::TABLE))
+            .bind(("team_member_table", TeamMemberRepository::TABLE))
+            .bind(("api_token_table", ApiTokenRepository::TABLE))
+            .bind(("audit_log_table", AuditLogRepository::TABLE))
+            .bind(("deleted_user", AuditLogRepository::DELETED_USER))
+            .await?;
+
+        Ok(())
+    }
+
     #[tracing::instrument(level = "trace")]
     pub async fn create(
         username: String,
@@ -92,3 +289,23 @@ impl UserRepository {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_id() {
+        // API tokens, reports etc. store the full id of a user
+        let user = UserRepository {
+            id: Some(thing("user:p4k9x2").unwrap()),
+            ..Default::default()
+        };
+        let stored = user.id().unwrap();
+
+        assert_eq!(stored, "user:p4k9x2");
+        assert_eq!(UserRepository::parse_id(&stored), user.id);
+        assert_eq!(UserRepository::parse_id("session:p4k9x2"), None);
+        assert_eq!(UserRepository::parse_id("p4k9x2"), None);
+    }
+}
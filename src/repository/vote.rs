@@ -0,0 +1,102 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::hooks::use_database;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct VoteRepository {
+    #[serde(skip_serializing)]
+    id: Option<Thing>,
+    pub user: String,
+    pub solution: String,
+    pub year: i32,
+    pub day: u8,
+    pub created_at: String,
+}
+
+impl VoteRepository {
+    pub const TABLE: &'static str = "vote";
+
+    pub fn id(&self) -> Option<String> {
+        self.id.as_ref().map(|id| format!("{}:{}", id.tb, id.id))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_day(year: i32, day: u8) -> Result<Vec<VoteRepository>, surrealdb::Error> {
+        tracing::debug!("getting votes for {year}/{day} from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE year = $year AND day = $day;")
+            .bind(("table", Self::TABLE))
+            .bind(("year", year))
+            .bind(("day", day))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_year(year: i32) -> Result<Vec<VoteRepository>, surrealdb::Error> {
+        tracing::debug!("getting votes for {year} from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE year = $year;")
+            .bind(("table", Self::TABLE))
+            .bind(("year", year))
+            .await?;
+
+        result.take(0)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_by_user(user: &str) -> Result<Vec<VoteRepository>, surrealdb::Error> {
+        tracing::debug!("getting votes of '{user}' from the database");
+        let db = use_database().await;
+
+        let mut result = db
+            .query("SELECT * FROM type::table($table) WHERE user = $user ORDER BY created_at;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.to_string()))
+            .await?;
+
+        result.take(0)
+    }
+
+    /// Cast the vote of a user for a day, replacing any vote the user already cast for that day.
+    #[tracing::instrument(level = "trace")]
+    pub async fn cast(
+        user: String,
+        solution: String,
+        year: i32,
+        day: u8,
+    ) -> Result<(), surrealdb::Error> {
+        tracing::debug!("casting vote of '{user}' for '{solution}'");
+        let db = use_database().await;
+
+        db.query("BEGIN TRANSACTION;")
+            .query("DELETE type::table($table) WHERE user = $user AND year = $year AND day = $day;")
+            .query("CREATE type::table($table) CONTENT $content;")
+            .query("COMMIT TRANSACTION;")
+            .bind(("table", Self::TABLE))
+            .bind(("user", user.clone()))
+            .bind(("year", year))
+            .bind(("day", day))
+            .bind((
+                "content",
+                VoteRepository {
+                    user,
+                    solution,
+                    year,
+                    day,
+                    created_at: Utc::now().to_rfc3339(),
+                    ..Default::default()
+                },
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
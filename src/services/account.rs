@@ -0,0 +1,54 @@
+use std::time::Duration as StdDuration;
+
+use actix_web::rt::time::sleep;
+use chrono::{Duration, Utc};
+
+use crate::{
+    functions::DELETION_GRACE_DAYS, repository::UserRepository, utils::avatar::avatar_path,
+};
+
+/// How often to look for accounts whose grace period is over.
+const PURGE_INTERVAL: StdDuration = StdDuration::from_secs(60 * 60);
+
+/// Delete all accounts whose deletion was requested more than the grace period ago.
+#[tracing::instrument(level = "trace")]
+async fn purge_deleted_accounts() {
+    let cutoff = (Utc::now() - Duration::days(DELETION_GRACE_DAYS)).to_rfc3339();
+
+    let users = match UserRepository::get_deletion_requested_before(&cutoff).await {
+        Ok(users) => users,
+        Err(e) => {
+            tracing::error!("failed to get accounts to delete: {e:?}");
+            return;
+        }
+    };
+
+    for user in users {
+        let Some(id) = user.id() else {
+            continue;
+        };
+
+        if let Err(e) = UserRepository::delete(&id, &user.username).await {
+            tracing::error!("failed to delete account '{id}': {e:?}");
+            continue;
+        }
+
+        let avatar = avatar_path(&id);
+        if avatar.exists() {
+            if let Err(e) = std::fs::remove_file(&avatar) {
+                tracing::error!("failed to remove avatar of deleted account '{id}': {e:?}");
+            }
+        }
+
+        tracing::info!("deleted account '{id}'");
+    }
+}
+
+/// Periodically delete accounts whose grace period is over.
+#[tracing::instrument(level = "trace")]
+pub async fn poll() {
+    loop {
+        purge_deleted_accounts().await;
+        sleep(PURGE_INTERVAL).await;
+    }
+}
@@ -0,0 +1,284 @@
+use std::{
+    env,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use actix_web::{
+    http::header::{HeaderName, FORWARDED, USER_AGENT, X_FORWARDED_FOR},
+    HttpRequest,
+};
+
+use crate::{functions::AuditEvent, model::User, repository::AuditLogRepository};
+
+/// Account an audit event concerns.
+#[derive(Debug, Clone)]
+pub struct Subject {
+    pub id: Option<String>,
+    pub username: String,
+}
+
+impl Subject {
+    /// A username that does not belong to any account, e.g., of a failed login.
+    pub fn unknown(username: &str) -> Self {
+        Self {
+            id: None,
+            username: username.to_string(),
+        }
+    }
+}
+
+impl From<&User> for Subject {
+    fn from(user: &User) -> Self {
+        Self {
+            id: Some(user.id.clone()),
+            username: user.username.clone(),
+        }
+    }
+}
+
+/// IP addresses of the reverse proxies, taken from the comma-separated `TRUSTED_PROXIES`.
+fn trusted_proxies() -> Vec<IpAddr> {
+    env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|proxy| proxy.trim().parse().ok())
+        .collect()
+}
+
+/// Addresses listed in the `Forwarded` header, or else in the `X-Forwarded-For` header, in the
+/// order of the hops. Entries that are not an IP address, e.g., obfuscated ones, are `None`.
+fn forwarded_addresses(req: &HttpRequest) -> Vec<Option<IpAddr>> {
+    let values = |name: HeaderName| {
+        req.headers()
+            .get_all(name)
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    let forwarded = values(FORWARDED);
+    if !forwarded.trim().is_empty() {
+        return parse_forwarded(&forwarded);
+    }
+
+    values(X_FORWARDED_FOR)
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(parse_address)
+        .collect()
+}
+
+/// Parse the `for` parameters of a `Forwarded` header (RFC 7239).
+fn parse_forwarded(header: &str) -> Vec<Option<IpAddr>> {
+    header
+        .split(',')
+        .filter(|element| !element.trim().is_empty())
+        .map(|element| {
+            element
+                .split(';')
+                .filter_map(|pair| pair.trim().split_once('='))
+                .find(|(key, _)| key.trim().eq_ignore_ascii_case("for"))
+                .and_then(|(_, value)| parse_address(value))
+        })
+        .collect()
+}
+
+/// Parse a forwarded address, which may be quoted and may have a port.
+fn parse_address(address: &str) -> Option<IpAddr> {
+    let address = address.trim().trim_matches('"');
+
+    if let Some(ipv6) = address.strip_prefix('[') {
+        return ipv6.split_once(']')?.0.parse().ok();
+    }
+
+    address.parse().ok().or_else(|| {
+        let (ipv4, port) = address.rsplit_once(':')?;
+        port.parse::<u16>().ok()?;
+        ipv4.parse::<Ipv4Addr>().ok().map(IpAddr::V4)
+    })
+}
+
+/// IP address of the client.
+///
+/// Any client can set forwarded headers, so they are only followed from the peer backwards as
+/// long as the addresses belong to trusted proxies. The first address not belonging to a trusted
+/// proxy is the client. If an entry cannot be parsed, the last trusted proxy is recorded instead.
+fn client_ip(
+    peer: Option<IpAddr>,
+    forwarded: &[Option<IpAddr>],
+    trusted: &[IpAddr],
+) -> Option<String> {
+    let mut client = peer?;
+
+    for hop in forwarded.iter().rev() {
+        if !trusted.contains(&client) {
+            break;
+        }
+
+        match hop {
+            Some(hop) => client = *hop,
+            None => break,
+        }
+    }
+
+    Some(client.to_string())
+}
+
+/// Append an event to the audit log, together with the IP address and user agent of the request.
+///
+/// Behind a reverse proxy, its address has to be listed in `TRUSTED_PROXIES` to record the
+/// forwarded address of the client instead. Failing to record an event is logged but does not fail
+/// the action itself.
+#[tracing::instrument(level = "trace", skip(req))]
+pub async fn record(
+    req: Option<&HttpRequest>,
+    subject: Subject,
+    actor: Option<&User>,
+    event: AuditEvent,
+) {
+    let ip = req.and_then(|req| {
+        client_ip(
+            req.peer_addr().map(|addr| addr.ip()),
+            &forwarded_addresses(req),
+            &trusted_proxies(),
+        )
+    });
+    let user_agent = req.and_then(|req| {
+        req.headers()
+            .get(USER_AGENT)
+            .and_then(|agent| agent.to_str().ok())
+            .map(|agent| agent.to_string())
+    });
+
+    if let Err(e) = AuditLogRepository::create(
+        subject.id,
+        subject.username,
+        actor.map(|actor| actor.username.clone()),
+        event,
+        ip,
+        user_agent,
+    )
+    .await
+    {
+        tracing::error!("failed to append to the audit log: {e:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(address: &str) -> IpAddr {
+        address.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(parse_address("198.51.100.2"), Some(ip("198.51.100.2")));
+        assert_eq!(
+            parse_address(" 198.51.100.2:4711"),
+            Some(ip("198.51.100.2"))
+        );
+        assert_eq!(parse_address("2001:db8::1"), Some(ip("2001:db8::1")));
+        assert_eq!(
+            parse_address("\"[2001:db8::1]:4711\""),
+            Some(ip("2001:db8::1"))
+        );
+        assert_eq!(parse_address("_hidden"), None);
+        assert_eq!(parse_address("unknown"), None);
+    }
+
+    #[test]
+    fn test_parse_forwarded() {
+        assert_eq!(
+            parse_forwarded("for=198.51.100.2;proto=https, For=\"[2001:db8::1]:80\", by=10.0.0.1"),
+            vec![Some(ip("198.51.100.2")), Some(ip("2001:db8::1")), None]
+        );
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let proxy = ip("10.0.0.1");
+        let client = ip("203.0.113.7");
+
+        assert_eq!(
+            client_ip(Some(proxy), &[Some(ip("198.51.100.2"))], &[proxy]),
+            Some("198.51.100.2".to_string())
+        );
+        // forwarded headers of untrusted peers are ignored
+        assert_eq!(
+            client_ip(Some(client), &[Some(ip("198.51.100.2"))], &[proxy]),
+            Some("203.0.113.7".to_string())
+        );
+        assert_eq!(
+            client_ip(Some(client), &[Some(ip("198.51.100.2"))], &[]),
+            Some("203.0.113.7".to_string())
+        );
+        assert_eq!(
+            client_ip(Some(proxy), &[], &[proxy]),
+            Some("10.0.0.1".to_string())
+        );
+        assert_eq!(client_ip(None, &[Some(ip("198.51.100.2"))], &[proxy]), None);
+    }
+
+    #[test]
+    fn test_client_ip_multiple_hops() {
+        let proxies = [ip("10.0.0.1"), ip("10.0.0.2")];
+
+        // client -> 10.0.0.2 -> 10.0.0.1
+        assert_eq!(
+            client_ip(
+                Some(ip("10.0.0.1")),
+                &[Some(ip("203.0.113.7")), Some(ip("10.0.0.2"))],
+                &proxies
+            ),
+            Some("203.0.113.7".to_string())
+        );
+        // an untrusted hop in between is the client as far as we can tell
+        assert_eq!(
+            client_ip(
+                Some(ip("10.0.0.1")),
+                &[Some(ip("203.0.113.7")), Some(ip("192.0.2.9"))],
+                &proxies
+            ),
+            Some("192.0.2.9".to_string())
+        );
+        // unparsable entries stop at the last trusted proxy
+        assert_eq!(
+            client_ip(
+                Some(ip("10.0.0.1")),
+                &[Some(ip("203.0.113.7")), None],
+                &proxies
+            ),
+            Some("10.0.0.1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_client_ip_spoofed() {
+        let proxy = ip("10.0.0.1");
+
+        // the client sent its own X-Forwarded-For, which the proxy appended to
+        assert_eq!(
+            client_ip(
+                Some(proxy),
+                &[Some(ip("198.51.100.2")), Some(ip("203.0.113.7"))],
+                &[proxy]
+            ),
+            Some("203.0.113.7".to_string())
+        );
+        // a client pretending to be a trusted proxy does not help either
+        assert_eq!(
+            client_ip(
+                Some(proxy),
+                &[
+                    Some(ip("198.51.100.2")),
+                    Some(proxy),
+                    Some(ip("203.0.113.7"))
+                ],
+                &[proxy]
+            ),
+            Some("203.0.113.7".to_string())
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use std::{env, fmt::Display, sync::OnceLock, time::Duration as StdDuration};
+
+use actix_web::rt::time::sleep;
+use chrono::{DateTime, Datelike, Duration, Utc};
+use futures_util::lock::Mutex;
+use reqwest::{
+    header::{HeaderName, COOKIE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    StatusCode,
+};
+
+use crate::{
+    repository::{
+        LeaderboardConfigRepository, LeaderboardFetchRepository, LeaderboardSnapshotRepository,
+    },
+    utils::{calendar::current_event_year, leaderboard::Leaderboard},
+};
+
+/// Advent of Code asks to not request private leaderboards more often than every 15 minutes.
+pub const MIN_INTERVAL_MINUTES: i64 = 15;
+
+/// Longest interval between two automatic refreshes, a day.
+pub const MAX_INTERVAL_MINUTES: i64 = 24 * 60;
+
+/// Time after which a request to Advent of Code is given up.
+const REQUEST_TIMEOUT: StdDuration = StdDuration::from_secs(30);
+
+/// Serializes refreshes, so the background poller and a manual refresh in the admin area cannot
+/// both pass the check of the last request before either of them recorded its own.
+static REFRESH_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefreshOutcome {
+    Updated,
+    NotModified,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefreshError {
+    /// The last request is too recent; holds the minutes until the next one is allowed.
+    TooSoon(i64),
+    Fetch(String),
+    Database(String),
+}
+
+impl Display for RefreshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RefreshError::TooSoon(minutes) => write!(f, "next request allowed in {minutes} min"),
+            RefreshError::Fetch(e) => write!(f, "fetching failed: {e}"),
+            RefreshError::Database(e) => write!(f, "database error: {e}"),
+        }
+    }
+}
+
+/// URL of the private leaderboard JSON for the given year.
+///
+/// Taken from the configuration of the admin area or, if unset, from `AOC_LEADERBOARD_URL`, where
+/// `{year}` is replaced by the year, e.g.,
+/// `https://adventofcode.com/{year}/leaderboard/private/view/123456.json`. It can point to a local
+/// stand-in for testing.
+async fn url(year: i32) -> Result<String, RefreshError> {
+    let configured = LeaderboardConfigRepository::get()
+        .await
+        .map_err(|e| RefreshError::Database(e.to_string()))?
+        .and_then(|config| config.url);
+
+    let url = configured
+        .or_else(|| env::var("AOC_LEADERBOARD_URL").ok())
+        .ok_or_else(|| RefreshError::Fetch("no leaderboard URL configured".to_string()))?;
+
+    Ok(url.replace("{year}", &year.to_string()))
+}
+
+/// Interval between two automatic refreshes, taken from the configuration of the admin area or,
+/// if unset, from `AOC_POLL_INTERVAL` (in minutes). It is kept between [`MIN_INTERVAL_MINUTES`]
+/// and [`MAX_INTERVAL_MINUTES`].
+pub async fn poll_interval() -> Duration {
+    let configured = LeaderboardConfigRepository::get()
+        .await
+        .ok()
+        .flatten()
+        .and_then(|config| config.poll_interval);
+
+    let minutes = configured.or_else(|| {
+        env::var("AOC_POLL_INTERVAL")
+            .ok()
+            .and_then(|minutes| minutes.parse().ok())
+    });
+
+    interval(minutes)
+}
+
+/// Interval for the given minutes, defaulting to [`MIN_INTERVAL_MINUTES`] and clamped between
+/// [`MIN_INTERVAL_MINUTES`] and [`MAX_INTERVAL_MINUTES`].
+fn interval(minutes: Option<i64>) -> Duration {
+    Duration::minutes(
+        minutes
+            .unwrap_or(MIN_INTERVAL_MINUTES)
+            .clamp(MIN_INTERVAL_MINUTES, MAX_INTERVAL_MINUTES),
+    )
+}
+
+/// Minutes until the next request is allowed after the one attempted at the given point in time
+/// (RFC 3339), if it is not allowed yet.
+fn minutes_until_allowed(attempted_at: &str, now: DateTime<Utc>) -> Option<i64> {
+    let attempted_at = DateTime::parse_from_rfc3339(attempted_at).ok()?;
+    let next = attempted_at.with_timezone(&Utc) + Duration::minutes(MIN_INTERVAL_MINUTES);
+
+    (next > now).then(|| (next - now).num_minutes() + 1)
+}
+
+/// Fetch the private leaderboard of the given year and store it as a new snapshot, if it changed.
+///
+/// The point in time of each request is persisted before it is sent, so neither failures nor
+/// restarts of the server can lead to more than one request per [`MIN_INTERVAL_MINUTES`]. The
+/// limit applies across all years, as Advent of Code counts the requests of a session.
+#[tracing::instrument(level = "trace")]
+pub async fn refresh(year: i32) -> Result<RefreshOutcome, RefreshError> {
+    tracing::debug!("refreshing leaderboard for {year}");
+    let url = url(year).await?;
+
+    let _guard = REFRESH_LOCK.get_or_init(|| Mutex::new(())).lock().await;
+
+    let last = LeaderboardFetchRepository::get_last()
+        .await
+        .map_err(|e| RefreshError::Database(e.to_string()))?;
+
+    let now = Utc::now();
+
+    if let Some(minutes) = last.and_then(|last| minutes_until_allowed(&last.attempted_at, now)) {
+        return Err(RefreshError::TooSoon(minutes));
+    }
+
+    let state = LeaderboardFetchRepository::get(year)
+        .await
+        .map_err(|e| RefreshError::Database(e.to_string()))?
+        .unwrap_or_default();
+
+    LeaderboardFetchRepository::save(
+        year,
+        now.to_rfc3339(),
+        state.etag.clone(),
+        state.last_modified.clone(),
+    )
+    .await
+    .map_err(|e| RefreshError::Database(e.to_string()))?;
+
+    let session = env::var("AOC_SESSION").unwrap_or_default();
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| RefreshError::Fetch(e.to_string()))?;
+    let mut request = client.get(url).header(COOKIE, format!("session={session}"));
+
+    if let Some(etag) = &state.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &state.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| RefreshError::Fetch(e.to_string()))?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(RefreshOutcome::NotModified);
+    }
+
+    let response = response
+        .error_for_status()
+        .map_err(|e| RefreshError::Fetch(e.to_string()))?;
+
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| RefreshError::Fetch(e.to_string()))?;
+
+    let leaderboard =
+        Leaderboard::from_json(&body).map_err(|e| RefreshError::Fetch(e.to_string()))?;
+
+    LeaderboardSnapshotRepository::create(year, leaderboard)
+        .await
+        .map_err(|e| RefreshError::Database(e.to_string()))?;
+
+    LeaderboardFetchRepository::save(year, now.to_rfc3339(), etag, last_modified)
+        .await
+        .map_err(|e| RefreshError::Database(e.to_string()))?;
+
+    Ok(RefreshOutcome::Updated)
+}
+
+/// Periodically refresh the leaderboard of the current event while it is running. The interval
+/// is read anew after each round, so changes in the admin area apply without a restart.
+#[tracing::instrument(level = "trace")]
+pub async fn poll() {
+    tracing::info!(
+        "polling leaderboard every {} minutes",
+        poll_interval().await.num_minutes()
+    );
+
+    loop {
+        let year = current_event_year();
+        if Utc::now().month() == 12 && url(year).await.is_ok() {
+            match refresh(year).await {
+                Ok(outcome) => tracing::debug!("leaderboard refresh: {outcome:?}"),
+                Err(RefreshError::TooSoon(minutes)) => {
+                    tracing::debug!("skipping leaderboard refresh, next one in {minutes} min")
+                }
+                Err(e) => tracing::error!("failed to refresh leaderboard: {e}"),
+            }
+        }
+
+        sleep(
+            poll_interval()
+                .await
+                .to_std()
+                .unwrap_or(StdDuration::from_secs(MIN_INTERVAL_MINUTES as u64 * 60)),
+        )
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(interval(None), Duration::minutes(MIN_INTERVAL_MINUTES));
+        assert_eq!(interval(Some(30)), Duration::minutes(30));
+        assert_eq!(interval(Some(1)), Duration::minutes(MIN_INTERVAL_MINUTES));
+        assert_eq!(interval(Some(-5)), Duration::minutes(MIN_INTERVAL_MINUTES));
+        assert_eq!(
+            interval(Some(i64::MAX)),
+            Duration::minutes(MAX_INTERVAL_MINUTES)
+        );
+    }
+
+    #[test]
+    fn test_minutes_until_allowed() {
+        let attempted_at = Utc::now();
+
+        assert_eq!(
+            minutes_until_allowed(&attempted_at.to_rfc3339(), attempted_at),
+            Some(MIN_INTERVAL_MINUTES + 1)
+        );
+        assert_eq!(
+            minutes_until_allowed(
+                &attempted_at.to_rfc3339(),
+                attempted_at + Duration::minutes(10)
+            ),
+            Some(MIN_INTERVAL_MINUTES - 10 + 1)
+        );
+        assert_eq!(
+            minutes_until_allowed(
+                &attempted_at.to_rfc3339(),
+                attempted_at + Duration::minutes(MIN_INTERVAL_MINUTES)
+            ),
+            None
+        );
+        // nothing attempted yet
+        assert_eq!(minutes_until_allowed("", attempted_at), None);
+    }
+}
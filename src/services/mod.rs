@@ -2,8 +2,12 @@ use cfg_if::cfg_if;
 
 cfg_if! {
 if #[cfg(feature = "ssr")] {
+    pub mod account;
+    pub mod audit;
     pub mod database;
     pub mod mail;
     pub mod jwt;
+    pub mod leaderboard;
+    pub mod roles;
 }
 }
@@ -0,0 +1,32 @@
+use std::env;
+
+use crate::{functions::Role, model::User};
+
+/// Promote the users listed in the comma-separated `ADMIN_USERS` to admins.
+///
+/// This is only meant to hand out the first admin role of a fresh installation; all further roles
+/// are managed in the admin area. Users who register later are not affected until the next start.
+#[tracing::instrument(level = "trace")]
+pub async fn bootstrap_admins() {
+    let admins = env::var("ADMIN_USERS").unwrap_or_default();
+
+    for username in admins
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let Some(user) = User::get_by_username(username).await else {
+            tracing::warn!("admin '{username}' from ADMIN_USERS does not exist");
+            continue;
+        };
+
+        if user.is_admin() {
+            continue;
+        }
+
+        match user.set_role(Role::Admin).await {
+            Ok(()) => tracing::info!("promoted '{username}' to admin"),
+            Err(e) => tracing::error!("failed to promote '{username}' to admin: {e:?}"),
+        }
+    }
+}
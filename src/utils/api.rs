@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    functions::{file_extension, ProfileSolution, Visibility, YearStars},
+    utils::leaderboard::Standing,
+};
+
+/// Path all routes of the REST API are nested under.
+pub const API_V1: &str = "/api/v1";
+
+/// Body of every unsuccessful response of the REST API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct ApiError {
+    pub error: String,
+}
+
+impl ApiError {
+    pub fn new(error: impl ToString) -> ApiError {
+        ApiError {
+            error: error.to_string(),
+        }
+    }
+}
+
+/// A solution as returned by the REST API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct ApiSolution {
+    pub id: String,
+    pub author: String,
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+    pub code: String,
+    /// Markdown source of the write-up attached to the solution.
+    pub writeup: Option<String>,
+    /// Point in time (RFC 3339) the solution was submitted.
+    pub created_at: String,
+}
+
+impl ApiSolution {
+    /// Name of a file for the code of this solution, e.g., `day01_part2.rs`.
+    pub fn file_name(&self) -> String {
+        format!(
+            "day{day:02}_part{part}.{extension}",
+            day = self.day,
+            part = self.part,
+            extension = file_extension(&self.language)
+        )
+    }
+}
+
+/// A solution to submit via the REST API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct NewSolution {
+    pub year: i32,
+    pub day: u8,
+    pub part: u8,
+    pub language: String,
+    pub code: String,
+    #[serde(default)]
+    pub writeup: Option<String>,
+    /// Visibility override of the solution. Without one, the default visibility of the author
+    /// applies.
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+}
+
+/// Standings of a year as returned by the REST API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct ApiLeaderboard {
+    pub year: i32,
+    /// Point in time (RFC 3339) of the snapshot the standings are computed from.
+    pub fetched_at: Option<String>,
+    pub standings: Vec<Standing>,
+}
+
+/// Profile of a user as returned by the REST API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct ApiProfile {
+    pub username: String,
+    pub display_name: Option<String>,
+    /// Markdown source of the bio.
+    pub bio: Option<String>,
+    pub favourite_languages: Vec<String>,
+    /// Advent of Code member the user is (confirmed to be) linked to.
+    pub member_id: Option<u64>,
+    /// Stars per year, newest year first.
+    pub stars: Vec<YearStars>,
+    /// Solutions visible to the owner of the token, newest first.
+    pub solutions: Vec<ProfileSolution>,
+}
@@ -0,0 +1,160 @@
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(feature = "ssr")]
+use std::{env, io::Cursor, path::PathBuf};
+
+#[cfg(feature = "ssr")]
+use image::{imageops::FilterType, io::Reader, ImageOutputFormat, Limits};
+
+/// Width and height (in pixels) avatars are stored with.
+pub const AVATAR_SIZE: u32 = 128;
+
+/// Maximum size (in bytes) of an uploaded avatar.
+pub const MAX_AVATAR_UPLOAD: usize = 2 * 1024 * 1024;
+
+/// Maximum width and height of an uploaded image, to reject decompression bombs early.
+#[cfg(feature = "ssr")]
+const MAX_DIMENSION: u32 = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvatarError {
+    TooLarge,
+    InvalidImage,
+    Internal,
+}
+
+impl AvatarError {
+    /// Identifier used to pass the error on via query parameters.
+    pub fn key(&self) -> &'static str {
+        match self {
+            AvatarError::TooLarge => "too-large",
+            AvatarError::InvalidImage => "invalid",
+            AvatarError::Internal => "internal",
+        }
+    }
+}
+
+impl FromStr for AvatarError {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "too-large" => Ok(AvatarError::TooLarge),
+            "invalid" => Ok(AvatarError::InvalidImage),
+            "internal" => Ok(AvatarError::Internal),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for AvatarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use AvatarError::*;
+
+        match self {
+            TooLarge => write!(
+                f,
+                "Avatars must not be larger than {} MiB",
+                MAX_AVATAR_UPLOAD / 1024 / 1024
+            ),
+            InvalidImage => f.write_str("The file is no supported image (PNG, JPEG, GIF or WebP)"),
+            Internal => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+/// URL the avatar of the given user is served at. The username is percent-encoded, as it may
+/// contain characters with a special meaning in URLs.
+pub fn avatar_url(username: &str) -> String {
+    let encoded = username
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect::<String>();
+
+    format!("/avatar/{encoded}")
+}
+
+/// Path of the file the avatar of the given user is stored in. The directory can be configured
+/// via `AVATAR_DIR`.
+#[cfg(feature = "ssr")]
+pub fn avatar_path(user_id: &str) -> PathBuf {
+    let directory = env::var("AVATAR_DIR").unwrap_or_else(|_| "avatars".to_string());
+    let key = user_id.split_once(':').map_or(user_id, |(_, key)| key);
+
+    PathBuf::from(directory).join(format!("{key}.png"))
+}
+
+/// Validate an uploaded image, crop it to a square of [`AVATAR_SIZE`] and re-encode it as PNG.
+///
+/// Re-encoding drops any metadata (e.g., EXIF location data) and guarantees that only proper
+/// images are served back to other users.
+#[cfg(feature = "ssr")]
+pub fn process_avatar(bytes: &[u8]) -> Result<Vec<u8>, AvatarError> {
+    if bytes.len() > MAX_AVATAR_UPLOAD {
+        return Err(AvatarError::TooLarge);
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+
+    let mut reader = Reader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|_| AvatarError::InvalidImage)?;
+    reader.limits(limits);
+
+    let image = reader.decode().map_err(|_| AvatarError::InvalidImage)?;
+    let avatar = image.resize_to_fill(AVATAR_SIZE, AVATAR_SIZE, FilterType::Lanczos3);
+
+    let mut png = vec![];
+    avatar
+        .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+        .map_err(|_| AvatarError::Internal)?;
+
+    Ok(png)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use image::{DynamicImage, GenericImageView, RgbImage};
+
+    use super::*;
+
+    #[test]
+    fn test_process_avatar() {
+        let mut jpeg = vec![];
+        DynamicImage::ImageRgb8(RgbImage::new(300, 200))
+            .write_to(&mut Cursor::new(&mut jpeg), ImageOutputFormat::Jpeg(90))
+            .unwrap();
+
+        let png = process_avatar(&jpeg).unwrap();
+        let avatar = image::load_from_memory(&png).unwrap();
+
+        assert_eq!(avatar.dimensions(), (AVATAR_SIZE, AVATAR_SIZE));
+        assert_eq!(image::guess_format(&png).unwrap(), image::ImageFormat::Png);
+    }
+
+    #[test]
+    fn test_avatar_url() {
+        assert_eq!(avatar_url("alice_1.x"), "/avatar/alice_1.x");
+        assert_eq!(avatar_url("../a b?c#d"), "/avatar/..%2Fa%20b%3Fc%23d");
+        assert_eq!(avatar_url("jürgen"), "/avatar/j%C3%BCrgen");
+    }
+
+    #[test]
+    fn test_process_invalid_avatar() {
+        assert_eq!(
+            process_avatar(b"<svg></svg>"),
+            Err(AvatarError::InvalidImage)
+        );
+        assert_eq!(
+            process_avatar(&vec![0; MAX_AVATAR_UPLOAD + 1]),
+            Err(AvatarError::TooLarge)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+/// Number of puzzles of each event.
+pub const DAYS: u8 = 25;
+
+/// Time zone in which Advent of Code unlocks its puzzles (EST, UTC-5). December is never affected
+/// by daylight saving time, so a fixed offset is sufficient.
+fn eastern() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).expect("UTC-5 should be a valid offset")
+}
+
+/// Get the year of the most recent Advent of Code event.
+///
+/// Before December, this is the event of the previous year.
+pub fn current_event_year() -> i32 {
+    let now = Utc::now();
+
+    if now.month() == 12 {
+        now.year()
+    } else {
+        now.year() - 1
+    }
+}
+
+/// Get the point in time when the puzzle of the given day unlocks.
+///
+/// Puzzles unlock at midnight EST (UTC-5), i.e., at 06:00 CET. Years and days that cannot be
+/// represented (the year might stem from user input) have no unlock time.
+pub fn unlock_time(year: i32, day: u8) -> Option<DateTime<Utc>> {
+    eastern()
+        .with_ymd_and_hms(year, 12, day as u32, 0, 0, 0)
+        .single()
+        .map(|unlock| unlock.with_timezone(&Utc))
+}
+
+/// Whether the puzzle of the given day is unlocked at the given point in time.
+pub fn is_unlocked(year: i32, day: u8, now: DateTime<Utc>) -> bool {
+    (1..=DAYS).contains(&day) && unlock_time(year, day).is_some_and(|unlock| now >= unlock)
+}
+
+/// Number of puzzles of the given year that are unlocked at the given point in time.
+pub fn unlocked_days(year: i32, now: DateTime<Utc>) -> u8 {
+    (1..=DAYS)
+        .take_while(|day| is_unlocked(year, *day, now))
+        .count() as u8
+}
+
+/// Get the next puzzle (year and day) to unlock after the given point in time together with its
+/// unlock time.
+pub fn next_unlock(now: DateTime<Utc>) -> Option<(i32, u8, DateTime<Utc>)> {
+    let year = now.with_timezone(&eastern()).year();
+
+    let (year, day) = match unlocked_days(year, now) {
+        DAYS => (year.checked_add(1)?, 1),
+        unlocked => (year, unlocked + 1),
+    };
+
+    Some((year, day, unlock_time(year, day)?))
+}
+
+/// Get the point in time when the event of the given year is over, i.e., when December ends in
+/// the time zone of Advent of Code. Years that cannot be represented have no end.
+pub fn event_end(year: i32) -> Option<DateTime<Utc>> {
+    eastern()
+        .with_ymd_and_hms(year.checked_add(1)?, 1, 1, 0, 0, 0)
+        .single()
+        .map(|end| end.with_timezone(&Utc))
+}
+
+/// Whether the event of the given year is over.
+pub fn event_ended(year: i32) -> bool {
+    event_end(year).is_some_and(|end| Utc::now() >= end)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        let unlock = unlock_time(2023, 1).unwrap();
+
+        assert_eq!(unlock, Utc.with_ymd_and_hms(2023, 12, 1, 5, 0, 0).unwrap());
+        assert!(is_unlocked(2023, 1, unlock));
+        assert!(!is_unlocked(2023, 1, unlock - Duration::seconds(1)));
+        assert!(!is_unlocked(2023, 26, unlock + Duration::days(100)));
+    }
+
+    #[test]
+    fn test_unlocked_days() {
+        assert_eq!(
+            unlocked_days(2023, unlock_time(2023, 1).unwrap() - Duration::hours(1)),
+            0
+        );
+        assert_eq!(
+            unlocked_days(2023, unlock_time(2023, 5).unwrap() + Duration::hours(1)),
+            5
+        );
+        assert_eq!(unlocked_days(2023, event_end(2023).unwrap()), 25);
+    }
+
+    #[test]
+    fn test_next_unlock() {
+        let (year, day, at) =
+            next_unlock(unlock_time(2023, 5).unwrap() + Duration::hours(1)).unwrap();
+        assert_eq!((year, day, at), (2023, 6, unlock_time(2023, 6).unwrap()));
+
+        let (year, day, _) =
+            next_unlock(Utc.with_ymd_and_hms(2023, 7, 1, 0, 0, 0).unwrap()).unwrap();
+        assert_eq!((year, day), (2023, 1));
+
+        let (year, day, _) = next_unlock(unlock_time(2023, 25).unwrap()).unwrap();
+        assert_eq!((year, day), (2024, 1));
+
+        // still the previous year in EST
+        let (year, day, _) =
+            next_unlock(Utc.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap()).unwrap();
+        assert_eq!((year, day), (2024, 1));
+    }
+
+    #[test]
+    fn test_unrepresentable_years() {
+        let now = Utc::now();
+
+        assert_eq!(unlock_time(999_999, 1), None);
+        assert_eq!(unlock_time(2023, 0), None);
+        assert!(!is_unlocked(999_999, 1, now));
+        assert_eq!(unlocked_days(999_999, now), 0);
+        assert_eq!(event_end(i32::MAX), None);
+        assert!(!event_ended(999_999));
+    }
+}
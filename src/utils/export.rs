@@ -0,0 +1,216 @@
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{calendar::DAYS, leaderboard::Leaderboard, scoring::LocalScore};
+
+/// Leaderboard in the format offered for download.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardExport {
+    pub year: i32,
+    /// Point in time (RFC 3339) of the snapshot the export is based on.
+    pub fetched_at: String,
+    pub members: Vec<MemberExport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberExport {
+    pub rank: usize,
+    pub member_id: u64,
+    pub name: String,
+    /// User of this site the member is linked to.
+    pub username: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    pub days: Vec<DayExport>,
+}
+
+/// Points in time (RFC 3339) when the stars of a day were collected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayExport {
+    pub day: u8,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl LeaderboardExport {
+    /// Create an export of a leaderboard. `username` looks up the user linked to a member ID.
+    pub fn new<F>(
+        year: i32,
+        fetched_at: String,
+        leaderboard: &Leaderboard,
+        username: F,
+    ) -> LeaderboardExport
+    where
+        F: Fn(u64) -> Option<String>,
+    {
+        let timestamp = |timestamp: Option<i64>| {
+            timestamp
+                .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+                .map(|time| time.to_rfc3339())
+        };
+
+        let members = leaderboard
+            .standings(&LocalScore)
+            .into_iter()
+            .filter_map(|standing| {
+                let member = leaderboard
+                    .members
+                    .values()
+                    .find(|member| member.id == standing.member_id)?;
+
+                Some(MemberExport {
+                    rank: standing.rank,
+                    member_id: standing.member_id,
+                    name: standing.name,
+                    username: username(standing.member_id),
+                    local_score: member.local_score,
+                    stars: member.stars,
+                    days: (1..=DAYS)
+                        .map(|day| DayExport {
+                            day,
+                            part_one: timestamp(member.star(day, 1)),
+                            part_two: timestamp(member.star(day, 2)),
+                        })
+                        .collect(),
+                })
+            })
+            .collect();
+
+        LeaderboardExport {
+            year,
+            fetched_at,
+            members,
+        }
+    }
+
+    /// Render the export as CSV with one row per member and one column per star.
+    pub fn to_csv(&self) -> String {
+        let mut header = [
+            "rank",
+            "member_id",
+            "name",
+            "username",
+            "local_score",
+            "stars",
+        ]
+        .map(String::from)
+        .to_vec();
+        for day in 1..=DAYS {
+            header.push(format!("day{day:02}_part1"));
+            header.push(format!("day{day:02}_part2"));
+        }
+
+        let mut csv = csv_row(&header);
+
+        for member in &self.members {
+            let mut row = vec![
+                member.rank.to_string(),
+                member.member_id.to_string(),
+                member.name.clone(),
+                member.username.clone().unwrap_or_default(),
+                member.local_score.to_string(),
+                member.stars.to_string(),
+            ];
+            for day in &member.days {
+                row.push(day.part_one.clone().unwrap_or_default());
+                row.push(day.part_two.clone().unwrap_or_default());
+            }
+
+            csv.push_str(&csv_row(&row));
+        }
+
+        csv
+    }
+}
+
+/// Join the fields to a CSV row (RFC 4180), quoting them where necessary.
+///
+/// Fields starting with a character spreadsheets treat as the start of a formula are prefixed
+/// with `'`, so names like `=HYPERLINK(...)` are shown as text instead of being evaluated.
+fn csv_row(fields: &[String]) -> String {
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+                format!("'{field}")
+            } else {
+                field.clone()
+            };
+
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>();
+
+    format!("{}\r\n", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEADERBOARD: &str = r#"{
+        "owner_id": 1,
+        "event": "2023",
+        "members": {
+            "1": {
+                "id": 1,
+                "name": "Smith, \"Agent\"",
+                "stars": 1,
+                "local_score": 2,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1701410400, "star_index": 1 } }
+                }
+            },
+            "2": { "id": 2, "name": null, "stars": 0, "local_score": 0 }
+        }
+    }"#;
+
+    #[test]
+    fn test_export() {
+        let leaderboard = Leaderboard::from_json(LEADERBOARD).unwrap();
+        let export = LeaderboardExport::new(2023, "now".to_string(), &leaderboard, |id| {
+            (id == 1).then(|| "agent".to_string())
+        });
+
+        assert_eq!(export.members.len(), 2);
+        assert_eq!(export.members[0].username.as_deref(), Some("agent"));
+        assert_eq!(export.members[0].days.len(), 25);
+        assert_eq!(
+            export.members[0].days[0].part_one.as_deref(),
+            Some("2023-12-01T06:00:00+00:00")
+        );
+        assert_eq!(export.members[0].days[0].part_two, None);
+    }
+
+    #[test]
+    fn test_csv() {
+        let leaderboard = Leaderboard::from_json(LEADERBOARD).unwrap();
+        let csv = LeaderboardExport::new(2023, "now".to_string(), &leaderboard, |_| None).to_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("rank,member_id,name,username,local_score,stars,day01_part1"));
+        assert_eq!(lines[0].split(',').count(), 56);
+        assert!(
+            lines[1].starts_with("1,1,\"Smith, \"\"Agent\"\"\",,2,1,2023-12-01T06:00:00+00:00,,")
+        );
+    }
+
+    #[test]
+    fn test_csv_formulas() {
+        let fields = ["=1+1", "+49 123", "-2", "@SUM(A1)", "agent", "a=b"].map(String::from);
+
+        assert_eq!(
+            csv_row(&fields),
+            "'=1+1,'+49 123,'-2,'@SUM(A1),agent,a=b\r\n"
+        );
+        assert_eq!(
+            csv_row(&["=HYPERLINK(\"x\", \"y\")".to_string()]),
+            "\"'=HYPERLINK(\"\"x\"\", \"\"y\"\")\"\r\n"
+        );
+    }
+}
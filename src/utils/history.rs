@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{calendar::unlock_time, leaderboard::Leaderboard, scoring::LocalScore};
+
+/// Score and rank of a member at the end of each day of the event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistorySeries {
+    pub member_id: u64,
+    pub name: String,
+    /// Score at the end of each day, if the member was part of the leaderboard at that time.
+    pub scores: Vec<Option<i64>>,
+    pub ranks: Vec<Option<usize>>,
+}
+
+/// Compute the history of all members from the snapshots of a year.
+///
+/// For every day, the last snapshot taken before the next puzzle unlocked is used. Days that
+/// have not been covered by a snapshot (e.g. days in the future) are cut off.
+pub fn history(year: i32, snapshots: &[(DateTime<Utc>, Leaderboard)]) -> Vec<HistorySeries> {
+    let newest = snapshots.iter().map(|(fetched_at, _)| *fetched_at).max();
+
+    let standings = (1..=25)
+        .map(|day| {
+            let unlock = unlock_time(year, day)?;
+            if newest.map_or(true, |newest| newest < unlock) {
+                return None;
+            }
+
+            let end = match day {
+                25 => unlock + chrono::Duration::days(1),
+                day => unlock_time(year, day + 1)?,
+            };
+
+            snapshots
+                .iter()
+                .filter(|(fetched_at, _)| *fetched_at < end)
+                .max_by_key(|(fetched_at, _)| *fetched_at)
+                .map(|(_, leaderboard)| leaderboard.standings(&LocalScore))
+        })
+        .collect::<Vec<_>>();
+
+    let days = standings
+        .iter()
+        .rposition(Option::is_some)
+        .map(|last| last + 1)
+        .unwrap_or_default();
+
+    let mut series = standings
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|standing| (standing.member_id, standing.name.clone()))
+        .collect::<Vec<_>>();
+    series.sort();
+    series.dedup_by_key(|(id, _)| *id);
+
+    series
+        .into_iter()
+        .map(|(member_id, name)| {
+            let of_day = |day: usize| {
+                standings[day]
+                    .as_ref()
+                    .and_then(|standings| standings.iter().find(|s| s.member_id == member_id))
+            };
+
+            HistorySeries {
+                member_id,
+                name,
+                scores: (0..days)
+                    .map(|day| of_day(day).and_then(|standing| standing.score))
+                    .collect(),
+                ranks: (0..days)
+                    .map(|day| of_day(day).map(|standing| standing.rank))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::utils::leaderboard::Member;
+
+    fn leaderboard(scores: &[(u64, u64)]) -> Leaderboard {
+        Leaderboard {
+            owner_id: 1,
+            event: "2023".to_string(),
+            members: scores
+                .iter()
+                .map(|(id, local_score)| {
+                    (
+                        id.to_string(),
+                        Member {
+                            id: *id,
+                            name: Some(format!("member {id}")),
+                            local_score: *local_score,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_history() {
+        let day =
+            |day: u8, hours: i64| unlock_time(2023, day).unwrap() + chrono::Duration::hours(hours);
+
+        let snapshots = vec![
+            (day(1, 1), leaderboard(&[(1, 2), (2, 1)])),
+            (day(1, 20), leaderboard(&[(1, 2), (2, 4)])),
+            (day(2, 1), leaderboard(&[(1, 6), (2, 4), (3, 1)])),
+        ];
+
+        let history = history(2023, &snapshots);
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].scores, vec![Some(2), Some(6)]);
+        assert_eq!(history[0].ranks, vec![Some(2), Some(1)]);
+        assert_eq!(history[1].scores, vec![Some(4), Some(4)]);
+        assert_eq!(history[2].scores, vec![None, Some(1)]);
+        assert_eq!(history[2].ranks, vec![None, Some(3)]);
+    }
+}
@@ -0,0 +1,75 @@
+/// Number of cells per row and column of an identicon. Odd, so there is a middle column.
+const GRID: usize = 5;
+
+/// Hash a string with 64-bit FNV-1a, which is stable across platforms and releases.
+fn fnv1a(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Compute which cells of the identicon of the given hash are filled. The pattern is mirrored
+/// along the vertical axis.
+fn cells(hash: u64) -> [[bool; GRID]; GRID] {
+    let mut cells = [[false; GRID]; GRID];
+    let half = GRID / 2 + 1;
+
+    for (row, columns) in cells.iter_mut().enumerate() {
+        for column in 0..half {
+            let filled = (hash >> (row * half + column)) & 1 == 1;
+            columns[column] = filled;
+            columns[GRID - 1 - column] = filled;
+        }
+    }
+
+    cells
+}
+
+/// Generate a deterministic identicon for the given seed (e.g., a username) as SVG.
+pub fn identicon(seed: &str) -> String {
+    let hash = fnv1a(seed);
+    let hue = (hash >> 48) % 360;
+
+    let rects = cells(hash)
+        .iter()
+        .enumerate()
+        .flat_map(|(row, columns)| {
+            columns
+                .iter()
+                .enumerate()
+                .filter(|(_, filled)| **filled)
+                .map(move |(column, _)| {
+                    format!(
+                        r#"<rect x="{x}" y="{y}" width="1" height="1"/>"#,
+                        x = column + 1,
+                        y = row + 1
+                    )
+                })
+        })
+        .collect::<String>();
+
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {size} {size}"><rect width="{size}" height="{size}" fill="hsl({hue}, 20%, 92%)"/><g fill="hsl({hue}, 60%, 45%)" shape-rendering="crispEdges">{rects}</g></svg>"#,
+        size = GRID + 2
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identicon_is_deterministic() {
+        assert_eq!(identicon("agent"), identicon("agent"));
+        assert_ne!(identicon("agent"), identicon("smith"));
+    }
+
+    #[test]
+    fn test_cells_are_symmetric() {
+        let cells = cells(fnv1a("agent"));
+
+        for row in cells {
+            assert!(row.iter().eq(row.iter().rev()));
+        }
+    }
+}
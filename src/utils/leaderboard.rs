@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::scoring::Scoring;
+
+/// Private leaderboard in the JSON format served by Advent of Code.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    #[serde(default)]
+    pub global_score: u64,
+    #[serde(default)]
+    pub last_star_ts: i64,
+    /// Stars per day (`"1"` to `"25"`) and part (`"1"` or `"2"`).
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    /// Name of the member as displayed by Advent of Code.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Timestamp of the star for the given day and part, if it was collected.
+    pub fn star(&self, day: u8, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.to_string())
+            .and_then(|parts| parts.get(&part.to_string()))
+            .map(|star| star.get_star_ts)
+    }
+
+    /// Number of stars (0, 1 or 2) collected for the given day.
+    pub fn stars_of_day(&self, day: u8) -> u8 {
+        (1..=2)
+            .filter(|part| self.star(day, *part).is_some())
+            .count() as u8
+    }
+}
+
+/// Position of a member within a leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(utoipa::ToSchema))]
+pub struct Standing {
+    pub rank: usize,
+    pub member_id: u64,
+    pub name: String,
+    /// User of this site the member is linked to.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Score according to the selected scoring mode, if the member could be scored.
+    pub score: Option<i64>,
+    /// Human readable representation of the score.
+    pub score_label: String,
+    pub stars: u32,
+    /// Number of stars for each of the 25 days.
+    pub days: Vec<u8>,
+}
+
+impl Leaderboard {
+    /// Parse a leaderboard from the JSON served by Advent of Code.
+    pub fn from_json(json: &str) -> Result<Leaderboard, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Year of the event this leaderboard belongs to.
+    pub fn year(&self) -> Option<i32> {
+        self.event.parse().ok()
+    }
+
+    /// Compute the standings of this leaderboard, ordered by the given scoring. Members without a
+    /// score are ranked last. Ties are broken by the number of stars and then by who got their
+    /// last star first.
+    pub fn standings(&self, scoring: &dyn Scoring) -> Vec<Standing> {
+        let scores = scoring.scores(self);
+        let mut members = self.members.values().collect::<Vec<_>>();
+
+        members.sort_by(|a, b| {
+            let by_score = match (scores.get(&a.id), scores.get(&b.id)) {
+                (Some(a), Some(b)) if scoring.ascending() => a.cmp(b),
+                (Some(a), Some(b)) => b.cmp(a),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            };
+
+            by_score
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+
+        members
+            .into_iter()
+            .enumerate()
+            .map(|(index, member)| Standing {
+                rank: index + 1,
+                member_id: member.id,
+                name: member.display_name(),
+                username: None,
+                score: scores.get(&member.id).copied(),
+                score_label: scores
+                    .get(&member.id)
+                    .map(|score| scoring.format(*score))
+                    .unwrap_or_else(|| "-".to_string()),
+                stars: member.stars,
+                days: (1..=25).map(|day| member.stars_of_day(day)).collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::scoring::{LocalScore, PartTwoDelta};
+
+    const LEADERBOARD: &str = r#"{
+        "owner_id": 1,
+        "event": "2023",
+        "members": {
+            "1": {
+                "id": 1,
+                "name": "alice",
+                "stars": 3,
+                "local_score": 10,
+                "global_score": 0,
+                "last_star_ts": 1701500000,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701410000, "star_index": 10 },
+                        "2": { "get_star_ts": 1701411000, "star_index": 20 }
+                    },
+                    "2": {
+                        "1": { "get_star_ts": 1701500000, "star_index": 30 }
+                    }
+                }
+            },
+            "2": {
+                "id": 2,
+                "name": null,
+                "stars": 1,
+                "local_score": 3,
+                "global_score": 0,
+                "last_star_ts": 1701420000,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701420000, "star_index": 15 }
+                    }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_leaderboard() {
+        let leaderboard = Leaderboard::from_json(LEADERBOARD).unwrap();
+
+        assert_eq!(leaderboard.year(), Some(2023));
+        assert_eq!(leaderboard.members.len(), 2);
+        assert_eq!(leaderboard.members["1"].star(1, 2), Some(1701411000));
+        assert_eq!(leaderboard.members["2"].star(1, 2), None);
+    }
+
+    #[test]
+    fn test_standings() {
+        let standings = Leaderboard::from_json(LEADERBOARD)
+            .unwrap()
+            .standings(&LocalScore);
+
+        assert_eq!(standings[0].name, "alice");
+        assert_eq!(standings[0].rank, 1);
+        assert_eq!(standings[0].score, Some(10));
+        assert_eq!(&standings[0].days[..3], &[2, 1, 0]);
+        assert_eq!(standings[1].name, "(anonymous user #2)");
+        assert_eq!(standings[1].rank, 2);
+    }
+
+    #[test]
+    fn test_standings_without_score() {
+        let standings = Leaderboard::from_json(LEADERBOARD)
+            .unwrap()
+            .standings(&PartTwoDelta);
+
+        assert_eq!(standings[0].score, Some(1000));
+        assert_eq!(standings[1].score, None);
+        assert_eq!(standings[1].score_label, "-");
+    }
+}
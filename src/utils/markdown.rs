@@ -0,0 +1,97 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+const SAFE_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// Check, whether a link destination is either relative or uses a safe scheme.
+fn is_safe_link(destination: &str) -> bool {
+    let destination = destination.trim().to_lowercase();
+
+    let Some((scheme, _)) = destination.split_once(':') else {
+        return true;
+    };
+
+    // a colon after a path, query or fragment delimiter does not introduce a scheme
+    if scheme.contains(['/', '?', '#']) {
+        return true;
+    }
+
+    SAFE_SCHEMES.contains(&scheme)
+}
+
+fn sanitize_link<'a>(destination: CowStr<'a>) -> CowStr<'a> {
+    if is_safe_link(&destination) {
+        destination
+    } else {
+        CowStr::Borrowed("#")
+    }
+}
+
+/// Render Markdown to HTML.
+///
+/// Raw HTML within the Markdown is escaped and links with unsafe schemes (e.g., `javascript:`)
+/// are neutralized. Code fences are rendered as `<pre><code class="language-*">`, so the client
+/// side highlighter picks them up like any other solution code.
+pub fn render_markdown(markdown: &str) -> String {
+    let parser = Parser::new_ext(
+        markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS,
+    )
+    .map(|event| match event {
+        Event::Html(raw) => Event::Text(raw),
+        Event::Start(Tag::Link(kind, destination, title)) => {
+            Event::Start(Tag::Link(kind, sanitize_link(destination), title))
+        }
+        Event::End(Tag::Link(kind, destination, title)) => {
+            Event::End(Tag::Link(kind, sanitize_link(destination), title))
+        }
+        Event::Start(Tag::Image(kind, destination, title)) => {
+            Event::Start(Tag::Image(kind, sanitize_link(destination), title))
+        }
+        Event::End(Tag::Image(kind, destination, title)) => {
+            Event::End(Tag::Image(kind, sanitize_link(destination), title))
+        }
+        event => event,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        assert_eq!(
+            render_markdown("# Day 1\n\nSome *text*"),
+            "<h1>Day 1</h1>\n<p>Some <em>text</em></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_escapes_html() {
+        let output = render_markdown("<script>alert(1)</script>\n\nand <b>inline</b>");
+
+        assert!(!output.contains("<script>"));
+        assert!(!output.contains("<b>"));
+        assert!(output.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn test_render_markdown_neutralizes_unsafe_links() {
+        let output = render_markdown("[click](javascript:alert(1)) [ok](https://adventofcode.com)");
+
+        assert!(!output.contains("javascript:"));
+        assert!(output.contains(r#"href="https://adventofcode.com""#));
+    }
+
+    #[test]
+    fn test_render_markdown_code_fence_language() {
+        let output = render_markdown("```rust\nfn main() {}\n```");
+
+        assert!(output.contains(r#"<code class="language-rust">"#));
+    }
+}
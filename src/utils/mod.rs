@@ -1,8 +1,21 @@
+pub mod api;
+pub mod avatar;
+pub mod calendar;
+pub mod export;
+pub mod history;
+pub mod identicon;
+pub mod leaderboard;
+pub mod scoring;
+pub mod stats;
+pub mod teams;
+
 use cfg_if::cfg_if;
 
 cfg_if! {
 if #[cfg(feature = "ssr")] {
+    pub mod markdown;
     pub mod password;
+    pub mod token;
 
 }
 }
@@ -0,0 +1,429 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    calendar::{unlock_time, DAYS},
+    leaderboard::{Leaderboard, Member},
+};
+
+/// Hours after the unlock until which all solves count the same in [`MorningFriendly`]. The
+/// clock effectively starts at 18:00 CET instead of 06:00 CET.
+pub const MORNING_FRIENDLY_GRACE_HOURS: i64 = 12;
+
+/// Strategy for scoring the members of a leaderboard.
+pub trait Scoring {
+    /// Scores of all members that can be scored, by member ID. Members without a score are
+    /// ranked last.
+    fn scores(&self, leaderboard: &Leaderboard) -> HashMap<u64, i64>;
+
+    /// Whether lower scores are better.
+    fn ascending(&self) -> bool {
+        false
+    }
+
+    /// Human readable representation of a score.
+    fn format(&self, score: i64) -> String {
+        score.to_string()
+    }
+}
+
+/// Scoring modes selectable on the leaderboard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScoringMode {
+    #[default]
+    LocalScore,
+    Stars,
+    MedianSolveTime,
+    PartTwoDelta,
+    MorningFriendly,
+}
+
+impl ScoringMode {
+    pub const ALL: [ScoringMode; 5] = [
+        ScoringMode::LocalScore,
+        ScoringMode::Stars,
+        ScoringMode::MedianSolveTime,
+        ScoringMode::PartTwoDelta,
+        ScoringMode::MorningFriendly,
+    ];
+
+    /// Identifier of this mode used in URLs.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ScoringMode::LocalScore => "local",
+            ScoringMode::Stars => "stars",
+            ScoringMode::MedianSolveTime => "median",
+            ScoringMode::PartTwoDelta => "delta",
+            ScoringMode::MorningFriendly => "morning",
+        }
+    }
+
+    pub fn strategy(&self) -> Box<dyn Scoring> {
+        match self {
+            ScoringMode::LocalScore => Box::new(LocalScore),
+            ScoringMode::Stars => Box::new(StarsOnly),
+            ScoringMode::MedianSolveTime => Box::new(MedianSolveTime),
+            ScoringMode::PartTwoDelta => Box::new(PartTwoDelta),
+            ScoringMode::MorningFriendly => Box::new(MorningFriendly),
+        }
+    }
+}
+
+impl FromStr for ScoringMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ScoringMode::ALL
+            .into_iter()
+            .find(|mode| mode.key() == s)
+            .ok_or(())
+    }
+}
+
+impl Display for ScoringMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScoringMode::LocalScore => f.write_str("Local score"),
+            ScoringMode::Stars => f.write_str("Stars"),
+            ScoringMode::MedianSolveTime => f.write_str("Median time since unlock"),
+            ScoringMode::PartTwoDelta => f.write_str("Median time from part 1 to part 2"),
+            ScoringMode::MorningFriendly => {
+                f.write_str("Morning friendly (clock starts 18:00 CET)")
+            }
+        }
+    }
+}
+
+/// The official local score as reported by Advent of Code.
+pub struct LocalScore;
+
+impl Scoring for LocalScore {
+    fn scores(&self, leaderboard: &Leaderboard) -> HashMap<u64, i64> {
+        per_member(leaderboard, |member| Some(member.local_score as i64))
+    }
+}
+
+/// Number of collected stars.
+pub struct StarsOnly;
+
+impl Scoring for StarsOnly {
+    fn scores(&self, leaderboard: &Leaderboard) -> HashMap<u64, i64> {
+        per_member(leaderboard, |member| Some(member.stars as i64))
+    }
+}
+
+/// Median of the seconds between the unlock of a puzzle and getting one of its stars.
+pub struct MedianSolveTime;
+
+impl Scoring for MedianSolveTime {
+    fn scores(&self, leaderboard: &Leaderboard) -> HashMap<u64, i64> {
+        let Some(year) = leaderboard.year() else {
+            return HashMap::new();
+        };
+
+        per_member(leaderboard, |member| {
+            let times = (1..=25)
+                .flat_map(|day| (1..=2).map(move |part| (day, part)))
+                .filter_map(|(day, part)| seconds_since_unlock(member, year, day, part))
+                .collect();
+
+            median(times)
+        })
+    }
+
+    fn ascending(&self) -> bool {
+        true
+    }
+
+    fn format(&self, score: i64) -> String {
+        format_duration(score)
+    }
+}
+
+/// Median of the seconds between getting the first and the second star of a day.
+pub struct PartTwoDelta;
+
+impl Scoring for PartTwoDelta {
+    fn scores(&self, leaderboard: &Leaderboard) -> HashMap<u64, i64> {
+        let Some(year) = leaderboard.year() else {
+            return HashMap::new();
+        };
+
+        per_member(leaderboard, |member| {
+            let deltas = (1..=25)
+                .filter_map(|day| {
+                    let first = seconds_since_unlock(member, year, day, 1)?;
+                    let second = seconds_since_unlock(member, year, day, 2)?;
+
+                    (second >= first).then_some(second - first)
+                })
+                .collect();
+
+            median(deltas)
+        })
+    }
+
+    fn ascending(&self) -> bool {
+        true
+    }
+
+    fn format(&self, score: i64) -> String {
+        format_duration(score)
+    }
+}
+
+/// Local score where every star collected within [`MORNING_FRIENDLY_GRACE_HOURS`] after the
+/// unlock counts as collected at the end of that grace period, so nobody has to get up at
+/// 06:00 CET.
+pub struct MorningFriendly;
+
+impl Scoring for MorningFriendly {
+    fn scores(&self, leaderboard: &Leaderboard) -> HashMap<u64, i64> {
+        let Some(year) = leaderboard.year() else {
+            return HashMap::new();
+        };
+
+        // all stars of a day and part are compared relative to the same unlock
+        local_score(leaderboard, |member, day, part| {
+            seconds_since_unlock(member, year, day, part)
+                .map(|seconds| seconds.max(MORNING_FRIENDLY_GRACE_HOURS * 3600))
+        })
+    }
+}
+
+/// Compute the local score of all members, based on the given (effective) time of each star.
+///
+/// Like Advent of Code, the first member to get a star receives as many points as there are
+/// members, the second one point less, and so on. Members with equal times get equal points.
+pub fn local_score<F>(leaderboard: &Leaderboard, time: F) -> HashMap<u64, i64>
+where
+    F: Fn(&Member, u8, u8) -> Option<i64>,
+{
+    let members = leaderboard.members.values().collect::<Vec<_>>();
+    let mut scores = members
+        .iter()
+        .map(|member| (member.id, 0))
+        .collect::<HashMap<_, _>>();
+
+    for day in 1..=25 {
+        for part in 1..=2 {
+            let times = members
+                .iter()
+                .filter_map(|member| time(member, day, part).map(|time| (member.id, time)))
+                .collect::<Vec<_>>();
+
+            for (id, time) in &times {
+                let faster = times.iter().filter(|(_, other)| other < time).count();
+                *scores.entry(*id).or_default() += (members.len() - faster) as i64;
+            }
+        }
+    }
+
+    scores
+}
+
+/// Seconds between the unlock of a puzzle and the given star of a member. Days outside of the
+/// calendar and stars with timestamps before the unlock cannot be genuine and are ignored.
+pub fn seconds_since_unlock(member: &Member, year: i32, day: u8, part: u8) -> Option<i64> {
+    if !(1..=DAYS).contains(&day) {
+        return None;
+    }
+
+    let unlock = unlock_time(year, day)?.timestamp();
+
+    member
+        .star(day, part)
+        .and_then(|timestamp| timestamp.checked_sub(unlock))
+        .filter(|seconds| *seconds >= 0)
+}
+
+/// Score each member on its own.
+fn per_member<F>(leaderboard: &Leaderboard, score: F) -> HashMap<u64, i64>
+where
+    F: Fn(&Member) -> Option<i64>,
+{
+    leaderboard
+        .members
+        .values()
+        .filter_map(|member| score(member).map(|score| (member.id, score)))
+        .collect()
+}
+
+/// Median of the given values, if there are any.
+pub fn median(mut values: Vec<i64>) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_unstable();
+    let middle = values.len() / 2;
+
+    if values.len() % 2 == 0 {
+        // the sum of two values may overflow, their mean cannot
+        let sum = i128::from(values[middle - 1]) + i128::from(values[middle]);
+        Some((sum / 2) as i64)
+    } else {
+        Some(values[middle])
+    }
+}
+
+/// Format seconds as e.g. `1h 02m 03s`.
+pub fn format_duration(seconds: i64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m {seconds:02}s")
+    } else {
+        format!("{minutes}m {seconds:02}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::utils::leaderboard::Star;
+
+    /// Create a member with the given stars, given as (day, part, seconds after unlock).
+    fn member(id: u64, stars: &[(u8, u8, i64)]) -> Member {
+        let mut member = Member {
+            id,
+            name: Some(format!("member {id}")),
+            stars: stars.len() as u32,
+            ..Default::default()
+        };
+
+        for (day, part, seconds) in stars {
+            member
+                .completion_day_level
+                .entry(day.to_string())
+                .or_default()
+                .insert(
+                    part.to_string(),
+                    Star {
+                        get_star_ts: unlock_time(2023, *day).unwrap().timestamp() + seconds,
+                        star_index: 0,
+                    },
+                );
+        }
+
+        member
+    }
+
+    fn leaderboard(members: Vec<Member>) -> Leaderboard {
+        Leaderboard {
+            owner_id: 1,
+            event: "2023".to_string(),
+            members: members
+                .into_iter()
+                .map(|member| (member.id.to_string(), member))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(vec![]), None);
+        assert_eq!(median(vec![3, 1, 2]), Some(2));
+        assert_eq!(median(vec![4, 1, 2, 3]), Some(2));
+        assert_eq!(median(vec![i64::MAX, i64::MAX]), Some(i64::MAX));
+        assert_eq!(median(vec![i64::MIN, i64::MIN]), Some(i64::MIN));
+    }
+
+    #[test]
+    fn test_seconds_since_unlock() {
+        let mut solver = member(1, &[(1, 1, 60), (1, 2, -60)]);
+
+        assert_eq!(seconds_since_unlock(&solver, 2023, 1, 1), Some(60));
+        // before the unlock
+        assert_eq!(seconds_since_unlock(&solver, 2023, 1, 2), None);
+        assert_eq!(seconds_since_unlock(&solver, 2023, 2, 1), None);
+        // outside of the calendar
+        assert_eq!(seconds_since_unlock(&solver, 2023, 26, 1), None);
+
+        for timestamp in [i64::MIN, i64::MAX] {
+            solver
+                .completion_day_level
+                .get_mut("1")
+                .unwrap()
+                .get_mut("1")
+                .unwrap()
+                .get_star_ts = timestamp;
+
+            let leaderboard = leaderboard(vec![solver.clone()]);
+            for mode in ScoringMode::ALL {
+                mode.strategy().scores(&leaderboard);
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(62), "1m 02s");
+        assert_eq!(format_duration(3723), "1h 02m 03s");
+    }
+
+    #[test]
+    fn test_stars_only() {
+        let early = member(1, &[(1, 1, 10)]);
+        let both = member(2, &[(1, 1, 20), (1, 2, 30)]);
+
+        let scores = StarsOnly.scores(&leaderboard(vec![early, both]));
+
+        assert_eq!(scores[&1], 1);
+        assert_eq!(scores[&2], 2);
+    }
+
+    #[test]
+    fn test_median_solve_time() {
+        let solver = member(1, &[(1, 1, 60), (1, 2, 120), (2, 1, 600)]);
+        let idle = member(2, &[]);
+
+        let scores = MedianSolveTime.scores(&leaderboard(vec![solver, idle]));
+
+        assert_eq!(scores.get(&1), Some(&120));
+        assert_eq!(scores.get(&2), None);
+        assert!(MedianSolveTime.ascending());
+    }
+
+    #[test]
+    fn test_part_two_delta() {
+        let solver = member(1, &[(1, 1, 60), (1, 2, 120), (2, 1, 100), (2, 2, 400)]);
+        let scores = PartTwoDelta.scores(&leaderboard(vec![solver]));
+
+        assert_eq!(scores[&1], 180);
+    }
+
+    #[test]
+    fn test_local_score() {
+        let first = member(1, &[(1, 1, 10), (1, 2, 20)]);
+        let second = member(2, &[(1, 1, 15)]);
+        let leaderboard = leaderboard(vec![first, second]);
+
+        let scores = local_score(&leaderboard, |member, day, part| member.star(day, part));
+
+        assert_eq!(scores[&1], 2 + 2);
+        assert_eq!(scores[&2], 1);
+    }
+
+    #[test]
+    fn test_morning_friendly() {
+        let early_bird = member(1, &[(1, 1, 60), (1, 2, 120)]);
+        let after_lectures = member(2, &[(1, 1, 4 * 3600), (1, 2, 5 * 3600)]);
+        let late = member(3, &[(1, 1, 13 * 3600)]);
+        let scores = MorningFriendly.scores(&leaderboard(vec![early_bird, after_lectures, late]));
+
+        // both solved within the grace period and therefore tie
+        assert_eq!(scores[&1], 6);
+        assert_eq!(scores[&2], 6);
+        assert_eq!(scores[&3], 1);
+    }
+
+    #[test]
+    fn test_scoring_mode_key() {
+        for mode in ScoringMode::ALL {
+            assert_eq!(mode.key().parse(), Ok(mode));
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{
+    calendar::unlock_time,
+    leaderboard::Leaderboard,
+    scoring::{median, seconds_since_unlock},
+};
+
+/// Upper bounds (in seconds since the unlock) of the buckets of the solve time histogram. The
+/// last bucket takes everything else.
+pub const HISTOGRAM_BUCKETS: [i64; 7] = [
+    15 * 60,
+    30 * 60,
+    60 * 60,
+    2 * 60 * 60,
+    4 * 60 * 60,
+    8 * 60 * 60,
+    24 * 60 * 60,
+];
+
+/// Label of each bucket of the solve time histogram.
+pub fn histogram_labels() -> Vec<String> {
+    HISTOGRAM_BUCKETS
+        .iter()
+        .map(|bound| match bound % 3600 {
+            0 => format!("< {}h", bound / 3600),
+            _ => format!("< {}m", bound / 60),
+        })
+        .chain(std::iter::once("later".to_string()))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fastest {
+    pub name: String,
+    /// Seconds between the unlock and getting the star.
+    pub seconds: i64,
+}
+
+/// Statistics of one part of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartStats {
+    pub part: u8,
+    pub solved: u32,
+    pub fastest: Option<Fastest>,
+    /// Median of the seconds between the unlock and getting the star.
+    pub median: Option<i64>,
+    /// Number of stars per bucket of [`HISTOGRAM_BUCKETS`].
+    pub histogram: Vec<u32>,
+}
+
+/// Statistics of a single day, derived from a leaderboard snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayStats {
+    pub year: i32,
+    pub day: u8,
+    pub members: u32,
+    /// Members with only the first star of the day.
+    pub one_star: u32,
+    /// Members with both stars of the day.
+    pub two_stars: u32,
+    pub parts: Vec<PartStats>,
+}
+
+impl DayStats {
+    /// Compute the statistics of a day, if the day exists.
+    pub fn from_leaderboard(leaderboard: &Leaderboard, year: i32, day: u8) -> Option<DayStats> {
+        unlock_time(year, day)?;
+        let members = leaderboard.members.values().collect::<Vec<_>>();
+
+        let count_stars = |stars: u8| {
+            members
+                .iter()
+                .filter(|member| member.stars_of_day(day) == stars)
+                .count() as u32
+        };
+
+        let parts = (1..=2)
+            .map(|part| {
+                let times = members
+                    .iter()
+                    .filter_map(|member| {
+                        seconds_since_unlock(member, year, day, part)
+                            .map(|seconds| (member, seconds))
+                    })
+                    .collect::<Vec<_>>();
+
+                let fastest = times
+                    .iter()
+                    .min_by_key(|(member, seconds)| (*seconds, member.id))
+                    .map(|(member, seconds)| Fastest {
+                        name: member.display_name(),
+                        seconds: *seconds,
+                    });
+
+                let mut histogram = vec![0; HISTOGRAM_BUCKETS.len() + 1];
+                for (_, seconds) in &times {
+                    let bucket = HISTOGRAM_BUCKETS
+                        .iter()
+                        .position(|bound| seconds < bound)
+                        .unwrap_or(HISTOGRAM_BUCKETS.len());
+                    histogram[bucket] += 1;
+                }
+
+                PartStats {
+                    part,
+                    solved: times.len() as u32,
+                    fastest,
+                    median: median(times.iter().map(|(_, seconds)| *seconds).collect()),
+                    histogram,
+                }
+            })
+            .collect();
+
+        Some(DayStats {
+            year,
+            day,
+            members: members.len() as u32,
+            one_star: count_stars(1),
+            two_stars: count_stars(2),
+            parts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::utils::leaderboard::{Member, Star};
+
+    fn member(id: u64, stars: &[(u8, i64)]) -> Member {
+        let unlock = unlock_time(2023, 1).unwrap().timestamp();
+
+        let parts = stars
+            .iter()
+            .map(|(part, seconds)| {
+                (
+                    part.to_string(),
+                    Star {
+                        get_star_ts: unlock + seconds,
+                        star_index: 0,
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        Member {
+            id,
+            name: Some(format!("member {id}")),
+            stars: stars.len() as u32,
+            completion_day_level: HashMap::from([("1".to_string(), parts)]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_day_stats() {
+        let leaderboard = Leaderboard {
+            owner_id: 1,
+            event: "2023".to_string(),
+            members: [
+                member(1, &[(1, 600), (2, 1200)]),
+                member(2, &[(1, 7200)]),
+                member(3, &[(1, 100_000), (2, 100_100)]),
+                member(4, &[]),
+            ]
+            .into_iter()
+            .map(|member| (member.id.to_string(), member))
+            .collect(),
+        };
+
+        let stats = DayStats::from_leaderboard(&leaderboard, 2023, 1).unwrap();
+
+        assert_eq!(stats.members, 4);
+        assert_eq!(stats.one_star, 1);
+        assert_eq!(stats.two_stars, 2);
+
+        let part_one = &stats.parts[0];
+        assert_eq!(part_one.solved, 3);
+        assert_eq!(part_one.fastest.as_ref().unwrap().name, "member 1");
+        assert_eq!(part_one.median, Some(7200));
+        assert_eq!(part_one.histogram, vec![1, 0, 0, 0, 1, 0, 0, 1]);
+
+        let part_two = &stats.parts[1];
+        assert_eq!(part_two.solved, 2);
+        assert_eq!(part_two.fastest.as_ref().unwrap().seconds, 1200);
+        assert_eq!(part_two.histogram.iter().sum::<u32>(), 2);
+    }
+}
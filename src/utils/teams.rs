@@ -0,0 +1,188 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Rule for combining the scores of the members of a team into a team score. Members without a
+/// score (e.g. not linked to an Advent of Code account) count as zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Aggregation {
+    #[default]
+    Sum,
+    Average,
+    /// Sum of the best N members, so bigger teams have no advantage.
+    Best(usize),
+}
+
+impl Aggregation {
+    /// Identifier of this aggregation used in URLs.
+    pub fn key(&self) -> String {
+        match self {
+            Aggregation::Sum => "sum".to_string(),
+            Aggregation::Average => "average".to_string(),
+            Aggregation::Best(n) => format!("best-{n}"),
+        }
+    }
+
+    pub fn aggregate(&self, scores: &[i64]) -> i64 {
+        match self {
+            Aggregation::Sum => scores.iter().sum(),
+            Aggregation::Average if scores.is_empty() => 0,
+            Aggregation::Average => scores.iter().sum::<i64>() / scores.len() as i64,
+            Aggregation::Best(n) => {
+                let mut scores = scores.to_vec();
+                scores.sort_unstable_by(|a, b| b.cmp(a));
+                scores.into_iter().take(*n).sum()
+            }
+        }
+    }
+}
+
+impl FromStr for Aggregation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Aggregation::Sum),
+            "average" => Ok(Aggregation::Average),
+            _ => s
+                .strip_prefix("best-")
+                .and_then(|n| n.parse().ok())
+                .filter(|n| *n > 0)
+                .map(Aggregation::Best)
+                .ok_or(()),
+        }
+    }
+}
+
+impl From<Aggregation> for String {
+    fn from(aggregation: Aggregation) -> Self {
+        aggregation.key()
+    }
+}
+
+impl TryFrom<String> for Aggregation {
+    type Error = String;
+
+    fn try_from(key: String) -> Result<Self, Self::Error> {
+        key.parse()
+            .map_err(|_| format!("unknown aggregation '{key}'"))
+    }
+}
+
+impl Display for Aggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Aggregation::Sum => f.write_str("Sum"),
+            Aggregation::Average => f.write_str("Average"),
+            Aggregation::Best(n) => write!(f, "Best {n}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamMemberScore {
+    pub username: String,
+    pub score: Option<i64>,
+}
+
+/// Position of a team within the team leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamStanding {
+    pub rank: usize,
+    pub team: String,
+    pub name: String,
+    pub score: i64,
+    pub members: Vec<TeamMemberScore>,
+}
+
+/// Rank the given teams (ID, name and member scores) by their aggregated score.
+pub fn team_standings(
+    teams: Vec<(String, String, Vec<TeamMemberScore>)>,
+    aggregation: Aggregation,
+) -> Vec<TeamStanding> {
+    let mut standings = teams
+        .into_iter()
+        .map(|(team, name, members)| {
+            let scores = members
+                .iter()
+                .map(|member| member.score.unwrap_or_default())
+                .collect::<Vec<_>>();
+
+            TeamStanding {
+                rank: 0,
+                team,
+                name,
+                score: aggregation.aggregate(&scores),
+                members,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    standings.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+
+    for (index, standing) in standings.iter_mut().enumerate() {
+        standing.rank = index + 1;
+    }
+
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate() {
+        let scores = [10, 40, 20, 0];
+
+        assert_eq!(Aggregation::Sum.aggregate(&scores), 70);
+        assert_eq!(Aggregation::Average.aggregate(&scores), 17);
+        assert_eq!(Aggregation::Best(2).aggregate(&scores), 60);
+        assert_eq!(Aggregation::Best(10).aggregate(&scores), 70);
+        assert_eq!(Aggregation::Average.aggregate(&[]), 0);
+    }
+
+    #[test]
+    fn test_parse_aggregation() {
+        for aggregation in [Aggregation::Sum, Aggregation::Average, Aggregation::Best(3)] {
+            assert_eq!(aggregation.key().parse(), Ok(aggregation));
+        }
+
+        assert_eq!("best-0".parse::<Aggregation>(), Err(()));
+        assert_eq!("median".parse::<Aggregation>(), Err(()));
+    }
+
+    #[test]
+    fn test_team_standings() {
+        let member = |username: &str, score: Option<i64>| TeamMemberScore {
+            username: username.to_string(),
+            score,
+        };
+
+        let standings = team_standings(
+            vec![
+                (
+                    "team:a".to_string(),
+                    "Big".to_string(),
+                    vec![
+                        member("a", Some(10)),
+                        member("b", Some(10)),
+                        member("c", None),
+                    ],
+                ),
+                (
+                    "team:b".to_string(),
+                    "Small".to_string(),
+                    vec![member("d", Some(15))],
+                ),
+            ],
+            Aggregation::Average,
+        );
+
+        assert_eq!(standings[0].name, "Small");
+        assert_eq!(standings[0].rank, 1);
+        assert_eq!(standings[1].score, 6);
+        assert_eq!(standings[1].rank, 2);
+    }
+}
@@ -0,0 +1,38 @@
+use leptos::*;
+
+use crate::{
+    components::{
+        AuditLogSearch, LeaderboardAdmin, LeaderboardConfigForm, ModerationQueue, UserTable,
+    },
+    functions::{RefreshLeaderboard, Role, UploadLeaderboard},
+    hooks::use_auth,
+    utils::calendar::current_event_year,
+};
+
+#[component]
+pub fn AdminView() -> impl IntoView {
+    let auth = use_auth();
+
+    let refresh = create_server_action::<RefreshLeaderboard>();
+    let upload = create_server_action::<UploadLeaderboard>();
+
+    view! {
+        <section class="admin-view">
+            <h1>"Admin"</h1>
+            <Transition fallback=move || ()>
+                {move || auth.role.get().map(|role| match role {
+                    Ok(Some(Role::Admin)) => view! {
+                        <ModerationQueue/>
+                        <UserTable/>
+                        <h2>"Leaderboard"</h2>
+                        <LeaderboardConfigForm/>
+                        <LeaderboardAdmin year=current_event_year() refresh=refresh upload=upload/>
+                        <AuditLogSearch/>
+                    }.into_view(),
+                    Ok(Some(Role::Moderator)) => view! { <ModerationQueue/> }.into_view(),
+                    _ => view! { <p>"Only moderators and admins can access this page."</p> }.into_view(),
+                })}
+            </Transition>
+        </section>
+    }
+}
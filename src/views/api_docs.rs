@@ -0,0 +1,104 @@
+use leptos::*;
+
+use crate::functions::{get_api_docs, ApiDocs, ApiEndpoint, ApiResponse};
+
+#[component]
+fn Endpoint(endpoint: ApiEndpoint) -> impl IntoView {
+    let ApiEndpoint {
+        method,
+        path,
+        summary,
+        description,
+        parameters,
+        request_body,
+        responses,
+    } = endpoint;
+
+    view! {
+        <article class="endpoint">
+            <h3>
+                <span class=format!("method {}", method.to_lowercase())>{method}</span>
+                <code>{path}</code>
+            </h3>
+            {summary.map(|summary| view! { <p class="summary">{summary}</p> })}
+            {description.map(|description| view! { <p>{description}</p> })}
+            {(!parameters.is_empty()).then(|| view! {
+                <h4>"Parameters"</h4>
+                <ul>
+                    {parameters
+                        .into_iter()
+                        .map(|parameter| view! {
+                            <li>
+                                <code>{parameter.name}</code>
+                                " (" {parameter.location}
+                                {parameter.required.then_some(", required")} ")"
+                                {parameter.description.map(|description| format!(": {description}"))}
+                            </li>
+                        })
+                        .collect_view()}
+                </ul>
+            })}
+            {request_body.map(|body| view! {
+                <h4>"Request body"</h4>
+                <p><a href=format!("#schema-{body}")><code>{body.clone()}</code></a></p>
+            })}
+            <h4>"Responses"</h4>
+            <ul>
+                {responses
+                    .into_iter()
+                    .map(|ApiResponse { status, description, body }| view! {
+                        <li>
+                            <code>{status}</code> " " {description}
+                            {body.map(|body| {
+                                let anchor = format!("#schema-{}", body.trim_end_matches("[]"));
+                                view! { " " <a href=anchor><code>{body}</code></a> }
+                            })}
+                        </li>
+                    })
+                    .collect_view()}
+            </ul>
+        </article>
+    }
+}
+
+/// Documentation of the REST API, rendered from its OpenAPI document so it works without any
+/// external resources.
+#[component]
+pub fn ApiDocsView() -> impl IntoView {
+    let docs = create_resource(|| (), |_| get_api_docs());
+
+    view! {
+        <section class="api-docs-view">
+            <Transition fallback=move || ()>
+                {move || docs.get().map(|docs| match docs {
+                    Ok(ApiDocs { title, version, description, endpoints, schemas }) => view! {
+                        <h1>{title}</h1>
+                        <p class="version">"Version " {version}</p>
+                        {description.map(|description| view! { <p>{description}</p> })}
+                        <p>
+                            "The machine-readable OpenAPI document is available at "
+                            <a href="/api/v1/openapi.json" rel="external"><code>"/api/v1/openapi.json"</code></a>
+                            "."
+                        </p>
+                        <h2>"Endpoints"</h2>
+                        {endpoints
+                            .into_iter()
+                            .map(|endpoint| view! { <Endpoint endpoint=endpoint/> })
+                            .collect_view()}
+                        <h2>"Schemas"</h2>
+                        {schemas
+                            .into_iter()
+                            .map(|schema| view! {
+                                <article class="schema" id=format!("schema-{}", schema.name)>
+                                    <h3><code>{schema.name}</code></h3>
+                                    <pre>{schema.definition}</pre>
+                                </article>
+                            })
+                            .collect_view()}
+                    }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the API documentation"</p> }.into_view(),
+                })}
+            </Transition>
+        </section>
+    }
+}
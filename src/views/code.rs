@@ -1,44 +1,90 @@
 use leptos::*;
 use leptos_router::*;
 
-use crate::{app::CODE, components::CodeSidebar};
+use crate::{
+    components::{CodeSidebar, SolutionSnippet, SubmitSolutionForm},
+    functions::{get_solutions, get_star_status, SubmitSolution, ToggleReaction, VoteSolution},
+    hooks::{use_auth, use_selected_day},
+};
 
 #[component]
 pub fn CodeView() -> impl IntoView {
+    let auth = use_auth();
     let query = use_params_map();
     let user = move || query.with(|params| params.get("user").cloned().unwrap_or_default());
+    let (year, day) = use_selected_day();
+
+    let react = create_server_action::<ToggleReaction>();
+    let vote = create_server_action::<VoteSolution>();
+    let submit = create_server_action::<SubmitSolution>();
+
+    let solutions = create_resource(
+        move || {
+            (
+                user(),
+                year(),
+                day(),
+                react.version().get(),
+                vote.version().get(),
+                submit.version().get(),
+            )
+        },
+        |(user, year, day, ..)| get_solutions(user, year, day),
+    );
+
+    let stars = create_resource(
+        move || (user(), year(), day()),
+        |(user, year, day)| get_star_status(user, year, day),
+    );
+
+    let version = Signal::derive(move || {
+        react.version().get() + vote.version().get() + submit.version().get()
+    });
+
+    let is_own_page = move || matches!(auth.user.get(), Some(Ok(Some(name))) if name == user());
 
     view! {
-        <CodeSidebar />
+        <CodeSidebar version=version />
         <Show when=move || user().trim() != "" fallback=move || view! { <section>Select a user...</section>}>
-            {Prism::highlight_all()}
             <section class="code-overview">
-                <ul>
-                    <li>
-                        <div class="code-snippet">
-                            <details open>
-                                <summary>
-                                    {user} Part 1
-                                </summary>
-                                <pre>
-                                    <code class="language-rust">{CODE.trim()}</code>
-                                </pre>
-                            </details>
-                        </div>
-                    </li>
-                    <li>
-                        <div class="code-snippet">
-                            <details>
-                                <summary>
-                                    {user} Part 2
-                                </summary>
-                                <pre>
-                                    <code class="language-rust">{CODE.trim()}</code>
-                                </pre>
-                            </details>
-                        </div>
-                    </li>
-                </ul>
+                <Transition fallback=move || ()>
+                    <a
+                        class="download"
+                        href=move || format!("/download/{year}/{user}", year = year(), user = user())
+                        rel="external"
+                    >
+                        "Download all solutions of " {user} " for " {move || year()}
+                    </a>
+                    {move || stars.get().and_then(|stars| stars.ok().flatten()).map(|stars| view! {
+                        <p class="star-status">
+                            {user} " collected " {stars} " of 2 stars on day " {day()}
+                            <span class="stars">{"*".repeat(stars as usize)}</span>
+                        </p>
+                    })}
+                    <Show when=is_own_page fallback=|| ()>
+                        <SubmitSolutionForm action=submit year=year day=day />
+                    </Show>
+                    {move || solutions.get().map(|solutions| match solutions {
+                        Ok(solutions) if !solutions.is_empty() => {
+                            Prism::highlight_all();
+                            view! {
+                                <ul>
+                                    {solutions
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(index, solution)| view! {
+                                            <li>
+                                                <SolutionSnippet solution=solution react=react vote=vote open={index == 0} />
+                                            </li>
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }.into_view()
+                        }
+                        Ok(_) => view! { <p>"No solutions for this day yet"</p> }.into_view(),
+                        Err(_) => view! { <p>"Failed to load solutions"</p> }.into_view(),
+                    })}
+                </Transition>
             </section>
         </Show>
     }
@@ -0,0 +1,61 @@
+use leptos::*;
+use leptos_router::*;
+
+use crate::{
+    functions::{get_hall_of_fame, HallOfFameEntry},
+    utils::calendar::current_event_year,
+};
+
+#[component]
+pub fn HallOfFameView() -> impl IntoView {
+    let params = use_params_map();
+    let year = move || {
+        params.with(|params| {
+            params
+                .get("year")
+                .and_then(|year| year.parse().ok())
+                .unwrap_or_else(current_event_year)
+        })
+    };
+
+    let entries = create_resource(year, get_hall_of_fame);
+
+    view! {
+        <section class="hall-of-fame">
+            <h1>"Hall of Fame " {year}</h1>
+            <Transition fallback=move || ()>
+                {move || entries.get().map(|entries| match entries {
+                    Ok(entries) if !entries.is_empty() => view! {
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>"Day"</th>
+                                    <th>"Solution"</th>
+                                    <th>"Votes"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {entries
+                                    .into_iter()
+                                    .map(|HallOfFameEntry { day, part, author, votes, .. }| {
+                                        let link = format!("/code/{author}?year={year}&day={day}", year = year());
+
+                                        view! {
+                                            <tr>
+                                                <td>{day}</td>
+                                                <td><a href=link>{author} " Part " {part}</a></td>
+                                                <td>{votes}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    }.into_view(),
+                    Ok(_) => view! { <p>"No votes for this year yet"</p> }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the hall of fame"</p> }.into_view(),
+                })}
+            </Transition>
+        </section>
+    }
+}
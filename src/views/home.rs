@@ -1,10 +1,103 @@
 use leptos::*;
+use leptos_router::*;
+
+use crate::{
+    components::{Countdown, ExportLinks, HistoryChart, LeaderboardAdmin, StandingsTable},
+    functions::{
+        get_leaderboard, get_leaderboard_history, HistoryData, LeaderboardData, RefreshLeaderboard,
+        UploadLeaderboard,
+    },
+    utils::{calendar::current_event_year, scoring::ScoringMode},
+};
 
 #[component]
 pub fn HomeView() -> impl IntoView {
+    let query = use_query_map();
+    let year = move || {
+        query.with(|query| {
+            query
+                .get("year")
+                .and_then(|year| year.parse().ok())
+                .unwrap_or_else(current_event_year)
+        })
+    };
+
+    let scoring = move || {
+        query.with(|query| {
+            query
+                .get("scoring")
+                .and_then(|scoring| scoring.parse().ok())
+                .unwrap_or_default()
+        })
+    };
+
+    let refresh = create_server_action::<RefreshLeaderboard>();
+    let upload = create_server_action::<UploadLeaderboard>();
+
+    let leaderboard = create_resource(
+        move || {
+            (
+                year(),
+                scoring(),
+                refresh.version().get(),
+                upload.version().get(),
+            )
+        },
+        |(year, scoring, ..)| get_leaderboard(year, scoring),
+    );
+
+    let history = create_resource(
+        move || (year(), refresh.version().get(), upload.version().get()),
+        |(year, ..)| get_leaderboard_history(year),
+    );
+
     view! {
-        <section>
+        <section class="leaderboard">
             <h1>Advent of Code</h1>
+            <Countdown/>
+            <Form method="GET" action="">
+                <input type="hidden" name="year" value=year/>
+                <label for="scoring">"Scoring"</label>
+                <select id="scoring" name="scoring">
+                    {ScoringMode::ALL
+                        .into_iter()
+                        .map(|mode| view! {
+                            <option value=mode.key() selected=move || scoring() == mode>
+                                {mode.to_string()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+                <input type="submit" value="Show"/>
+            </Form>
+            <Transition fallback=move || ()>
+                {move || leaderboard.get().map(|leaderboard| match leaderboard {
+                    Ok(LeaderboardData { year, fetched_at, standings, can_manage }) => view! {
+                        <h2>"Leaderboard " {year}</h2>
+                        {can_manage.then(|| view! { <LeaderboardAdmin year=year refresh=refresh upload=upload/> })}
+                        {fetched_at.map(|fetched_at| view! { <p class="fetched-at">"Last updated: " {fetched_at}</p> })}
+                        {if standings.is_empty() {
+                            view! { <p>"No leaderboard data for this year yet."</p> }.into_view()
+                        } else {
+                            view! {
+                                <StandingsTable year=year standings=standings scoring=scoring()/>
+                                <ExportLinks year=year/>
+                            }.into_view()
+                        }}
+                    }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the leaderboard"</p> }.into_view(),
+                })}
+            </Transition>
+            <Transition fallback=move || ()>
+                {move || history.get().map(|history| match history {
+                    Ok(HistoryData { series, own_member }) if !series.is_empty() => view! {
+                        <h2>"History"</h2>
+                        <HistoryChart series=series highlight=own_member/>
+                    }.into_view(),
+                    Ok(_) => ().into_view(),
+                    Err(_) => view! { <p>"Failed to load the leaderboard history"</p> }.into_view(),
+                })}
+            </Transition>
         </section>
     }
 }
@@ -0,0 +1,135 @@
+use leptos::*;
+use leptos_router::*;
+
+use crate::{
+    components::{ExportLinks, StandingsTable},
+    functions::{
+        get_archive_year, get_archive_years, get_leaderboard, ArchiveResult, ArchiveYearData,
+        LeaderboardData, SetYearFrozen, YearSummary,
+    },
+    utils::scoring::ScoringMode,
+};
+
+/// Index of all past events.
+#[component]
+pub fn LastYearsView() -> impl IntoView {
+    let years = create_resource(|| (), |_| get_archive_years());
+
+    view! {
+        <section class="last-years">
+            <h1>"Last Years"</h1>
+            <Transition fallback=move || ()>
+                {move || years.get().map(|years| match years {
+                    Ok(years) if !years.is_empty() => view! {
+                        <ul class="years">
+                            {years
+                                .into_iter()
+                                .map(|YearSummary { year, frozen, solutions, participants, .. }| view! {
+                                    <li>
+                                        <a href=format!("/last-years/{year}")>{year}</a>
+                                        {frozen.then(|| view! { <span class="badge" title="Archived">"🔒"</span> })}
+                                        <span class="details">
+                                            {solutions} " solutions"
+                                            {participants.map(|participants| format!(", {participants} participants"))}
+                                        </span>
+                                    </li>
+                                })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                    Ok(_) => view! { <p>"No past events yet."</p> }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the past events"</p> }.into_view(),
+                })}
+            </Transition>
+        </section>
+    }
+}
+
+/// Archive of a single year with its final leaderboard, solutions and statistics.
+#[component]
+pub fn LastYearView() -> impl IntoView {
+    let params = use_params_map();
+    let year = move || {
+        params.with(|params| {
+            params
+                .get("year")
+                .and_then(|year| year.parse::<i32>().ok())
+                .unwrap_or_default()
+        })
+    };
+
+    let set_frozen = create_server_action::<SetYearFrozen>();
+
+    let archive = create_resource(
+        move || (year(), set_frozen.version().get()),
+        |(year, _)| get_archive_year(year),
+    );
+    let leaderboard = create_resource(year, |year| get_leaderboard(year, ScoringMode::default()));
+
+    let message = move || {
+        set_frozen.value().get().map(|result| {
+            result
+                .unwrap_or(ArchiveResult::InternalServerError)
+                .to_string()
+        })
+    };
+
+    view! {
+        <section class="leaderboard last-year">
+            <h1>"Advent of Code " {year}</h1>
+            <Transition fallback=move || ()>
+                {move || archive.get().map(|archive| match archive {
+                    Ok(ArchiveYearData { summary: YearSummary { year, frozen, ended, .. }, can_manage }) => view! {
+                        {frozen.then(|| view! { <p class="frozen">"🔒 This year is archived and read-only."</p> })}
+                        {(can_manage && (ended || frozen)).then(|| view! {
+                            <ActionForm action=set_frozen>
+                                <p class="message">{message}</p>
+                                <input type="hidden" name="year" value=year/>
+                                <input type="hidden" name="frozen" value=(!frozen).to_string()/>
+                                <button type="submit">
+                                    {if frozen { "Unfreeze year" } else { "Freeze year" }}
+                                </button>
+                            </ActionForm>
+                        })}
+                    }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the year"</p> }.into_view(),
+                })}
+            </Transition>
+            <nav class="year-links">
+                <a href=move || format!("/hall-of-fame/{}", year())>"Hall of Fame"</a>
+                <a href=move || format!("/code?year={}&day=1", year())>"Solutions"</a>
+            </nav>
+            <table class="days">
+                <thead>
+                    <tr>
+                        <th>"Day"</th>
+                        <th>"Solutions"</th>
+                        <th>"Statistics"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {(1..=25u8)
+                        .map(|day| view! {
+                            <tr>
+                                <td>{day}</td>
+                                <td><a href=move || format!("/code?year={}&day={day}", year())>"Solutions"</a></td>
+                                <td><a href=move || format!("/stats/{}/{day}", year())>"Statistics"</a></td>
+                            </tr>
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+            <Transition fallback=move || ()>
+                {move || leaderboard.get().map(|leaderboard| match leaderboard {
+                    Ok(LeaderboardData { year, standings, .. }) if !standings.is_empty() => view! {
+                        <h2>"Final Leaderboard"</h2>
+                        <StandingsTable year=year standings=standings/>
+                        <ExportLinks year=year/>
+                    }.into_view(),
+                    Ok(_) => view! { <p>"No leaderboard data for this year."</p> }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the leaderboard"</p> }.into_view(),
+                })}
+            </Transition>
+        </section>
+    }
+}
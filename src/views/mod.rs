@@ -1,19 +1,33 @@
+mod admin;
+mod api_docs;
 mod code;
+mod hall_of_fame;
 mod home;
 mod impressum;
+mod last_years;
 mod login;
 mod logout;
 mod profile;
 mod register;
+mod search;
 mod settings;
+mod stats;
+mod teams;
 mod verify;
 
+pub use self::admin::*;
+pub use self::api_docs::*;
 pub use self::code::*;
+pub use self::hall_of_fame::*;
 pub use self::home::*;
 pub use self::impressum::*;
+pub use self::last_years::*;
 pub use self::login::*;
 pub use self::logout::*;
 pub use self::profile::*;
 pub use self::register::*;
+pub use self::search::*;
 pub use self::settings::*;
+pub use self::stats::*;
+pub use self::teams::*;
 pub use self::verify::*;
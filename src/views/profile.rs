@@ -1,10 +1,184 @@
 use leptos::*;
+use leptos_router::*;
 
+use crate::{
+    components::{Avatar, MemberLinkForm},
+    functions::{
+        get_profile, Activity, ActivityKind, ProfileData, ProfileResult, ProfileSolution,
+        UpdateProfile, YearStars,
+    },
+    utils::avatar::{AvatarError, MAX_AVATAR_UPLOAD},
+};
+
+/// Profile of the user given by the `username` parameter, or of the current user if there is
+/// none. The owner of a profile can edit it.
 #[component]
 pub fn ProfileView() -> impl IntoView {
+    let params = use_params_map();
+    let username =
+        move || params.with(|params| params.get("username").cloned().unwrap_or_default());
+    let query = use_query_map();
+    let avatar_error = move || {
+        query.with(|query| {
+            query
+                .get("avatar_error")
+                .and_then(|error| error.parse::<AvatarError>().ok())
+                .map(|error| error.to_string())
+        })
+    };
+
+    let update = create_server_action::<UpdateProfile>();
+    let message = move || {
+        update.value().get().map(|result| {
+            result
+                .unwrap_or(ProfileResult::InternalServerError)
+                .to_string()
+        })
+    };
+
+    let profile = create_resource(
+        move || (username(), update.version().get()),
+        |(username, _)| get_profile(username),
+    );
+
     view! {
-        <section>
-            Profile Page
+        <section class="profile-view">
+            <Transition fallback=move || ()>
+                {move || profile.get().map(|profile| match profile {
+                    Ok(Some(ProfileData {
+                        username,
+                        display_name,
+                        bio,
+                        bio_html,
+                        favourite_languages,
+                        member_id,
+                        stars,
+                        solutions,
+                        activity,
+                        own,
+                    })) => view! {
+                        <Avatar username=username.clone() size=128/>
+                        <h1>{display_name.clone().unwrap_or_else(|| username.clone())}</h1>
+                        <p class="username">"@" {username.clone()}</p>
+                        {bio_html.map(|html| view! { <div class="bio" inner_html=html></div> })}
+                        <dl>
+                            <dt>"Favourite languages"</dt>
+                            <dd>
+                                {if favourite_languages.is_empty() {
+                                    "-".to_string()
+                                } else {
+                                    favourite_languages.join(", ")
+                                }}
+                            </dd>
+                            <dt>"Advent of Code member"</dt>
+                            <dd>{member_id.map(|id| format!("#{id}")).unwrap_or_else(|| "-".to_string())}</dd>
+                        </dl>
+                        <h2>"Stars"</h2>
+                        {if stars.is_empty() {
+                            view! { <p>"No stars yet."</p> }.into_view()
+                        } else {
+                            view! {
+                                <table class="stars">
+                                    <tbody>
+                                        {stars
+                                            .into_iter()
+                                            .map(|YearStars { year, stars }| view! {
+                                                <tr>
+                                                    <td>{year}</td>
+                                                    <td>{stars} " ★"</td>
+                                                </tr>
+                                            })
+                                            .collect_view()}
+                                    </tbody>
+                                </table>
+                            }.into_view()
+                        }}
+                        <h2>"Solutions"</h2>
+                        {if solutions.is_empty() {
+                            view! { <p>"No solutions yet."</p> }.into_view()
+                        } else {
+                            let username = username.clone();
+                            view! {
+                                <ul class="solutions">
+                                    {solutions
+                                        .into_iter()
+                                        .map(|ProfileSolution { year, day, part, language }| view! {
+                                            <li>
+                                                <a href=format!("/code/{username}?year={year}&day={day}")>
+                                                    {year} " Day " {day} " Part " {part}
+                                                </a>
+                                                " (" {language} ")"
+                                            </li>
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }.into_view()
+                        }}
+                        <h2>"Recent activity"</h2>
+                        {if activity.is_empty() {
+                            view! { <p>"Nothing happened yet."</p> }.into_view()
+                        } else {
+                            view! {
+                                <ul class="activity">
+                                    {activity
+                                        .into_iter()
+                                        .map(|Activity { at, year, day, part, kind }| view! {
+                                            <li>
+                                                <span class="at">{at}</span>
+                                                {match kind {
+                                                    ActivityKind::Star => format!("Collected star {part} of {year} Day {day}"),
+                                                    ActivityKind::Solution { language } => format!("Submitted a solution for {year} Day {day} Part {part} in {language}"),
+                                                }}
+                                            </li>
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }.into_view()
+                        }}
+                        {own.then(|| view! {
+                            <details class="edit-profile">
+                                <summary>"Edit profile"</summary>
+                                <ActionForm action=update>
+                                    <p class="message">{message}</p>
+                                    <label>
+                                        <span>"Display name"</span>
+                                        <input type="text" name="display_name" value=display_name.unwrap_or_default()/>
+                                    </label>
+                                    <label>
+                                        <span>"Bio (Markdown)"</span>
+                                        <textarea name="bio" rows="6">{bio.unwrap_or_default()}</textarea>
+                                    </label>
+                                    <label>
+                                        <span>"Favourite languages (comma-separated)"</span>
+                                        <input
+                                            type="text"
+                                            name="favourite_languages"
+                                            placeholder="rust, python"
+                                            value=favourite_languages.join(", ")
+                                        />
+                                    </label>
+                                    <button type="submit" class="primary">"Save"</button>
+                                </ActionForm>
+                                <h2>"Avatar"</h2>
+                                <p class="message">{avatar_error}</p>
+                                <form method="post" action="/avatar" enctype="multipart/form-data">
+                                    <label>
+                                        <span>{format!("Image (PNG, JPEG, GIF or WebP, at most {} MiB)", MAX_AVATAR_UPLOAD / 1024 / 1024)}</span>
+                                        <input type="file" name="avatar" accept="image/png,image/jpeg,image/gif,image/webp" required/>
+                                    </label>
+                                    <button type="submit" class="primary">"Upload"</button>
+                                </form>
+                                <form method="post" action="/avatar/delete">
+                                    <button type="submit">"Use generated avatar"</button>
+                                </form>
+                                <MemberLinkForm/>
+                            </details>
+                        })}
+                    }.into_view(),
+                    Ok(None) => view! { <p>"This user does not exist."</p> }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the profile"</p> }.into_view(),
+                })}
+            </Transition>
         </section>
     }
 }
@@ -0,0 +1,115 @@
+use leptos::*;
+use leptos_router::*;
+
+use crate::functions::{search, SearchFilter, SearchHit, LANGUAGES};
+
+#[component]
+pub fn SearchView() -> impl IntoView {
+    let query = use_query_map();
+
+    let param = move |name: &str| {
+        query.with(|query| {
+            query
+                .get(name)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        })
+    };
+
+    let hits = create_resource(
+        move || {
+            (
+                param("q").unwrap_or_default(),
+                SearchFilter {
+                    year: param("year").and_then(|year| year.parse().ok()),
+                    day: param("day").and_then(|day| day.parse().ok()),
+                    language: param("language"),
+                    author: param("user"),
+                },
+            )
+        },
+        |(query, filter)| search(query, filter),
+    );
+
+    view! {
+        <section class="search-view">
+            <h1>"Search"</h1>
+            <Form method="GET" action="">
+                <input
+                    type="search"
+                    name="q"
+                    placeholder="e.g. dijkstra or nom"
+                    value=move || param("q").unwrap_or_default()
+                    required
+                />
+                <div class="filters">
+                    <input
+                        type="number"
+                        name="year"
+                        placeholder="Year"
+                        min="2015"
+                        value=move || param("year").unwrap_or_default()
+                    />
+                    <input
+                        type="number"
+                        name="day"
+                        placeholder="Day"
+                        min="1"
+                        max="25"
+                        value=move || param("day").unwrap_or_default()
+                    />
+                    <select name="language">
+                        <option value="">"Any language"</option>
+                        {LANGUAGES
+                            .into_iter()
+                            .map(|language| view! {
+                                <option
+                                    value=language
+                                    selected=move || param("language").as_deref() == Some(language)
+                                >
+                                    {language}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
+                    <input
+                        type="text"
+                        name="user"
+                        placeholder="User"
+                        value=move || param("user").unwrap_or_default()
+                    />
+                </div>
+                <button type="submit" class="primary">"Search"</button>
+            </Form>
+            <Transition fallback=move || ()>
+                {move || hits.get().map(|hits| match hits {
+                    Ok(hits) if !hits.is_empty() => view! {
+                        <ul class="search-results">
+                            {hits
+                                .into_iter()
+                                .map(|SearchHit { author, year, day, part, language, excerpt, in_writeup, occurrences, .. }| {
+                                    let link = format!("/code/{author}?year={year}&day={day}");
+
+                                    view! {
+                                        <li>
+                                            <a href=link>
+                                                {author} " – " {year} " Day " {day} " Part " {part}
+                                            </a>
+                                            <span class="meta">
+                                                {language} " · " {occurrences} " matches"
+                                                {in_writeup.then_some(" · in write-up")}
+                                            </span>
+                                            <pre><code>{excerpt}</code></pre>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                    }.into_view(),
+                    Ok(_) => view! { <p>"No results"</p> }.into_view(),
+                    Err(_) => view! { <p>"Search failed"</p> }.into_view(),
+                })}
+            </Transition>
+        </section>
+    }
+}
@@ -1,10 +1,405 @@
+use chrono_tz::TZ_VARIANTS;
 use leptos::*;
+use leptos_router::ActionForm;
+
+use crate::{
+    components::{AuditLogTable, VisibilitySelect},
+    contexts::PreferencesContext,
+    functions::{
+        get_account_status, get_api_tokens, get_default_visibility, get_own_audit_log,
+        get_sessions, AccountResult, AccountStatus, ApiTokenData, CancelAccountDeletion,
+        ChangePassword, CreateApiToken, HighlighterTheme, PreferencesResult,
+        RequestAccountDeletion, RevokeApiToken, RevokeSession, SecurityResult, SessionData,
+        SetDefaultVisibility, Theme, TokenResult, TokenScope, VisibilityResult,
+        DELETION_GRACE_DAYS, MAX_TOKEN_NAME_LENGTH, TAB_WIDTHS,
+    },
+    hooks::use_preferences_context,
+};
+
+/// Form for the display preferences, which are available to anonymous visitors as well.
+#[component]
+fn PreferencesForm() -> impl IntoView {
+    let PreferencesContext {
+        set_preferences,
+        preferences,
+    } = use_preferences_context();
+
+    let message = move || {
+        set_preferences.value().get().map(|result| {
+            result
+                .unwrap_or(PreferencesResult::InternalServerError)
+                .to_string()
+        })
+    };
+
+    view! {
+        <Transition fallback=move || ()>
+            {move || preferences.get().map(|preferences| {
+                let preferences = preferences.unwrap_or_default();
+
+                view! {
+                    <ActionForm action=set_preferences>
+                        <h2>"Display"</h2>
+                        <p class="message">{message}</p>
+                        <label>
+                            <span>"Theme"</span>
+                            <select name="theme">
+                                {Theme::ALL
+                                    .into_iter()
+                                    .map(|theme| view! {
+                                        <option value=theme.key() selected=theme == preferences.theme>{theme.to_string()}</option>
+                                    })
+                                    .collect_view()}
+                            </select>
+                        </label>
+                        <label>
+                            <span>"Code highlighting"</span>
+                            <select name="highlighter">
+                                {HighlighterTheme::ALL
+                                    .into_iter()
+                                    .map(|theme| view! {
+                                        <option value=theme.key() selected=theme == preferences.highlighter>{theme.to_string()}</option>
+                                    })
+                                    .collect_view()}
+                            </select>
+                        </label>
+                        <label>
+                            <span>"Tab width"</span>
+                            <select name="tab_width">
+                                {TAB_WIDTHS
+                                    .into_iter()
+                                    .map(|width| view! {
+                                        <option value=width selected=width == preferences.tab_width>{width}</option>
+                                    })
+                                    .collect_view()}
+                            </select>
+                        </label>
+                        <label>
+                            <span>"Time zone"</span>
+                            <select name="time_zone">
+                                {TZ_VARIANTS
+                                    .iter()
+                                    .map(|time_zone| {
+                                        let name = time_zone.name();
+                                        view! {
+                                            <option value=name selected=name == preferences.time_zone>{name}</option>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </select>
+                        </label>
+                        <button type="submit" class="primary">"Save"</button>
+                    </ActionForm>
+                }
+            })}
+        </Transition>
+    }
+}
+
+/// Password, active sessions and the security-relevant events of the account.
+#[component]
+fn SecurityPanel() -> impl IntoView {
+    let change_password = create_server_action::<ChangePassword>();
+    let revoke_session = create_server_action::<RevokeSession>();
+
+    let sessions = create_resource(
+        move || {
+            (
+                change_password.version().get(),
+                revoke_session.version().get(),
+            )
+        },
+        |_| get_sessions(),
+    );
+    let audit_log = create_resource(
+        move || {
+            (
+                change_password.version().get(),
+                revoke_session.version().get(),
+            )
+        },
+        |_| get_own_audit_log(),
+    );
+
+    let message = move || {
+        change_password
+            .value()
+            .get()
+            .or_else(|| revoke_session.value().get())
+            .map(|result| {
+                result
+                    .unwrap_or(SecurityResult::InternalServerError)
+                    .to_string()
+            })
+    };
+
+    view! {
+        <Transition fallback=move || ()>
+            {move || sessions.get().map(|sessions| match sessions {
+                Ok(Some(sessions)) => view! {
+                    <div class="security-panel">
+                        <h2>"Security"</h2>
+                        <p class="message">{message}</p>
+                        <ActionForm action=change_password>
+                            <h3>"Change Password"</h3>
+                            <label>
+                                <span>"Current password"</span>
+                                <input type="password" name="current" required/>
+                            </label>
+                            <label>
+                                <span>"New password"</span>
+                                <input type="password" name="password" required/>
+                            </label>
+                            <label>
+                                <span>"Confirm new password"</span>
+                                <input type="password" name="password_confirm" required/>
+                            </label>
+                            <button type="submit" class="primary">"Change password"</button>
+                        </ActionForm>
+                        <h3>"Sessions"</h3>
+                        <ul class="sessions">
+                            {sessions
+                                .into_iter()
+                                .map(|SessionData { id, created_at, current }| view! {
+                                    <li>
+                                        "Logged in " {created_at}
+                                        {if current {
+                                            view! { <span class="current">" (this session)"</span> }.into_view()
+                                        } else {
+                                            view! {
+                                                <ActionForm action=revoke_session>
+                                                    <input type="hidden" name="session" value=id/>
+                                                    <button type="submit">"Log out"</button>
+                                                </ActionForm>
+                                            }.into_view()
+                                        }}
+                                    </li>
+                                })
+                                .collect_view()}
+                        </ul>
+                        <h3>"Recent Activity"</h3>
+                        {move || audit_log.get().map(|entries| match entries {
+                            Ok(Some(entries)) => view! { <AuditLogTable entries=entries/> }.into_view(),
+                            _ => ().into_view(),
+                        })}
+                    </div>
+                }.into_view(),
+                _ => ().into_view(),
+            })}
+        </Transition>
+    }
+}
+
+/// Personal tokens for the REST API.
+#[component]
+fn ApiTokenPanel() -> impl IntoView {
+    let create_token = create_server_action::<CreateApiToken>();
+    let revoke_token = create_server_action::<RevokeApiToken>();
+
+    let tokens = create_resource(
+        move || (create_token.version().get(), revoke_token.version().get()),
+        |_| get_api_tokens(),
+    );
+
+    let result = move || {
+        create_token
+            .value()
+            .get()
+            .or_else(|| revoke_token.value().get())
+            .map(|result| result.unwrap_or(TokenResult::InternalServerError))
+    };
+
+    view! {
+        <Transition fallback=move || ()>
+            {move || tokens.get().map(|tokens| match tokens {
+                Ok(Some(tokens)) => view! {
+                    <div class="api-token-panel">
+                        <h2>"API Tokens"</h2>
+                        <p>
+                            "Tokens give scripts access to the REST API below "
+                            <code>"/api/v1"</code>
+                            ", sent as "
+                            <code>"Authorization: Bearer <token>"</code>
+                            " header. See the "
+                            <a href="/api-docs">"API documentation"</a>
+                            "."
+                        </p>
+                        <p class="message">{move || result().map(|result| result.to_string())}</p>
+                        {move || match result() {
+                            Some(TokenResult::Created(token)) => view! {
+                                <pre class="new-token">{token}</pre>
+                            }.into_view(),
+                            _ => ().into_view(),
+                        }}
+                        <ActionForm action=create_token>
+                            <label>
+                                <span>"Name"</span>
+                                <input
+                                    type="text"
+                                    name="name"
+                                    maxlength=MAX_TOKEN_NAME_LENGTH
+                                    placeholder="e.g., editor"
+                                    required
+                                />
+                            </label>
+                            <label>
+                                <span>"Scope"</span>
+                                <select name="scope">
+                                    {TokenScope::ALL
+                                        .into_iter()
+                                        .map(|scope| view! { <option value=scope.key()>{scope.to_string()}</option> })
+                                        .collect_view()}
+                                </select>
+                            </label>
+                            <button type="submit" class="primary">"Create token"</button>
+                        </ActionForm>
+                        <ul class="tokens">
+                            {tokens
+                                .into_iter()
+                                .map(|ApiTokenData { id, name, scope, created_at, last_used_at }| view! {
+                                    <li>
+                                        <span class="name">{name}</span>
+                                        <span class="details">
+                                            {scope.to_string()} ", created " {created_at} ", "
+                                            {match last_used_at {
+                                                Some(last_used_at) => format!("last used {last_used_at}"),
+                                                None => "never used".to_string(),
+                                            }}
+                                        </span>
+                                        <ActionForm action=revoke_token>
+                                            <input type="hidden" name="token" value=id/>
+                                            <button type="submit" class="danger">"Revoke"</button>
+                                        </ActionForm>
+                                    </li>
+                                })
+                                .collect_view()}
+                        </ul>
+                    </div>
+                }.into_view(),
+                _ => ().into_view(),
+            })}
+        </Transition>
+    }
+}
+
+/// Download of the stored data and deletion of the account.
+#[component]
+fn AccountPanel() -> impl IntoView {
+    let request_deletion = create_server_action::<RequestAccountDeletion>();
+    let cancel_deletion = create_server_action::<CancelAccountDeletion>();
+
+    let status = create_resource(
+        move || {
+            (
+                request_deletion.version().get(),
+                cancel_deletion.version().get(),
+            )
+        },
+        |_| get_account_status(),
+    );
+
+    let message = move || {
+        request_deletion
+            .value()
+            .get()
+            .or_else(|| cancel_deletion.value().get())
+            .map(|result| {
+                result
+                    .unwrap_or(AccountResult::InternalServerError)
+                    .to_string()
+            })
+    };
+
+    view! {
+        <Transition fallback=move || ()>
+            {move || status.get().map(|status| match status {
+                Ok(Some(AccountStatus { deletion_at })) => view! {
+                    <div class="account-panel">
+                        <h2>"Your Data"</h2>
+                        <p>"Download everything stored about you, including your solutions."</p>
+                        <p class="downloads">
+                            <a href="/account/export.json" rel="external" download>"JSON"</a>
+                            <a href="/account/export.zip" rel="external" download>"ZIP"</a>
+                        </p>
+                        <h2>"Delete Account"</h2>
+                        <p class="message">{message}</p>
+                        {match deletion_at {
+                            Some(deletion_at) => view! {
+                                <p>"Your account will be deleted on " {deletion_at} "."</p>
+                                <ActionForm action=cancel_deletion>
+                                    <button type="submit" class="primary">"Keep my account"</button>
+                                </ActionForm>
+                            }.into_view(),
+                            None => view! {
+                                <p>
+                                    {format!("Your account and everything belonging to it will be deleted after {DELETION_GRACE_DAYS} days. Until then, you can log in and cancel the deletion.")}
+                                </p>
+                                <ActionForm action=request_deletion>
+                                    <label>
+                                        <span>"Password"</span>
+                                        <input type="password" name="password" required/>
+                                    </label>
+                                    <button type="submit" class="danger">"Delete account"</button>
+                                </ActionForm>
+                            }.into_view(),
+                        }}
+                    </div>
+                }.into_view(),
+                _ => ().into_view(),
+            })}
+        </Transition>
+    }
+}
 
 #[component]
 pub fn SettingsView() -> impl IntoView {
+    let set_default_visibility = create_server_action::<SetDefaultVisibility>();
+
+    let default_visibility = create_resource(
+        move || set_default_visibility.version().get(),
+        |_| get_default_visibility(),
+    );
+
+    let result = move || match set_default_visibility.value().get() {
+        Some(Ok(result)) => Some(result),
+        Some(Err(_)) => Some(VisibilityResult::InternalServerError),
+        None => None,
+    };
+
+    let is_ok = move || matches!(result(), Some(VisibilityResult::Ok));
+
     view! {
-        <section>
-            Settings Page
+        <section class="settings-view">
+            <h1>"Settings"</h1>
+            <PreferencesForm/>
+            <Transition fallback=move || ()>
+                {move || default_visibility.get().map(|visibility| match visibility {
+                    Ok(Some(visibility)) => view! {
+                        <ActionForm action=set_default_visibility>
+                            <h2>"Solutions"</h2>
+                            <Show
+                                when=move || result().is_some()
+                                fallback=|| view! { <span></span> }
+                            >
+                                <div
+                                    class="result"
+                                    class:error=move || !is_ok()
+                                    class:success=is_ok
+                                >
+                                    {move || result().map(|result| result.to_string())}
+                                </div>
+                            </Show>
+                            <p>"Default visibility of your solutions. Each solution can override it."</p>
+                            <VisibilitySelect selected=Some(visibility)/>
+                            <button type="submit" class="primary">"Save"</button>
+                        </ActionForm>
+                    }.into_view(),
+                    _ => view! { <p>"Log in to set the default visibility of your solutions."</p> }.into_view(),
+                })}
+            </Transition>
+            <SecurityPanel/>
+            <ApiTokenPanel/>
+            <AccountPanel/>
         </section>
     }
 }
@@ -0,0 +1,94 @@
+use chrono::Utc;
+use leptos::*;
+use leptos_router::*;
+
+use crate::{
+    components::BarChart,
+    functions::get_day_stats,
+    utils::{
+        calendar::{current_event_year, is_unlocked},
+        scoring::format_duration,
+        stats::{histogram_labels, DayStats, Fastest, PartStats},
+    },
+};
+
+#[component]
+pub fn StatsView() -> impl IntoView {
+    let params = use_params_map();
+    let year = move || {
+        params.with(|params| {
+            params
+                .get("year")
+                .and_then(|year| year.parse().ok())
+                .unwrap_or_else(current_event_year)
+        })
+    };
+    let day = move || {
+        params.with(|params| {
+            params
+                .get("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .unwrap_or(1)
+        })
+    };
+
+    let stats = create_resource(
+        move || (year(), day()),
+        |(year, day)| get_day_stats(year, day),
+    );
+
+    view! {
+        <section class="stats-view">
+            <h1>"Statistics " {year} " Day " {day}</h1>
+            <nav class="day-navigation">
+                {move || (day() > 1).then(|| view! {
+                    <a href=format!("/stats/{}/{}", year(), day() - 1)>"← Previous day"</a>
+                })}
+                {move || is_unlocked(year(), day() + 1, Utc::now()).then(|| view! {
+                    <a href=format!("/stats/{}/{}", year(), day() + 1)>"Next day →"</a>
+                })}
+            </nav>
+            <Transition fallback=move || ()>
+                {move || stats.get().map(|stats| match stats {
+                    Ok(Some(DayStats { members, one_star, two_stars, parts, .. })) => view! {
+                        <p>
+                            {two_stars} " of " {members} " members got both stars, "
+                            {one_star} " only got the first one."
+                        </p>
+                        <BarChart
+                            title="Stars per member"
+                            labels=vec!["0 stars".to_string(), "1 star".to_string(), "2 stars".to_string()]
+                            values=vec![members - one_star - two_stars, one_star, two_stars]
+                        />
+                        {parts
+                            .into_iter()
+                            .map(|PartStats { part, solved, fastest, median, histogram }| view! {
+                                <h2>"Part " {part}</h2>
+                                <dl>
+                                    <dt>"Solved by"</dt>
+                                    <dd>{solved}</dd>
+                                    <dt>"Fastest"</dt>
+                                    <dd>
+                                        {match fastest {
+                                            Some(Fastest { name, seconds }) => format!("{name} ({})", format_duration(seconds)),
+                                            None => "-".to_string(),
+                                        }}
+                                    </dd>
+                                    <dt>"Median time"</dt>
+                                    <dd>{median.map(format_duration).unwrap_or_else(|| "-".to_string())}</dd>
+                                </dl>
+                                <BarChart
+                                    title=format!("Solve times of part {part}")
+                                    labels=histogram_labels()
+                                    values=histogram
+                                />
+                            })
+                            .collect_view()}
+                    }.into_view(),
+                    Ok(None) => view! { <p>"No leaderboard data for this day yet."</p> }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the statistics"</p> }.into_view(),
+                })}
+            </Transition>
+        </section>
+    }
+}
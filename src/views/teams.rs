@@ -0,0 +1,74 @@
+use leptos::*;
+use leptos_router::*;
+
+use crate::{
+    components::{TeamPanel, TeamStandingsTable},
+    functions::get_team_leaderboard,
+    utils::{calendar::current_event_year, teams::Aggregation},
+};
+
+const AGGREGATIONS: [Aggregation; 4] = [
+    Aggregation::Sum,
+    Aggregation::Average,
+    Aggregation::Best(3),
+    Aggregation::Best(5),
+];
+
+#[component]
+pub fn TeamsView() -> impl IntoView {
+    let query = use_query_map();
+    let year = move || {
+        query.with(|query| {
+            query
+                .get("year")
+                .and_then(|year| year.parse().ok())
+                .unwrap_or_else(current_event_year)
+        })
+    };
+    let aggregation = move || {
+        query.with(|query| {
+            query
+                .get("aggregation")
+                .and_then(|aggregation| aggregation.parse().ok())
+                .unwrap_or_default()
+        })
+    };
+
+    let (version, set_version) = create_signal(0);
+
+    let standings = create_resource(
+        move || (year(), aggregation(), version()),
+        |(year, aggregation, _)| get_team_leaderboard(year, aggregation),
+    );
+
+    view! {
+        <section class="leaderboard teams-view">
+            <h1>"Teams " {year}</h1>
+            <Form method="GET" action="">
+                <input type="hidden" name="year" value=year/>
+                <label for="aggregation">"Team score"</label>
+                <select id="aggregation" name="aggregation">
+                    {AGGREGATIONS
+                        .into_iter()
+                        .map(|option| view! {
+                            <option value=option.key() selected=move || aggregation() == option>
+                                {option.to_string()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+                <input type="submit" value="Show"/>
+            </Form>
+            <Transition fallback=move || ()>
+                {move || standings.get().map(|standings| match standings {
+                    Ok(standings) if !standings.is_empty() => view! {
+                        <TeamStandingsTable standings=standings/>
+                    }.into_view(),
+                    Ok(_) => view! { <p>"No teams yet."</p> }.into_view(),
+                    Err(_) => view! { <p>"Failed to load the team leaderboard"</p> }.into_view(),
+                })}
+            </Transition>
+            <TeamPanel version=set_version/>
+        </section>
+    }
+}